@@ -4,14 +4,14 @@ use cosmwasm_std::entry_point;
 use crate::error::ContractError;
 use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 use crate::state::CONFIG;
-use crate::{entrypoints::*, Config};
+use crate::{entrypoints::*, Config, PoolCreationPolicy};
 
 use cosmwasm_std::{to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
-use cw2::set_contract_version;
+use cw2::{get_contract_version, set_contract_version};
 
 // version info for migration info
-const CONTRACT_NAME: &str = "crates.io:oraiswap_v3";
-const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+pub const CONTRACT_NAME: &str = "crates.io:oraiswap_v3";
+pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -25,6 +25,10 @@ pub fn instantiate(
         fee_tiers: vec![],
         admin: info.sender,
         protocol_fee: msg.protocol_fee,
+        guardian: None,
+        pool_creation_policy: PoolCreationPolicy::Open,
+        pool_creation_fee: None,
+        max_tick_cross: None,
     };
     CONFIG.save(deps.storage, &config)?;
 
@@ -39,11 +43,33 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::ChangeAdmin { new_admin } => change_admin(deps, info, new_admin),
+        ExecuteMsg::ChangeAdmin { new_admin } => {
+            propose_admin(deps, env, info, new_admin.into_string(), None)
+        }
+        ExecuteMsg::ProposeAdmin { new_admin, expires } => {
+            propose_admin(deps, env, info, new_admin, expires)
+        }
+        ExecuteMsg::AcceptAdmin {} => accept_admin(deps, env, info),
+        ExecuteMsg::CancelAdminProposal {} => cancel_admin_proposal(deps, info),
         ExecuteMsg::WithdrawProtocolFee { pool_key } => withdraw_protocol_fee(deps, info, pool_key),
         ExecuteMsg::ChangeProtocolFee { protocol_fee } => {
             change_protocol_fee(deps, info, protocol_fee)
         }
+        ExecuteMsg::ChangePoolProtocolFee {
+            pool_key,
+            protocol_fee,
+        } => change_pool_protocol_fee(deps, info, pool_key, protocol_fee),
+        ExecuteMsg::ChangePoolDynamicFee {
+            pool_key,
+            dynamic_fee,
+        } => change_pool_dynamic_fee(deps, env, info, pool_key, dynamic_fee),
+        ExecuteMsg::ChangePoolCreationPolicy { policy } => {
+            change_pool_creation_policy(deps, info, policy)
+        }
+        ExecuteMsg::ChangePoolCreationFee { fee } => change_pool_creation_fee(deps, info, fee),
+        ExecuteMsg::ChangeMaxTickCross { max_tick_cross } => {
+            change_max_tick_cross(deps, info, max_tick_cross)
+        }
         ExecuteMsg::ChangeFeeReceiver {
             pool_key,
             fee_receiver,
@@ -66,12 +92,63 @@ pub fn execute(
             slippage_limit_lower,
             slippage_limit_upper,
         ),
+        ExecuteMsg::CreatePositionByAmounts {
+            pool_key,
+            lower_tick,
+            upper_tick,
+            amount_x_desired,
+            amount_y_desired,
+            amount_x_min,
+            amount_y_min,
+        } => create_position_by_amounts(
+            deps,
+            env,
+            info,
+            pool_key,
+            lower_tick,
+            upper_tick,
+            amount_x_desired,
+            amount_y_desired,
+            amount_x_min,
+            amount_y_min,
+        ),
+        ExecuteMsg::IncreaseLiquidity {
+            index,
+            liquidity_delta,
+            slippage_limit_lower,
+            slippage_limit_upper,
+        } => increase_liquidity(
+            deps,
+            env,
+            info,
+            index,
+            liquidity_delta,
+            slippage_limit_lower,
+            slippage_limit_upper,
+        ),
+        ExecuteMsg::DecreaseLiquidity {
+            index,
+            liquidity_delta,
+            min_amount_x,
+            min_amount_y,
+        } => decrease_liquidity(
+            deps,
+            env,
+            info,
+            index,
+            liquidity_delta,
+            min_amount_x,
+            min_amount_y,
+        ),
         ExecuteMsg::Swap {
             pool_key,
             x_to_y,
             amount,
             by_amount_in,
             sqrt_price_limit,
+            recipient,
+            deadline,
+            allow_partial,
         } => swap(
             deps,
             env,
@@ -81,12 +158,17 @@ pub fn execute(
             amount,
             by_amount_in,
             sqrt_price_limit,
+            recipient,
+            deadline,
+            allow_partial,
         ),
         ExecuteMsg::SwapRoute {
             amount_in,
             expected_amount_out,
             slippage,
             swaps,
+            recipient,
+            deadline,
         } => swap_route(
             deps,
             env,
@@ -95,11 +177,41 @@ pub fn execute(
             expected_amount_out,
             slippage,
             swaps,
+            recipient,
+            deadline,
         ),
+        ExecuteMsg::SwapRouteExactOut {
+            amount_out,
+            max_amount_in,
+            swaps,
+            recipient,
+            deadline,
+        } => swap_route_exact_out(
+            deps,
+            env,
+            info,
+            amount_out,
+            max_amount_in,
+            swaps,
+            recipient,
+            deadline,
+        ),
+        ExecuteMsg::SwapSplit {
+            legs,
+            min_amount_out,
+            recipient,
+            deadline,
+        } => swap_split(deps, env, info, legs, min_amount_out, recipient, deadline),
+        ExecuteMsg::Receive(cw20_msg) => receive_cw20(deps, env, info, cw20_msg),
         ExecuteMsg::TransferPosition { index, receiver } => {
             transfer_position(deps, env, info, index, receiver)
         }
+        ExecuteMsg::ChangeGuardian { guardian } => change_guardian(deps, info, guardian),
+        ExecuteMsg::Pause { scope } => pause(deps, info, scope),
+        ExecuteMsg::Unpause { scope } => unpause(deps, info, scope),
+        ExecuteMsg::MigratePositions { limit } => migrate_positions(deps, info, limit),
         ExecuteMsg::ClaimFee { index } => claim_fee(deps, env, info, index),
+        ExecuteMsg::ClaimIncentives { index } => claim_incentives(deps, env, info, index),
         ExecuteMsg::RemovePosition { index } => remove_position(deps, env, info, index),
         ExecuteMsg::CreatePool {
             token_0,
@@ -110,12 +222,51 @@ pub fn execute(
         } => create_pool(
             deps,
             env,
+            info,
             token_0,
             token_1,
             fee_tier,
             init_sqrt_price,
             init_tick,
         ),
+        ExecuteMsg::CreatePoolWithSqrtPrice {
+            token_0,
+            token_1,
+            fee_tier,
+            init_sqrt_price,
+        } => create_pool_with_sqrt_price(
+            deps,
+            env,
+            info,
+            token_0,
+            token_1,
+            fee_tier,
+            init_sqrt_price,
+        ),
+        ExecuteMsg::IncreaseObservationCardinality {
+            pool_key,
+            observation_cardinality_next,
+        } => increase_observation_cardinality(deps, env, pool_key, observation_cardinality_next),
+        ExecuteMsg::CreateIncentive {
+            pool_key,
+            reward_token,
+            reward_per_sec,
+            start_timestamp,
+            end_timestamp,
+        } => create_incentive(
+            deps,
+            env,
+            info,
+            pool_key,
+            reward_token,
+            reward_per_sec,
+            start_timestamp,
+            end_timestamp,
+        ),
+        ExecuteMsg::WithdrawUnallocatedIncentive {
+            pool_key,
+            incentive_id,
+        } => withdraw_unallocated_incentive(deps, env, info, pool_key, incentive_id),
         ExecuteMsg::AddFeeTier { fee_tier } => add_fee_tier(deps, env, info, fee_tier),
         ExecuteMsg::RemoveFeeTier { fee_tier } => remove_fee_tier(deps, env, info, fee_tier),
         ExecuteMsg::Approve {
@@ -160,6 +311,7 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::Admin {} => to_binary(&query_admin(deps)?),
         QueryMsg::ProtocolFee {} => to_binary(&get_protocol_fee(deps)?),
         QueryMsg::Position { owner_id, index } => to_binary(&get_position(deps, owner_id, index)?),
+        QueryMsg::TokenId { owner_id, index } => to_binary(&query_token_id(deps, owner_id, index)?),
         QueryMsg::Positions {
             owner_id,
             limit,
@@ -172,6 +324,11 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             fee_tier,
         } => to_binary(&get_pool(deps, token_0, token_1, fee_tier)?),
         QueryMsg::Pools { limit, start_after } => to_binary(&get_pools(deps, limit, start_after)?),
+        QueryMsg::PoolPositions {
+            pool_key,
+            start_after,
+            limit,
+        } => to_binary(&get_pool_positions(deps, pool_key, start_after, limit)?),
         QueryMsg::Tick { key, index } => to_binary(&get_tick(deps, key, index)?),
         QueryMsg::IsTickInitialized { key, index } => {
             to_binary(&is_tick_initialized(deps, key, index)?)
@@ -224,9 +381,63 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             by_amount_in,
             sqrt_price_limit,
         )?),
+        QueryMsg::QuotePartial {
+            pool_key,
+            x_to_y,
+            amount,
+            by_amount_in,
+            sqrt_price_limit,
+        } => to_binary(&quote_partial(
+            deps,
+            env,
+            pool_key,
+            x_to_y,
+            amount,
+            by_amount_in,
+            sqrt_price_limit,
+        )?),
         QueryMsg::QuoteRoute { amount_in, swaps } => {
             to_binary(&quote_route(deps, env, amount_in, swaps)?)
         }
+        QueryMsg::QuoteRouteExactOut { amount_out, swaps } => {
+            to_binary(&quote_route_exact_out(deps, env, amount_out, swaps)?)
+        }
+        QueryMsg::QuoteSwapSplit {
+            amount_in,
+            routes,
+            parts,
+        } => to_binary(&quote_swap_split(deps, env, amount_in, routes, parts)?),
+        QueryMsg::FindBestRoute {
+            offer_token,
+            ask_token,
+            amount,
+            max_hops,
+            limit,
+        } => to_binary(&find_best_route(
+            deps,
+            env,
+            offer_token,
+            ask_token,
+            amount,
+            max_hops,
+            limit,
+        )?),
+        QueryMsg::Observe {
+            pool_key,
+            seconds_agos,
+        } => to_binary(&observe(deps, env, pool_key, seconds_agos)?),
+        QueryMsg::PositionSecondsInside { owner_id, index } => {
+            to_binary(&get_position_seconds_inside(deps, env, owner_id, index)?)
+        }
+        QueryMsg::PositionIncentives { owner_id, index } => {
+            to_binary(&get_position_incentives(deps, env, owner_id, index)?)
+        }
+        QueryMsg::PoolCreationPolicy {} => to_binary(&query_pool_creation_policy(deps)?),
+        QueryMsg::PoolCreationFee {} => to_binary(&query_pool_creation_fee(deps)?),
+        QueryMsg::MaxTickCross {} => to_binary(&query_max_tick_cross(deps)?),
+        QueryMsg::PendingAdmin {} => to_binary(&query_pending_admin(deps)?),
+        QueryMsg::Guardian {} => to_binary(&query_guardian(deps)?),
+        QueryMsg::IsPaused { scope } => to_binary(&is_paused(deps, scope)?),
         QueryMsg::OwnerOf {
             token_id,
             include_expired,
@@ -271,6 +482,27 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
-    Ok(Response::default())
+pub fn migrate(mut deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    let stored = get_contract_version(deps.storage)?;
+    if stored.contract != CONTRACT_NAME {
+        return Err(ContractError::InvalidMigrationContract {
+            contract: stored.contract,
+        });
+    }
+
+    let from = parse_version(&stored.version)?;
+    let to = parse_version(CONTRACT_VERSION)?;
+    let applied = run_migrations(deps.branch(), &env, &msg, from, to)?;
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let mut response = Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("from_version", stored.version)
+        .add_attribute("to_version", CONTRACT_VERSION);
+    if !applied.is_empty() {
+        response = response.add_attribute("applied", applied.join(","));
+    }
+
+    Ok(response)
 }
@@ -7,12 +7,13 @@ use decimal::{CheckedOps, Decimal};
 
 use crate::{
     check_tick, compute_swap_step,
-    interface::{Approval, Asset, AssetInfo, CalculateSwapResult, SwapHop},
+    interface::{Approval, Asset, AssetInfo, CalculateSwapResult, PartialFillReason, SwapHop},
+    liquidity::Liquidity,
     sqrt_price::{get_max_tick, get_min_tick, SqrtPrice},
-    state::{self, CONFIG, POOLS},
+    state::{self, CONFIG, MAX_SPLIT_ROUTES, POOLS},
     token_amount::TokenAmount,
-    ContractError, PoolKey, Position, Tick, UpdatePoolTick, MAX_SQRT_PRICE, MAX_TICKMAP_QUERY_SIZE,
-    MIN_SQRT_PRICE,
+    ContractError, FeeTier, PauseScope, Pool, PoolKey, Position, Tick, UpdatePoolTick,
+    MAX_SQRT_PRICE, MAX_TICKMAP_QUERY_SIZE, MIN_SQRT_PRICE,
 };
 
 pub trait TimeStampExt {
@@ -25,22 +26,96 @@ impl TimeStampExt for Timestamp {
     }
 }
 
+/// Rejects the call when the block time is past the optional deadline.
+pub fn check_deadline(block: &BlockInfo, deadline: Option<Timestamp>) -> Result<(), ContractError> {
+    match deadline {
+        Some(deadline) if block.time > deadline => Err(ContractError::DeadlineExceeded),
+        _ => Ok(()),
+    }
+}
+
 pub fn create_tick(
     store: &mut dyn Storage,
     current_timestamp: u64,
     pool_key: &PoolKey,
+    pool: &Pool,
     index: i32,
 ) -> Result<Tick, ContractError> {
     check_tick(index, pool_key.fee_tier.tick_spacing)?;
-    let pool = state::get_pool(store, pool_key)?;
 
-    let tick = Tick::create(index, &pool, current_timestamp);
+    let tick = Tick::create(index, pool, current_timestamp);
     state::add_tick(store, pool_key, index, &tick)?;
     state::flip_bitmap(store, true, index, pool_key.fee_tier.tick_spacing, pool_key)?;
 
     Ok(tick)
 }
 
+#[allow(clippy::too_many_arguments)]
+pub fn create_position_internal(
+    store: &mut dyn Storage,
+    current_timestamp: u64,
+    current_block_number: u64,
+    owner: &Addr,
+    pool_key: &PoolKey,
+    lower_tick: i32,
+    upper_tick: i32,
+    liquidity_delta: Liquidity,
+    slippage_limit_lower: SqrtPrice,
+    slippage_limit_upper: SqrtPrice,
+) -> Result<(u64, Position, Pool, TokenAmount, TokenAmount), ContractError> {
+    state::check_not_paused(store, &PauseScope::PositionCreation, pool_key)?;
+
+    // liquidity delta = 0 => return
+    if liquidity_delta.is_zero() {
+        return Err(ContractError::InsufficientLiquidity {});
+    }
+
+    if lower_tick == upper_tick {
+        return Err(ContractError::InvalidTickIndex {});
+    }
+    let pool_key_db = pool_key.key();
+    let mut pool = POOLS
+        .load(store, &pool_key_db)
+        .map_err(|_| ContractError::PoolNotFound {})?;
+    state::write_observation(store, pool_key, &pool, current_timestamp)?;
+
+    // new ticks snapshot the global accumulators, so they have to be up to date
+    pool.update_seconds_per_liquidity_global(current_timestamp)?;
+    pool.update_global_incentives(current_timestamp)?;
+
+    let mut lower_tick = match state::get_tick(store, pool_key, lower_tick) {
+        Ok(tick) => tick,
+        _ => create_tick(store, current_timestamp, pool_key, &pool, lower_tick)?,
+    };
+
+    let mut upper_tick = match state::get_tick(store, pool_key, upper_tick) {
+        Ok(tick) => tick,
+        _ => create_tick(store, current_timestamp, pool_key, &pool, upper_tick)?,
+    };
+
+    let (position, x, y) = Position::create(
+        &mut pool,
+        pool_key.clone(),
+        &mut lower_tick,
+        &mut upper_tick,
+        current_timestamp,
+        liquidity_delta,
+        slippage_limit_lower,
+        slippage_limit_upper,
+        current_block_number,
+        pool_key.fee_tier.tick_spacing,
+    )?;
+
+    POOLS.save(store, &pool_key_db, &pool)?;
+
+    let position_id = state::add_position(store, owner, &position)?;
+
+    state::update_tick(store, pool_key, lower_tick.index, &lower_tick)?;
+    state::update_tick(store, pool_key, upper_tick.index, &upper_tick)?;
+
+    Ok((position_id, position, pool, x, y))
+}
+
 pub fn calculate_swap(
     store: &dyn Storage,
     current_timestamp: u64,
@@ -74,6 +149,20 @@ pub fn calculate_swap(
         get_max_tick(pool_key.fee_tier.tick_spacing)
     };
 
+    pool.update_seconds_per_liquidity_global(current_timestamp)?;
+    pool.update_global_incentives(current_timestamp)?;
+
+    let config = CONFIG.load(store)?;
+    let protocol_fee = pool.protocol_fee.unwrap_or(config.protocol_fee);
+    let max_tick_cross = config.max_tick_cross() as usize;
+    let mut max_ticks_crossed = false;
+    let mut partial_fill = None;
+    let fee_tier = FeeTier {
+        fee: pool.swap_fee(pool_key.fee_tier.fee, current_timestamp),
+        ..pool_key.fee_tier
+    };
+    let start_tick_index = pool.current_tick_index;
+
     let mut remaining_amount = amount;
 
     let mut total_amount_in = TokenAmount::new(0);
@@ -81,16 +170,27 @@ pub fn calculate_swap(
 
     let event_start_sqrt_price = pool.sqrt_price;
     let mut event_fee_amount = TokenAmount::new(0);
+    let event_start_protocol_fee = if x_to_y {
+        pool.fee_protocol_token_x
+    } else {
+        pool.fee_protocol_token_y
+    };
 
     while !remaining_amount.is_zero() {
-        let (swap_limit, limiting_tick) = state::get_closer_limit(
+        let (swap_limit, limiting_tick) = match state::get_closer_limit(
             store,
             sqrt_price_limit,
             x_to_y,
             pool.current_tick_index,
             pool_key.fee_tier.tick_spacing,
             pool_key,
-        )?;
+        ) {
+            Err(ContractError::TickLimitReached) => {
+                partial_fill = Some(tick_limit_reason(&pool));
+                break;
+            }
+            closer_limit => closer_limit?,
+        };
 
         let result = compute_swap_step(
             pool.sqrt_price,
@@ -98,7 +198,7 @@ pub fn calculate_swap(
             pool.liquidity,
             remaining_amount,
             by_amount_in,
-            pool_key.fee_tier.fee,
+            fee_tier.fee,
         )?;
 
         // make remaining amount smaller
@@ -112,7 +212,7 @@ pub fn calculate_swap(
                 .map_err(|_| ContractError::Sub)?;
         }
 
-        pool.add_fee(result.fee_amount, x_to_y, CONFIG.load(store)?.protocol_fee)?;
+        pool.add_fee(result.fee_amount, x_to_y, protocol_fee)?;
         event_fee_amount += result.fee_amount;
 
         pool.sqrt_price = result.next_sqrt_price;
@@ -120,9 +220,10 @@ pub fn calculate_swap(
         total_amount_in += result.amount_in + result.fee_amount;
         total_amount_out += result.amount_out;
 
-        // Fail if price would go over swap limit
+        // Stop if price would go over swap limit
         if pool.sqrt_price == sqrt_price_limit && !remaining_amount.is_zero() {
-            return Err(ContractError::PriceLimitReached {});
+            partial_fill = Some(PartialFillReason::PriceLimit);
+            break;
         }
 
         let mut tick_update = {
@@ -146,8 +247,8 @@ pub fn calculate_swap(
             by_amount_in,
             x_to_y,
             current_timestamp,
-            CONFIG.load(store)?.protocol_fee,
-            pool_key.fee_tier,
+            protocol_fee,
+            fee_tier,
         )?;
 
         remaining_amount = amount_after_tick_update;
@@ -155,7 +256,11 @@ pub fn calculate_swap(
 
         if let UpdatePoolTick::TickInitialized(tick) = tick_update {
             if has_crossed {
-                ticks.push(tick)
+                ticks.push(tick);
+                if ticks.len() > max_tick_cross {
+                    max_ticks_crossed = true;
+                    break;
+                }
             }
         }
 
@@ -165,38 +270,69 @@ pub fn calculate_swap(
         };
 
         if reached_tick_limit {
-            return Err(ContractError::TickLimitReached {});
+            partial_fill = Some(tick_limit_reason(&pool));
+            break;
         }
     }
-    if total_amount_out.is_zero() {
+    if total_amount_out.is_zero() && partial_fill.is_none() {
         return Err(ContractError::NoGainSwap {});
     }
 
+    if pool.dynamic_fee.is_some() {
+        pool.volatility
+            .record(start_tick_index, pool.current_tick_index);
+    }
+
+    let event_end_protocol_fee = if x_to_y {
+        pool.fee_protocol_token_x
+    } else {
+        pool.fee_protocol_token_y
+    };
+
     Ok(CalculateSwapResult {
         amount_in: total_amount_in,
         amount_out: total_amount_out,
         start_sqrt_price: event_start_sqrt_price,
         target_sqrt_price: pool.sqrt_price,
         fee: event_fee_amount,
+        protocol_fee: event_end_protocol_fee - event_start_protocol_fee,
+        swap_fee: fee_tier.fee,
         pool,
         ticks,
+        max_ticks_crossed,
+        partial_fill,
     })
 }
 
+/// A swap stopped at the lowest or highest tick ran out of liquidity unless the pool still has some.
+fn tick_limit_reason(pool: &Pool) -> PartialFillReason {
+    if pool.liquidity.is_zero() {
+        PartialFillReason::LiquidityExhausted
+    } else {
+        PartialFillReason::TickLimit
+    }
+}
+
+/// Calculates the swap and persists the updated pool and crossed ticks without moving any funds.
+/// With `allow_partial` the swap may stop before using the whole amount, see `partial_fill`.
 #[allow(clippy::too_many_arguments)]
-pub fn swap_internal(
+pub fn execute_swap(
     store: &mut dyn Storage,
-    api: &dyn Api,
-    info: &MessageInfo,
-    msgs: &mut Vec<CosmosMsg>,
-    contract_address: &Addr,
     current_timestamp: u64,
     pool_key: &PoolKey,
     x_to_y: bool,
     amount: TokenAmount,
     by_amount_in: bool,
     sqrt_price_limit: SqrtPrice,
+    allow_partial: bool,
 ) -> Result<CalculateSwapResult, ContractError> {
+    // every swap path ends up here, so this also guards `swap_internal`, routes and cw20 hooks
+    state::check_not_paused(store, &PauseScope::Swaps, pool_key)?;
+
+    // the oracle records the price that was in effect up to this swap
+    let pool = state::get_pool(store, pool_key)?;
+    state::write_observation(store, pool_key, &pool, current_timestamp)?;
+
     let calculate_swap_result = calculate_swap(
         store,
         current_timestamp,
@@ -206,6 +342,16 @@ pub fn swap_internal(
         by_amount_in,
         sqrt_price_limit,
     )?;
+    if allow_partial {
+        if calculate_swap_result.max_ticks_crossed {
+            return Err(ContractError::TickCrossLimitReached);
+        }
+        if calculate_swap_result.amount_out.is_zero() {
+            return Err(ContractError::NoGainSwap);
+        }
+    } else {
+        calculate_swap_result.ensure_filled()?;
+    }
 
     for tick in &calculate_swap_result.ticks {
         state::update_tick(store, pool_key, tick.index, tick)?;
@@ -213,6 +359,36 @@ pub fn swap_internal(
 
     POOLS.save(store, &pool_key.key(), &calculate_swap_result.pool)?;
 
+    Ok(calculate_swap_result)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn swap_internal(
+    store: &mut dyn Storage,
+    api: &dyn Api,
+    info: &MessageInfo,
+    msgs: &mut Vec<CosmosMsg>,
+    contract_address: &Addr,
+    recipient: &Addr,
+    current_timestamp: u64,
+    pool_key: &PoolKey,
+    x_to_y: bool,
+    amount: TokenAmount,
+    by_amount_in: bool,
+    sqrt_price_limit: SqrtPrice,
+    allow_partial: bool,
+) -> Result<CalculateSwapResult, ContractError> {
+    let calculate_swap_result = execute_swap(
+        store,
+        current_timestamp,
+        pool_key,
+        x_to_y,
+        amount,
+        by_amount_in,
+        sqrt_price_limit,
+        allow_partial,
+    )?;
+
     let (token_0, token_1) = if x_to_y {
         (&pool_key.token_x, &pool_key.token_y)
     } else {
@@ -229,21 +405,227 @@ pub fn swap_internal(
         amount: calculate_swap_result.amount_out.into(),
     };
 
-    asset_0.transfer_from(msgs, &info, contract_address.to_string())?;
-    asset_1.transfer(msgs, &info)?;
+    asset_0.transfer_from(msgs, info, contract_address.to_string())?;
+    asset_1.transfer_to(msgs, recipient)?;
 
     Ok(calculate_swap_result)
 }
 
+/// Checks that a route is not empty and that every hop pays in the token the previous hop paid out.
+/// Only the first token is taken from the caller, so a broken chain would spend reserves of other pools.
+pub fn check_swap_route(swaps: &[SwapHop]) -> Result<(), ContractError> {
+    if swaps.is_empty() {
+        return Err(ContractError::EmptySwapRoute);
+    }
+    if swaps
+        .windows(2)
+        .any(|hops| hops[0].token_out() != hops[1].token_in())
+    {
+        return Err(ContractError::InvalidSwapRoute);
+    }
+    Ok(())
+}
+
+/// Executes every hop of a route, intermediate amounts never leave the contract.
+/// Returns the result of every hop in route order.
+pub fn execute_swap_route(
+    store: &mut dyn Storage,
+    current_timestamp: u64,
+    amount_in: TokenAmount,
+    swaps: &[SwapHop],
+) -> Result<Vec<CalculateSwapResult>, ContractError> {
+    check_swap_route(swaps)?;
+
+    let mut results = Vec::with_capacity(swaps.len());
+    let mut next_swap_amount = amount_in;
+
+    for swap_hop in swaps {
+        let sqrt_price_limit = if swap_hop.x_to_y {
+            SqrtPrice::new(MIN_SQRT_PRICE)
+        } else {
+            SqrtPrice::new(MAX_SQRT_PRICE)
+        };
+
+        let result = execute_swap(
+            store,
+            current_timestamp,
+            &swap_hop.pool_key,
+            swap_hop.x_to_y,
+            next_swap_amount,
+            true,
+            sqrt_price_limit,
+            false,
+        )?;
+
+        next_swap_amount = result.amount_out;
+        results.push(result);
+    }
+
+    Ok(results)
+}
+
+/// Executes a route backwards so that the last hop delivers exactly `amount_out`.
+/// Every hop is sized by the input the following hop requires.
+/// Returns the result of every hop in route order.
+pub fn execute_swap_route_exact_out(
+    store: &mut dyn Storage,
+    current_timestamp: u64,
+    amount_out: TokenAmount,
+    swaps: &[SwapHop],
+) -> Result<Vec<CalculateSwapResult>, ContractError> {
+    check_swap_route(swaps)?;
+
+    let mut results = Vec::with_capacity(swaps.len());
+    let mut next_swap_amount = amount_out;
+
+    for swap_hop in swaps.iter().rev() {
+        let sqrt_price_limit = if swap_hop.x_to_y {
+            SqrtPrice::new(MIN_SQRT_PRICE)
+        } else {
+            SqrtPrice::new(MAX_SQRT_PRICE)
+        };
+
+        let result = execute_swap(
+            store,
+            current_timestamp,
+            &swap_hop.pool_key,
+            swap_hop.x_to_y,
+            next_swap_amount,
+            false,
+            sqrt_price_limit,
+            false,
+        )?;
+
+        next_swap_amount = result.amount_in;
+        results.push(result);
+    }
+
+    results.reverse();
+    Ok(results)
+}
+
+/// Returns the amount taken by the first hop and the amount produced by the last one.
+pub fn route_amounts(results: &[CalculateSwapResult]) -> (TokenAmount, TokenAmount) {
+    match (results.first(), results.last()) {
+        (Some(first), Some(last)) => (first.amount_in, last.amount_out),
+        _ => (TokenAmount::new(0), TokenAmount::new(0)),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn swap_route_internal(
     store: &mut dyn Storage,
     api: &dyn Api,
     env: Env,
     info: &MessageInfo,
     msgs: &mut Vec<CosmosMsg>,
+    recipient: &Addr,
+    amount_in: TokenAmount,
+    swaps: &[SwapHop],
+) -> Result<Vec<CalculateSwapResult>, ContractError> {
+    let results = execute_swap_route(store, env.block.time.millis(), amount_in, swaps)?;
+    let (amount_in, amount_out) = route_amounts(&results);
+
+    // only the first token is pulled and only the last one is paid out
+    let asset_in = Asset {
+        info: AssetInfo::from_denom(api, swaps[0].token_in()),
+        amount: amount_in.into(),
+    };
+
+    let asset_out = Asset {
+        info: AssetInfo::from_denom(api, swaps[swaps.len() - 1].token_out()),
+        amount: amount_out.into(),
+    };
+
+    asset_in.transfer_from(msgs, info, env.contract.address.to_string())?;
+    asset_out.transfer_to(msgs, recipient)?;
+
+    Ok(results)
+}
+
+/// Checks that there is at least one and at most `MAX_SPLIT_ROUTES` routes, that every route
+/// is valid on its own and that every route swaps the same token into the same token.
+pub fn check_split_routes<'a>(
+    routes: impl IntoIterator<Item = &'a Vec<SwapHop>>,
+) -> Result<(), ContractError> {
+    let mut tokens: Option<(&str, &str)> = None;
+    for (index, swaps) in routes.into_iter().enumerate() {
+        if index >= MAX_SPLIT_ROUTES {
+            return Err(ContractError::SwapSplitRouteLimitReached);
+        }
+        check_swap_route(swaps)?;
+        let route_tokens = (swaps[0].token_in(), swaps[swaps.len() - 1].token_out());
+        match tokens {
+            None => tokens = Some(route_tokens),
+            Some(tokens) if tokens != route_tokens => return Err(ContractError::InvalidSwapSplit),
+            Some(_) => {}
+        }
+    }
+
+    if tokens.is_none() {
+        return Err(ContractError::EmptySwapRoute);
+    }
+    Ok(())
+}
+
+/// Sums the input and output of every leg of a split swap.
+pub fn split_amounts(results: &[Vec<CalculateSwapResult>]) -> (TokenAmount, TokenAmount) {
+    results.iter().fold(
+        (TokenAmount::new(0), TokenAmount::new(0)),
+        |(amount_in, amount_out), leg_results| {
+            let (leg_in, leg_out) = route_amounts(leg_results);
+            (amount_in + leg_in, amount_out + leg_out)
+        },
+    )
+}
+
+/// Executes every leg of a split swap one after another, so later legs see the pools moved by earlier ones.
+/// Returns the results of every leg in leg order.
+pub fn swap_split_internal(
+    store: &mut dyn Storage,
+    api: &dyn Api,
+    env: Env,
+    info: &MessageInfo,
+    msgs: &mut Vec<CosmosMsg>,
+    recipient: &Addr,
+    legs: &[(Vec<SwapHop>, TokenAmount)],
+) -> Result<Vec<Vec<CalculateSwapResult>>, ContractError> {
+    check_split_routes(legs.iter().map(|(swaps, _)| swaps))?;
+
+    let results = legs
+        .iter()
+        .map(|(swaps, amount_in)| {
+            execute_swap_route(store, env.block.time.millis(), *amount_in, swaps)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let (amount_in, amount_out) = split_amounts(&results);
+
+    // every leg starts and ends with the same tokens, so they are moved once
+    let swaps = &legs[0].0;
+    let asset_in = Asset {
+        info: AssetInfo::from_denom(api, swaps[0].token_in()),
+        amount: amount_in.into(),
+    };
+
+    let asset_out = Asset {
+        info: AssetInfo::from_denom(api, swaps[swaps.len() - 1].token_out()),
+        amount: amount_out.into(),
+    };
+
+    asset_in.transfer_from(msgs, info, env.contract.address.to_string())?;
+    asset_out.transfer_to(msgs, recipient)?;
+
+    Ok(results)
+}
+
+pub fn route(
+    store: &dyn Storage,
+    env: Env,
     amount_in: TokenAmount,
     swaps: Vec<SwapHop>,
 ) -> Result<TokenAmount, ContractError> {
+    check_swap_route(&swaps)?;
+
     let mut next_swap_amount = amount_in;
 
     let current_timestamp = env.block.time.millis();
@@ -255,52 +637,52 @@ pub fn swap_route_internal(
             SqrtPrice::new(MAX_SQRT_PRICE)
         };
 
-        next_swap_amount = swap_internal(
+        let result = calculate_swap(
             store,
-            api,
-            info,
-            msgs,
-            &env.contract.address,
             current_timestamp,
             &swap_hop.pool_key,
             swap_hop.x_to_y,
             next_swap_amount,
             true,
             sqrt_price_limit,
-        )?
-        .amount_out;
+        )?;
+        result.ensure_filled()?;
+        next_swap_amount = result.amount_out;
     }
 
     Ok(next_swap_amount)
 }
 
-pub fn route(
+pub fn route_exact_out(
     store: &dyn Storage,
     env: Env,
-    amount_in: TokenAmount,
+    amount_out: TokenAmount,
     swaps: Vec<SwapHop>,
 ) -> Result<TokenAmount, ContractError> {
-    let mut next_swap_amount = amount_in;
+    check_swap_route(&swaps)?;
+
+    let mut next_swap_amount = amount_out;
 
     let current_timestamp = env.block.time.millis();
 
-    for swap_hop in &swaps {
+    for swap_hop in swaps.iter().rev() {
         let sqrt_price_limit = if swap_hop.x_to_y {
             SqrtPrice::new(MIN_SQRT_PRICE)
         } else {
             SqrtPrice::new(MAX_SQRT_PRICE)
         };
 
-        next_swap_amount = calculate_swap(
+        let result = calculate_swap(
             store,
             current_timestamp,
             &swap_hop.pool_key,
             swap_hop.x_to_y,
             next_swap_amount,
-            true,
+            false,
             sqrt_price_limit,
-        )?
-        .amount_out;
+        )?;
+        result.ensure_filled()?;
+        next_swap_amount = result.amount_in;
     }
 
     Ok(next_swap_amount)
@@ -417,10 +799,11 @@ pub fn update_approvals(
     add: bool,
     expires: Option<Expiration>,
 ) -> Result<Position, ContractError> {
-    let owner_raw = &token_id[..token_id.len() - 4];
-    let mut pos = state::get_position_by_key(deps.storage, token_id)?;
+    let position_id = state::parse_token_id(token_id)?;
+    let (owner, _) = state::get_position_owner(deps.storage, position_id)?;
+    let mut pos = state::get_position_by_id(deps.storage, position_id)?;
     // ensure we have permissions
-    check_can_approve(deps.as_ref(), env, info, owner_raw)?;
+    check_can_approve(deps.as_ref(), env, info, owner.as_bytes())?;
 
     // update the approval list (remove any for the same spender before adding)
     pos.approvals = pos
@@ -443,7 +826,7 @@ pub fn update_approvals(
         pos.approvals.push(approval);
     }
 
-    state::POSITIONS.save(deps.storage, token_id, &pos)?;
+    state::POSITIONS.save(deps.storage, position_id, &pos)?;
 
     Ok(pos)
 }
@@ -455,17 +838,16 @@ pub fn transfer_nft(
     recipient: &Addr,
     token_id: &[u8],
 ) -> Result<(), ContractError> {
-    let owner_raw = &token_id[..token_id.len() - 4];
-    let index = u32::from_be_bytes(token_id[token_id.len() - 4..].try_into().unwrap());
-    let account_id = Addr::unchecked(String::from_utf8(owner_raw.to_vec())?);
-    let mut pos = state::get_position_by_key(deps.storage, token_id)?;
+    let position_id = state::parse_token_id(token_id)?;
+    let (owner, index) = state::get_position_owner(deps.storage, position_id)?;
+    let mut pos = state::get_position_by_id(deps.storage, position_id)?;
     // ensure we have permissions
-    check_can_send(deps.as_ref(), env, info, owner_raw, &pos)?;
-    // set owner and remove existing approvals
-    state::remove_position(deps.storage, &account_id, index)?;
+    check_can_send(deps.as_ref(), env, info, owner.as_bytes(), &pos)?;
+    // set owner, the token id stays the same
+    state::transfer_position(deps.storage, &owner, index, recipient)?;
     // reset approvals when transfer
     pos.approvals = vec![];
-    state::add_position(deps.storage, recipient, &pos)?;
+    state::POSITIONS.save(deps.storage, position_id, &pos)?;
     Ok(())
 }
 
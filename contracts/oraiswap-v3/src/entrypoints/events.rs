@@ -0,0 +1,83 @@
+use cosmwasm_std::{attr, Addr, Attribute, Event};
+
+use crate::{
+    interface::{CalculateSwapResult, SwapHop},
+    PoolKey,
+};
+
+/// Identifies the pool in every event touching it.
+pub fn pool_key_attributes(pool_key: &PoolKey) -> Vec<Attribute> {
+    vec![
+        attr("token_x", pool_key.token_x.as_str()),
+        attr("token_y", pool_key.token_y.as_str()),
+        attr("fee", pool_key.fee_tier.fee.to_string()),
+        attr("tick_spacing", pool_key.fee_tier.tick_spacing.to_string()),
+    ]
+}
+
+/// Builds a `swap` event for a single pool swap followed by a `cross_tick` event for every tick
+/// it crossed.
+pub fn swap_events(
+    sender: &Addr,
+    recipient: &Addr,
+    pool_key: &PoolKey,
+    x_to_y: bool,
+    result: &CalculateSwapResult,
+) -> Vec<Event> {
+    let mut swap_event = Event::new("swap")
+        .add_attributes(pool_key_attributes(pool_key))
+        .add_attributes(vec![
+            attr("sender", sender.as_str()),
+            attr("recipient", recipient.as_str()),
+            attr("x_to_y", x_to_y.to_string()),
+            attr("amount_in", result.amount_in.to_string()),
+            attr("amount_out", result.amount_out.to_string()),
+            attr("fee_amount", result.fee.to_string()),
+            attr("protocol_fee", result.protocol_fee.to_string()),
+            attr("swap_fee", result.swap_fee.to_string()),
+            attr("sqrt_price_before", result.start_sqrt_price.to_string()),
+            attr("sqrt_price_after", result.target_sqrt_price.to_string()),
+            attr("current_tick", result.pool.current_tick_index.to_string()),
+            attr("liquidity", result.pool.liquidity.to_string()),
+        ]);
+    if let Some(reason) = result.partial_fill {
+        swap_event = swap_event.add_attribute("partial_fill", reason.as_str());
+    }
+
+    let mut events = vec![swap_event];
+
+    for tick in &result.ticks {
+        events.push(
+            Event::new("cross_tick")
+                .add_attributes(pool_key_attributes(pool_key))
+                .add_attributes(vec![
+                    attr("index", tick.index.to_string()),
+                    attr("sign", tick.sign.to_string()),
+                    attr("liquidity_change", tick.liquidity_change.to_string()),
+                ]),
+        );
+    }
+
+    events
+}
+
+/// Builds the swap events of every hop of a route, `results` are in hop order. The contract
+/// receives the output of every hop but the last one.
+pub fn route_events(
+    sender: &Addr,
+    contract: &Addr,
+    recipient: &Addr,
+    swaps: &[SwapHop],
+    results: &[CalculateSwapResult],
+) -> Vec<Event> {
+    let last = swaps.len().saturating_sub(1);
+    swaps
+        .iter()
+        .zip(results)
+        .enumerate()
+        .flat_map(|(index, (hop, result))| {
+            let hop_recipient = if index == last { recipient } else { contract };
+            swap_events(sender, hop_recipient, &hop.pool_key, hop.x_to_y, result)
+        })
+        .collect()
+}
@@ -1,46 +1,125 @@
 use crate::error::ContractError;
-use crate::interface::{Asset, AssetInfo, CalculateSwapResult, Cw721ReceiveMsg, SwapHop};
+use crate::fee_growth::FeeGrowth;
+use crate::interface::{Asset, AssetInfo, Cw721ReceiveMsg, SwapHop};
 use crate::liquidity::Liquidity;
+use crate::logic::get_liquidity;
+use crate::math::log::get_tick_at_sqrt_price;
+use crate::msg::Cw20HookMsg;
 use crate::percentage::Percentage;
 use crate::sqrt_price::SqrtPrice;
-use crate::state::{self, CONFIG, POOLS};
+use crate::state::{self, CONFIG, MAX_POOL_INCENTIVES, POOLS};
 use crate::token_amount::TokenAmount;
-use crate::{calculate_min_amount_out, check_tick, FeeTier, Pool, PoolKey, Position};
+use crate::{
+    calculate_amount_delta, calculate_min_amount_out, check_tick, DynamicFeeConfig, FeeTier,
+    IncentiveRecord, PauseScope, PendingAdmin, Pool, PoolCreationFee, PoolCreationPolicy, PoolKey,
+    Volatility,
+};
 
 use super::{
-    check_can_send, create_tick, remove_tick_and_flip_bitmap, swap_internal, swap_route_internal,
-    transfer_nft, update_approvals, TimeStampExt,
+    check_can_send, check_deadline, create_position_internal, execute_swap, execute_swap_route,
+    execute_swap_route_exact_out, migrate_legacy_positions, pool_key_attributes,
+    remove_tick_and_flip_bitmap, route_amounts, route_events, split_amounts, swap_events,
+    swap_internal, swap_route_internal, swap_split_internal, transfer_nft, update_approvals,
+    TimeStampExt,
+};
+use cosmwasm_std::{
+    attr, coins, from_binary, Addr, BankMsg, Binary, CosmosMsg, DepsMut, Env, MessageInfo,
+    Response, Storage, Timestamp, Uint128,
 };
-use cosmwasm_std::{attr, Addr, Binary, DepsMut, Env, MessageInfo, Response};
-use cw20::Expiration;
-use decimal::Decimal;
+use cw20::{Cw20ReceiveMsg, Expiration};
+use decimal::{Decimal, Factories, U256};
 
-/// Allows an admin to adjust admin.
+/// Allows an admin to propose a new admin, the transfer only happens once the proposed
+/// address accepts it.
 ///
 /// # Parameters
-/// - `new_admin`: new admin address.
+/// - `new_admin`: proposed admin address.
+/// - `expires`: optional deadline for accepting the proposal.
 ///
 /// # Errors
 /// - Reverts the call when the caller is an unauthorized user.
-///
-
-pub fn change_admin(
+/// - Fails if the address is invalid or the expiry has already passed.
+pub fn propose_admin(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
-    new_admin: Addr,
+    new_admin: String,
+    expires: Option<Expiration>,
 ) -> Result<Response, ContractError> {
-    let mut config = CONFIG.load(deps.storage)?;
+    let config = CONFIG.load(deps.storage)?;
 
     if info.sender != config.admin {
         return Err(ContractError::Unauthorized {});
     }
 
-    config.admin = new_admin.clone();
+    let address = deps.api.addr_validate(&new_admin)?;
+    let expires = expires.unwrap_or_default();
+    if expires.is_expired(&env.block) {
+        return Err(ContractError::AdminProposalExpired);
+    }
+
+    state::PENDING_ADMIN.save(
+        deps.storage,
+        &PendingAdmin {
+            address: address.clone(),
+            expires,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "propose_admin")
+        .add_attribute("pending_admin", address)
+        .add_attribute("expires", expires.to_string()))
+}
+
+/// Allows the proposed admin to take over the contract.
+///
+/// # Errors
+/// - Fails if there is no pending proposal or it has expired.
+/// - Reverts the call when the caller is not the proposed admin.
+pub fn accept_admin(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    let pending_admin = state::PENDING_ADMIN
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoPendingAdmin)?;
+
+    if info.sender != pending_admin.address {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if pending_admin.expires.is_expired(&env.block) {
+        return Err(ContractError::AdminProposalExpired);
+    }
+
+    let mut config = CONFIG.load(deps.storage)?;
+    let previous_admin = config.admin;
+    config.admin = pending_admin.address;
     CONFIG.save(deps.storage, &config)?;
+    state::PENDING_ADMIN.remove(deps.storage);
 
     Ok(Response::new()
-        .add_attribute("action", "change_admin")
-        .add_attribute("new_admin", new_admin))
+        .add_attribute("action", "accept_admin")
+        .add_attribute("previous_admin", previous_admin)
+        .add_attribute("new_admin", config.admin))
+}
+
+/// Allows an admin to withdraw a pending admin proposal.
+///
+/// # Errors
+/// - Reverts the call when the caller is an unauthorized user.
+/// - Fails if there is no pending proposal.
+pub fn cancel_admin_proposal(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if !state::PENDING_ADMIN.exists(deps.storage) {
+        return Err(ContractError::NoPendingAdmin);
+    }
+    state::PENDING_ADMIN.remove(deps.storage);
+
+    Ok(Response::new().add_attribute("action", "cancel_admin_proposal"))
 }
 
 /// Allows an fee receiver to withdraw collected fees.
@@ -81,7 +160,11 @@ pub fn withdraw_protocol_fee(
 
     Ok(Response::new()
         .add_messages(msgs)
-        .add_attribute("action", "withdraw_protocol_fee"))
+        .add_attribute("action", "withdraw_protocol_fee")
+        .add_attributes(pool_key_attributes(&pool_key))
+        .add_attribute("fee_receiver", info.sender.as_str())
+        .add_attribute("amount_x", fee_protocol_token_x.to_string())
+        .add_attribute("amount_y", fee_protocol_token_y.to_string()))
 }
 
 /// Allows an admin to adjust the protocol fee.
@@ -108,6 +191,170 @@ pub fn change_protocol_fee(
     Ok(Response::new().add_attribute("action", "change_protocol_fee"))
 }
 
+/// Allows an admin to override the protocol fee of a single pool.
+///
+/// # Parameters
+/// - `pool_key`: A unique key that identifies the specified pool.
+/// - `protocol_fee`: The fee taken from the pool swaps, `None` falls back to the global protocol fee.
+///
+/// # Errors
+/// - Reverts the call when the caller is an unauthorized user.
+/// - Fails if the fee is greater than 100%.
+pub fn change_pool_protocol_fee(
+    deps: DepsMut,
+    info: MessageInfo,
+    pool_key: PoolKey,
+    protocol_fee: Option<Percentage>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(protocol_fee) = protocol_fee {
+        if protocol_fee > Percentage::from_integer(1) {
+            return Err(ContractError::InvalidFee);
+        }
+    }
+
+    let pool_key_db = pool_key.key();
+    let mut pool = POOLS.load(deps.storage, &pool_key_db)?;
+    pool.protocol_fee = protocol_fee;
+    POOLS.save(deps.storage, &pool_key_db, &pool)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "change_pool_protocol_fee")
+        .add_attribute(
+            "protocol_fee",
+            protocol_fee.map_or("default".to_string(), |fee| fee.get().to_string()),
+        ))
+}
+
+/// Allows an admin to switch a pool to a fee scaling with the recent price movement.
+///
+/// # Parameters
+/// - `pool_key`: A unique key that identifies the specified pool.
+/// - `dynamic_fee`: The fee bounds and volatility window, `None` restores the fee tier fee.
+///
+/// # Errors
+/// - Reverts the call when the caller is an unauthorized user.
+/// - Fails if the bounds or the window are invalid.
+pub fn change_pool_dynamic_fee(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    pool_key: PoolKey,
+    dynamic_fee: Option<DynamicFeeConfig>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(dynamic_fee) = &dynamic_fee {
+        dynamic_fee.validate()?;
+    }
+
+    let pool_key_db = pool_key.key();
+    let mut pool = POOLS.load(deps.storage, &pool_key_db)?;
+    pool.dynamic_fee = dynamic_fee;
+    pool.volatility = Volatility::new(env.block.time.seconds());
+    POOLS.save(deps.storage, &pool_key_db, &pool)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "change_pool_dynamic_fee")
+        .add_attribute("enabled", dynamic_fee.is_some().to_string()))
+}
+
+/// Allows an admin to restrict who can create pools.
+///
+/// # Parameters
+/// - `policy`: Open to everyone, admin only, or limited to whitelisted tokens.
+///
+/// # Errors
+/// - Reverts the call when the caller is an unauthorized user.
+pub fn change_pool_creation_policy(
+    deps: DepsMut,
+    info: MessageInfo,
+    policy: PoolCreationPolicy,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    config.pool_creation_policy = policy;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "change_pool_creation_policy"))
+}
+
+/// Allows an admin to change how many initialized ticks a single swap may cross.
+///
+/// # Parameters
+/// - `max_tick_cross`: The new limit, `None` restores the default `MAX_TICK_CROSS`.
+///
+/// # Errors
+/// - Reverts the call when the caller is an unauthorized user.
+/// - Fails if the limit is zero.
+pub fn change_max_tick_cross(
+    deps: DepsMut,
+    info: MessageInfo,
+    max_tick_cross: Option<u32>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if max_tick_cross == Some(0) {
+        return Err(ContractError::InvalidTickCrossLimit);
+    }
+
+    config.max_tick_cross = max_tick_cross;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "change_max_tick_cross")
+        .add_attribute("max_tick_cross", config.max_tick_cross().to_string()))
+}
+
+/// Allows an admin to charge a native fee for creating pools.
+///
+/// # Parameters
+/// - `fee`: The denom, amount and treasury receiving the fee, `None` makes pool creation free.
+///
+/// # Errors
+/// - Reverts the call when the caller is an unauthorized user.
+/// - Fails if the fee amount is zero or the treasury address is invalid.
+pub fn change_pool_creation_fee(
+    deps: DepsMut,
+    info: MessageInfo,
+    fee: Option<PoolCreationFee>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(fee) = &fee {
+        if fee.amount.is_zero() {
+            return Err(ContractError::InvalidPoolCreationFee);
+        }
+        deps.api.addr_validate(fee.treasury.as_str())?;
+    }
+
+    config.pool_creation_fee = fee;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "change_pool_creation_fee"))
+}
+
 /// Allows admin to change current fee receiver.
 ///
 /// # Parameters
@@ -133,224 +380,1306 @@ pub fn change_fee_receiver(
     pool.fee_receiver = fee_receiver.to_string();
     POOLS.save(deps.storage, &pool_key_db, &pool)?;
 
-    Ok(Response::new().add_attribute("action", "change_fee_receiver"))
+    Ok(Response::new()
+        .add_attribute("action", "change_fee_receiver")
+        .add_attributes(pool_key_attributes(&pool_key))
+        .add_attribute("fee_receiver", fee_receiver.as_str()))
 }
 
-/// Opens a position.
+/// Allows admin to start an incentive program for the pool, the whole emission is transferred
+/// from the admin up front.
 ///
 /// # Parameters
 /// - `pool_key`: A unique key that identifies the specified pool.
-/// - `lower_tick`: The index of the lower tick for opening the position.
-/// - `upper_tick`: The index of the upper tick for opening the position.
-/// - `liquidity_delta`: The desired liquidity provided by the user in the specified range.
-/// - `slippage_limit_lower`: The price limit for downward movement to execute the position creation.
-/// - `slippage_limit_upper`: The price limit for upward movement to execute the position creation.
-///
-/// # Events
-/// - On successful transfer, emits a `Create Position` event for the newly opened position.
+/// - `reward_token`: The token distributed to the liquidity providers.
+/// - `reward_per_sec`: The amount emitted every second to the in-range liquidity.
+/// - `start_timestamp`: The block time in seconds the emission starts at, defaults to now.
+/// - `end_timestamp`: The block time in seconds the emission stops at.
 ///
 /// # Errors
-/// - Fails if the user attempts to open a position with zero liquidity.
-/// - Fails if the user attempts to create a position with invalid tick indexes or tick spacing.
-/// - Fails if the price has reached the slippage limit.
-/// - Fails if the allowance is insufficient or the user balance transfer fails.
-/// - Fails if pool does not exist
+/// - Reverts the call when the caller is an unauthorized user.
+/// - Fails if the program starts in the past or does not end after it starts.
+/// - Fails if the pool already holds `MAX_POOL_INCENTIVES` programs.
 #[allow(clippy::too_many_arguments)]
-pub fn create_position(
+pub fn create_incentive(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
     pool_key: PoolKey,
-    lower_tick: i32,
-    upper_tick: i32,
-    liquidity_delta: Liquidity,
-    slippage_limit_lower: SqrtPrice,
-    slippage_limit_upper: SqrtPrice,
+    reward_token: AssetInfo,
+    reward_per_sec: TokenAmount,
+    start_timestamp: Option<u64>,
+    end_timestamp: u64,
 ) -> Result<Response, ContractError> {
-    let current_timestamp = env.block.time.millis();
-    let current_block_number = env.block.height;
+    let config = CONFIG.load(deps.storage)?;
 
-    // liquidity delta = 0 => return
-    if liquidity_delta.is_zero() {
-        return Err(ContractError::InsufficientLiquidity {});
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
     }
 
-    if lower_tick == upper_tick {
-        return Err(ContractError::InvalidTickIndex {});
+    if reward_per_sec.is_zero() {
+        return Err(ContractError::AmountIsZero {});
+    }
+
+    let current_timestamp = env.block.time.seconds();
+    let start_timestamp = start_timestamp.unwrap_or(current_timestamp);
+    if start_timestamp < current_timestamp || end_timestamp <= start_timestamp {
+        return Err(ContractError::InvalidIncentiveDuration);
     }
+
+    let total_reward = reward_per_sec
+        .get()
+        .checked_mul((end_timestamp - start_timestamp) as u128)
+        .ok_or(ContractError::Mul)?;
+
     let pool_key_db = pool_key.key();
     let mut pool = POOLS
         .load(deps.storage, &pool_key_db)
         .map_err(|_| ContractError::PoolNotFound {})?;
 
-    let mut lower_tick = match state::get_tick(deps.storage, &pool_key, lower_tick) {
-        Ok(tick) => tick,
-        _ => create_tick(deps.storage, current_timestamp, &pool_key, lower_tick)?,
-    };
+    // ended programs are kept for the positions to settle, so they count as well
+    if pool.incentives.len() >= MAX_POOL_INCENTIVES {
+        return Err(ContractError::IncentiveLimitReached);
+    }
 
-    let mut upper_tick = match state::get_tick(deps.storage, &pool_key, upper_tick) {
-        Ok(tick) => tick,
-        _ => create_tick(deps.storage, current_timestamp, &pool_key, upper_tick)?,
-    };
+    let id = pool.incentives.len() as u64;
+    pool.incentives.push(IncentiveRecord {
+        id,
+        reward_token: reward_token.clone(),
+        reward_per_sec,
+        start_timestamp,
+        end_timestamp,
+        last_updated: current_timestamp,
+        incentive_growth_global: FeeGrowth::new(0),
+        unallocated: TokenAmount::new(0),
+    });
+    POOLS.save(deps.storage, &pool_key_db, &pool)?;
 
-    let (position, x, y) = Position::create(
-        &mut pool,
-        pool_key.clone(),
-        &mut lower_tick,
-        &mut upper_tick,
-        current_timestamp,
-        liquidity_delta,
-        slippage_limit_lower,
-        slippage_limit_upper,
-        current_block_number,
-        pool_key.fee_tier.tick_spacing,
-    )?;
+    let mut msgs = vec![];
+    Asset {
+        info: reward_token.clone(),
+        amount: total_reward.into(),
+    }
+    .transfer_from(&mut msgs, &info, env.contract.address.to_string())?;
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attribute("action", "create_incentive")
+        .add_attribute("incentive_id", id.to_string())
+        .add_attribute("reward_token", reward_token.denom())
+        .add_attribute("reward_per_sec", reward_per_sec.to_string())
+        .add_attribute("total_reward", total_reward.to_string()))
+}
+
+/// Allows admin to withdraw the emission of an incentive program that was not distributed because
+/// the pool had no in-range liquidity.
+///
+/// # Parameters
+/// - `pool_key`: A unique key that identifies the specified pool.
+/// - `incentive_id`: The id of the incentive program in the pool.
+///
+/// # Errors
+/// - Reverts the call when the caller is an unauthorized user.
+/// - Fails if the pool or the incentive program does not exist.
+/// - Fails if there is nothing to withdraw.
+pub fn withdraw_unallocated_incentive(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    pool_key: PoolKey,
+    incentive_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let pool_key_db = pool_key.key();
+    let mut pool = POOLS
+        .load(deps.storage, &pool_key_db)
+        .map_err(|_| ContractError::PoolNotFound {})?;
+    pool.update_global_incentives(env.block.time.millis())?;
+
+    let record = pool
+        .incentives
+        .get_mut(incentive_id as usize)
+        .ok_or(ContractError::IncentiveNotFound)?;
+    let amount = record.unallocated;
+    if amount.is_zero() {
+        return Err(ContractError::AmountIsZero {});
+    }
+    record.unallocated = TokenAmount::new(0);
+    let reward_token = record.reward_token.clone();
+    POOLS.save(deps.storage, &pool_key_db, &pool)?;
+
+    let mut msgs = vec![];
+    Asset {
+        info: reward_token.clone(),
+        amount: amount.into(),
+    }
+    .transfer(&mut msgs, &info)?;
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attribute("action", "withdraw_unallocated_incentive")
+        .add_attributes(pool_key_attributes(&pool_key))
+        .add_attribute("incentive_id", incentive_id.to_string())
+        .add_attribute("reward_token", reward_token.denom())
+        .add_attribute("amount", amount.to_string()))
+}
+
+/// Allows admin to appoint the guardian that can pause and unpause operations, `None` removes it.
+///
+/// # Parameters
+/// - `guardian`: The address of the new guardian.
+///
+/// # Errors
+/// - Reverts the call when the caller is an unauthorized user.
+/// - Fails if the guardian address is invalid.
+pub fn change_guardian(
+    deps: DepsMut,
+    info: MessageInfo,
+    guardian: Option<String>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    config.guardian = guardian
+        .map(|guardian| deps.api.addr_validate(&guardian))
+        .transpose()?;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "change_guardian")
+        .add_attribute(
+            "guardian",
+            config
+                .guardian
+                .map(|guardian| guardian.to_string())
+                .unwrap_or_default(),
+        ))
+}
+
+/// Allows admin or guardian to halt the operations covered by `scope`.
+/// Removing positions and claiming fees stay open so liquidity providers can always exit.
+///
+/// # Parameters
+/// - `scope`: The operations to halt.
+///
+/// # Errors
+/// - Reverts the call when the caller is neither the admin nor the guardian.
+pub fn pause(
+    deps: DepsMut,
+    info: MessageInfo,
+    scope: PauseScope,
+) -> Result<Response, ContractError> {
+    check_can_pause(deps.storage, &info.sender)?;
+
+    state::PAUSED.save(deps.storage, &scope.key(), &true)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "pause")
+        .add_attribute("scope", scope.name()))
+}
+
+/// Allows admin or guardian to resume the operations covered by `scope`.
+///
+/// # Parameters
+/// - `scope`: The operations to resume.
+///
+/// # Errors
+/// - Reverts the call when the caller is neither the admin nor the guardian.
+pub fn unpause(
+    deps: DepsMut,
+    info: MessageInfo,
+    scope: PauseScope,
+) -> Result<Response, ContractError> {
+    check_can_pause(deps.storage, &info.sender)?;
+
+    state::PAUSED.remove(deps.storage, &scope.key());
+
+    Ok(Response::new()
+        .add_attribute("action", "unpause")
+        .add_attribute("scope", scope.name()))
+}
+
+fn check_can_pause(store: &dyn Storage, sender: &Addr) -> Result<(), ContractError> {
+    let config = CONFIG.load(store)?;
+
+    if *sender != config.admin && Some(sender) != config.guardian.as_ref() {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    Ok(())
+}
+
+/// Allows an admin to move the positions left behind by the 0.3.0 migration to stable ids.
+/// Until then those positions can not be addressed, so this is repeated until `pending` is false.
+///
+/// # Parameters
+/// - `limit`: The maximum number of positions to move, 100 by default.
+///
+/// # Errors
+/// - Reverts the call when the caller is an unauthorized user.
+pub fn migrate_positions(
+    deps: DepsMut,
+    info: MessageInfo,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let (migrated, pending) =
+        migrate_legacy_positions(deps.storage, limit.unwrap_or(state::MAX_LIMIT))?;
+
+    Ok(Response::new()
+        .add_attribute("action", "migrate_positions")
+        .add_attribute("migrated", migrated.to_string())
+        .add_attribute("pending", pending.to_string()))
+}
+
+/// Opens a position.
+///
+/// # Parameters
+/// - `pool_key`: A unique key that identifies the specified pool.
+/// - `lower_tick`: The index of the lower tick for opening the position.
+/// - `upper_tick`: The index of the upper tick for opening the position.
+/// - `liquidity_delta`: The desired liquidity provided by the user in the specified range.
+/// - `slippage_limit_lower`: The price limit for downward movement to execute the position creation.
+/// - `slippage_limit_upper`: The price limit for upward movement to execute the position creation.
+///
+/// # Events
+/// - On successful transfer, emits a `Create Position` event for the newly opened position.
+///
+/// # Errors
+/// - Fails if the user attempts to open a position with zero liquidity.
+/// - Fails if the user attempts to create a position with invalid tick indexes or tick spacing.
+/// - Fails if the price has reached the slippage limit.
+/// - Fails if the allowance is insufficient or the user balance transfer fails.
+/// - Fails if pool does not exist
+#[allow(clippy::too_many_arguments)]
+pub fn create_position(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    pool_key: PoolKey,
+    lower_tick: i32,
+    upper_tick: i32,
+    liquidity_delta: Liquidity,
+    slippage_limit_lower: SqrtPrice,
+    slippage_limit_upper: SqrtPrice,
+) -> Result<Response, ContractError> {
+    let (position_id, position, pool, x, y) = create_position_internal(
+        deps.storage,
+        env.block.time.millis(),
+        env.block.height,
+        &info.sender,
+        &pool_key,
+        lower_tick,
+        upper_tick,
+        liquidity_delta,
+        slippage_limit_lower,
+        slippage_limit_upper,
+    )?;
+
+    let asset_0 = Asset {
+        info: AssetInfo::from_denom(deps.api, pool_key.token_x.as_str()),
+        amount: x.into(),
+    };
+
+    let asset_1 = Asset {
+        info: AssetInfo::from_denom(deps.api, pool_key.token_y.as_str()),
+        amount: y.into(),
+    };
+
+    let mut msgs = vec![];
+    asset_0.transfer_from(&mut msgs, &info, env.contract.address.to_string())?;
+    asset_1.transfer_from(&mut msgs, &info, env.contract.address.to_string())?;
+
+    let event_attributes = vec![
+        attr("action", "create_position"),
+        attr("address", info.sender.as_str()),
+        attr(
+            "token_id",
+            Binary::from(state::token_id(position_id)).to_string(),
+        ),
+        attr("liquidity", liquidity_delta.to_string()),
+        attr("lower_tick", position.lower_tick_index.to_string()),
+        attr("upper_tick", position.upper_tick_index.to_string()),
+        attr("current_sqrt_price", pool.sqrt_price.to_string()),
+    ];
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attributes(event_attributes))
+}
+
+/// Opens a position from token amounts instead of a raw liquidity.
+/// The liquidity is derived on-chain from the current pool price, only the required amounts are
+/// transferred and any native token excess is refunded.
+///
+/// # Parameters
+/// - `pool_key`: A unique key that identifies the specified pool.
+/// - `lower_tick`: The index of the lower tick for opening the position.
+/// - `upper_tick`: The index of the upper tick for opening the position.
+/// - `amount_x_desired`: The maximum amount of token x the user is willing to provide.
+/// - `amount_y_desired`: The maximum amount of token y the user is willing to provide.
+/// - `amount_x_min`: The minimum amount of token x that has to be provided.
+/// - `amount_y_min`: The minimum amount of token y that has to be provided.
+///
+/// # Events
+/// - On successful transfer, emits a `Create Position` event for the newly opened position.
+///
+/// # Errors
+/// - Fails if the desired amounts result in zero liquidity.
+/// - Fails if the user attempts to create a position with invalid tick indexes or tick spacing.
+/// - Fails if the required amounts are under the specified minimum amounts.
+/// - Fails if the allowance is insufficient or the user balance transfer fails.
+/// - Fails if funds other than the pool tokens are attached.
+/// - Fails if pool does not exist
+#[allow(clippy::too_many_arguments)]
+pub fn create_position_by_amounts(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    pool_key: PoolKey,
+    lower_tick: i32,
+    upper_tick: i32,
+    amount_x_desired: TokenAmount,
+    amount_y_desired: TokenAmount,
+    amount_x_min: TokenAmount,
+    amount_y_min: TokenAmount,
+) -> Result<Response, ContractError> {
+    if lower_tick >= upper_tick {
+        return Err(ContractError::InvalidTickIndex {});
+    }
+
+    let pool = state::get_pool(deps.storage, &pool_key).map_err(|_| ContractError::PoolNotFound)?;
+
+    // only the pool tokens can be attached, their excess is refunded below
+    if info
+        .funds
+        .iter()
+        .any(|coin| coin.denom != pool_key.token_x && coin.denom != pool_key.token_y)
+    {
+        return Err(ContractError::InvalidFunds {
+            transfer_amount: Uint128::zero(),
+        });
+    }
+
+    let mut liquidity_delta = get_liquidity(
+        amount_x_desired,
+        amount_y_desired,
+        lower_tick,
+        upper_tick,
+        pool.sqrt_price,
+        false,
+    )?
+    .l;
+
+    // the required amounts are rounded up, so the liquidity is scaled down until they fit in the
+    // desired amounts, every step strictly lowers it
+    let scale = |liquidity: Liquidity, desired: TokenAmount, required: TokenAmount| {
+        Liquidity::new(
+            (U256::from(liquidity.get()) * U256::from(desired.get()) / U256::from(required.get()))
+                .as_u128(),
+        )
+    };
+    loop {
+        let (x, y, _) = calculate_amount_delta(
+            pool.current_tick_index,
+            pool.sqrt_price,
+            liquidity_delta,
+            true,
+            upper_tick,
+            lower_tick,
+        )?;
+        if x > amount_x_desired {
+            liquidity_delta = scale(liquidity_delta, amount_x_desired, x);
+        } else if y > amount_y_desired {
+            liquidity_delta = scale(liquidity_delta, amount_y_desired, y);
+        } else {
+            break;
+        }
+    }
+
+    // the price can not move within the same call, so the slippage is guarded by the minimum amounts
+    let (position_id, position, pool, x, y) = create_position_internal(
+        deps.storage,
+        env.block.time.millis(),
+        env.block.height,
+        &info.sender,
+        &pool_key,
+        lower_tick,
+        upper_tick,
+        liquidity_delta,
+        pool.sqrt_price,
+        pool.sqrt_price,
+    )?;
+
+    if x < amount_x_min || y < amount_y_min {
+        return Err(ContractError::PositionAmountUnderMinimum);
+    }
+
+    let asset_0 = Asset {
+        info: AssetInfo::from_denom(deps.api, pool_key.token_x.as_str()),
+        amount: x.into(),
+    };
+
+    let asset_1 = Asset {
+        info: AssetInfo::from_denom(deps.api, pool_key.token_y.as_str()),
+        amount: y.into(),
+    };
+
+    let mut msgs = vec![];
+    asset_0.transfer_from(&mut msgs, &info, env.contract.address.to_string())?;
+    asset_1.transfer_from(&mut msgs, &info, env.contract.address.to_string())?;
+
+    let event_attributes = vec![
+        attr("action", "create_position"),
+        attr("address", info.sender.as_str()),
+        attr(
+            "token_id",
+            Binary::from(state::token_id(position_id)).to_string(),
+        ),
+        attr("liquidity", liquidity_delta.to_string()),
+        attr("lower_tick", position.lower_tick_index.to_string()),
+        attr("upper_tick", position.upper_tick_index.to_string()),
+        attr("amount_x", x.to_string()),
+        attr("amount_y", y.to_string()),
+        attr("current_sqrt_price", pool.sqrt_price.to_string()),
+    ];
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attributes(event_attributes))
+}
+
+/// Adds liquidity to an existing position without opening a new one.
+///
+/// # Parameters
+/// - `index`: The index of the user position to top up.
+/// - `liquidity_delta`: The additional liquidity provided by the user in the position range.
+/// - `slippage_limit_lower`: The price limit for downward movement to execute the liquidity increase.
+/// - `slippage_limit_upper`: The price limit for upward movement to execute the liquidity increase.
+///
+/// # Events
+/// - On success, emits an `Increase Liquidity` event for the updated position.
+///
+/// # Errors
+/// - Fails if the user attempts to add zero liquidity.
+/// - Fails if the position cannot be found.
+/// - Fails if the price has reached the slippage limit.
+/// - Fails if the allowance is insufficient or the user balance transfer fails.
+pub fn increase_liquidity(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    index: u32,
+    liquidity_delta: Liquidity,
+    slippage_limit_lower: SqrtPrice,
+    slippage_limit_upper: SqrtPrice,
+) -> Result<Response, ContractError> {
+    let current_timestamp = env.block.time.millis();
+    let current_block_number = env.block.height;
+
+    if liquidity_delta.is_zero() {
+        return Err(ContractError::InsufficientLiquidity {});
+    }
+
+    let mut position = state::get_position(deps.storage, &info.sender, index)?;
+    state::check_not_paused(
+        deps.storage,
+        &PauseScope::PositionCreation,
+        &position.pool_key,
+    )?;
+
+    let mut lower_tick =
+        state::get_tick(deps.storage, &position.pool_key, position.lower_tick_index)?;
+    let mut upper_tick =
+        state::get_tick(deps.storage, &position.pool_key, position.upper_tick_index)?;
+    let pool_key_db = position.pool_key.key();
+    let mut pool = POOLS.load(deps.storage, &pool_key_db)?;
+    state::write_observation(deps.storage, &position.pool_key, &pool, current_timestamp)?;
+
+    let (x, y) = position.increase(
+        &mut pool,
+        &mut lower_tick,
+        &mut upper_tick,
+        current_timestamp,
+        liquidity_delta,
+        slippage_limit_lower,
+        slippage_limit_upper,
+        current_block_number,
+        position.pool_key.fee_tier.tick_spacing,
+    )?;
+
+    state::update_position(deps.storage, &info.sender, index, &position)?;
+    POOLS.save(deps.storage, &pool_key_db, &pool)?;
+    state::update_tick(
+        deps.storage,
+        &position.pool_key,
+        lower_tick.index,
+        &lower_tick,
+    )?;
+    state::update_tick(
+        deps.storage,
+        &position.pool_key,
+        upper_tick.index,
+        &upper_tick,
+    )?;
+
+    let asset_0 = Asset {
+        info: AssetInfo::from_denom(deps.api, position.pool_key.token_x.as_str()),
+        amount: x.into(),
+    };
+
+    let asset_1 = Asset {
+        info: AssetInfo::from_denom(deps.api, position.pool_key.token_y.as_str()),
+        amount: y.into(),
+    };
+
+    let mut msgs = vec![];
+    asset_0.transfer_from(&mut msgs, &info, env.contract.address.to_string())?;
+    asset_1.transfer_from(&mut msgs, &info, env.contract.address.to_string())?;
+
+    let event_attributes = vec![
+        attr("action", "increase_liquidity"),
+        attr("address", info.sender.as_str()),
+        attr("index", index.to_string()),
+        attr("liquidity", liquidity_delta.to_string()),
+        attr("amount_x", x.to_string()),
+        attr("amount_y", y.to_string()),
+        attr("current_sqrt_price", pool.sqrt_price.to_string()),
+    ];
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attributes(event_attributes))
+}
+
+/// Withdraws part of the liquidity of a position. Sends the withdrawn tokens together with the accrued fees to the owner.
+/// The position is removed once its liquidity reaches zero.
+///
+/// # Parameters
+/// - `index`: The index of the user position to withdraw from.
+/// - `liquidity_delta`: The liquidity to withdraw from the position.
+/// - `min_amount_x`: The minimum amount of token x expected from the withdrawn liquidity.
+/// - `min_amount_y`: The minimum amount of token y expected from the withdrawn liquidity.
+///
+/// # Events
+/// - On success, emits a `Decrease Liquidity` event for the updated position.
+///
+/// # Errors
+/// - Fails if the user attempts to withdraw zero liquidity.
+/// - Fails if the position cannot be found.
+/// - Fails if the position holds less liquidity than requested.
+/// - Fails if the withdrawn amounts are under the specified minimum amounts.
+pub fn decrease_liquidity(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    index: u32,
+    liquidity_delta: Liquidity,
+    min_amount_x: TokenAmount,
+    min_amount_y: TokenAmount,
+) -> Result<Response, ContractError> {
+    let current_timestamp = env.block.time.millis();
+
+    if liquidity_delta.is_zero() {
+        return Err(ContractError::InsufficientLiquidity {});
+    }
+
+    let mut position = state::get_position(deps.storage, &info.sender, index)?;
+
+    let mut lower_tick =
+        state::get_tick(deps.storage, &position.pool_key, position.lower_tick_index)?;
+    let mut upper_tick =
+        state::get_tick(deps.storage, &position.pool_key, position.upper_tick_index)?;
+
+    let pool_key_db = position.pool_key.key();
+    let mut pool = POOLS.load(deps.storage, &pool_key_db)?;
+    state::write_observation(deps.storage, &position.pool_key, &pool, current_timestamp)?;
+
+    let (withdrawn_x, withdrawn_y, deinitialize_lower_tick, deinitialize_upper_tick) = position
+        .decrease(
+            &mut pool,
+            current_timestamp,
+            &mut lower_tick,
+            &mut upper_tick,
+            liquidity_delta,
+            position.pool_key.fee_tier.tick_spacing,
+        )?;
+
+    if withdrawn_x < min_amount_x || withdrawn_y < min_amount_y {
+        return Err(ContractError::AmountUnderMinimumAmountOut);
+    }
+
+    // a fully withdrawn position is removed, so its incentive rewards are paid out with it
+    let rewards = if position.liquidity.is_zero() {
+        position.claim_incentives(&mut pool, &lower_tick, &upper_tick, current_timestamp)?
+    } else {
+        vec![]
+    };
+
+    let amount_x = withdrawn_x + position.tokens_owed_x;
+    let amount_y = withdrawn_y + position.tokens_owed_y;
+    position.tokens_owed_x = TokenAmount(0);
+    position.tokens_owed_y = TokenAmount(0);
+
+    POOLS.save(deps.storage, &pool_key_db, &pool)?;
+
+    if deinitialize_lower_tick {
+        remove_tick_and_flip_bitmap(deps.storage, &position.pool_key, &lower_tick)?;
+    } else {
+        state::update_tick(
+            deps.storage,
+            &position.pool_key,
+            position.lower_tick_index,
+            &lower_tick,
+        )?;
+    }
+
+    if deinitialize_upper_tick {
+        remove_tick_and_flip_bitmap(deps.storage, &position.pool_key, &upper_tick)?;
+    } else {
+        state::update_tick(
+            deps.storage,
+            &position.pool_key,
+            position.upper_tick_index,
+            &upper_tick,
+        )?;
+    }
+
+    if position.liquidity.is_zero() {
+        state::remove_position(deps.storage, &info.sender, index)?;
+    } else {
+        state::update_position(deps.storage, &info.sender, index, &position)?;
+    }
+
+    let asset_0 = Asset {
+        info: AssetInfo::from_denom(deps.api, position.pool_key.token_x.as_str()),
+        amount: amount_x.into(),
+    };
+
+    let asset_1 = Asset {
+        info: AssetInfo::from_denom(deps.api, position.pool_key.token_y.as_str()),
+        amount: amount_y.into(),
+    };
+
+    let mut msgs = vec![];
+    asset_0.transfer(&mut msgs, &info)?;
+    asset_1.transfer(&mut msgs, &info)?;
+    for reward in &rewards {
+        reward.transfer(&mut msgs, &info)?;
+    }
+
+    let event_attributes = vec![
+        attr("action", "decrease_liquidity"),
+        attr("address", info.sender.as_str()),
+        attr("index", index.to_string()),
+        attr("liquidity", liquidity_delta.to_string()),
+        attr("remaining_liquidity", position.liquidity.to_string()),
+        attr("amount_x", amount_x.to_string()),
+        attr("amount_y", amount_y.to_string()),
+        attr("current_sqrt_price", pool.sqrt_price.to_string()),
+    ];
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attributes(event_attributes))
+}
+
+/// Performs a single swap based on the provided parameters.
+///
+/// # Parameters
+/// - `pool_key`: A unique key that identifies the specified pool.
+/// - `x_to_y`: A boolean specifying the swap direction.
+/// - `amount`: TokenAmount that the user wants to swap.
+/// - `by_amount_in`: A boolean specifying whether the user provides the amount to swap or expects the amount out.
+/// - `sqrt_price_limit`: A square root of price limit allowing the price to move for the swap to occur.
+/// - `recipient`: An optional address receiving the swap output instead of the caller.
+/// - `deadline`: An optional block time after which the swap is rejected.
+/// - `allow_partial`: A boolean allowing the swap to stop at the price limit or where liquidity runs out,
+///   only the consumed input is taken and excess native funds are refunded.
+///
+/// # Events
+/// - On a successful swap, emits a `swap` event for the freshly made swap, with a `partial_fill` reason if it stopped early.
+/// - On a successful swap, emits a `cross_tick` event for every single tick crossed.
+///
+/// # Errors
+/// - Fails if the block time is past the deadline.
+/// - Fails if the user attempts to perform a swap with zero amounts.
+/// - Fails if the price has reached the specified price limit (or price associated with specified square root of price), unless `allow_partial` is set.
+/// - Fails if the user would receive zero tokens.
+/// - Fails if the allowance is insufficient or the user balance transfer fails.
+/// - Fails if there is insufficient liquidity in pool, unless `allow_partial` is set.
+/// - Fails if the swap would cross more ticks than the configured limit.
+/// - Fails if pool does not
+#[allow(clippy::too_many_arguments)]
+pub fn swap(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    pool_key: PoolKey,
+    x_to_y: bool,
+    amount: TokenAmount,
+    by_amount_in: bool,
+    sqrt_price_limit: SqrtPrice,
+    recipient: Option<String>,
+    deadline: Option<Timestamp>,
+    allow_partial: bool,
+) -> Result<Response, ContractError> {
+    check_deadline(&env.block, deadline)?;
+
+    let recipient = match recipient {
+        Some(recipient) => deps.api.addr_validate(&recipient)?,
+        None => info.sender.clone(),
+    };
+
+    let mut msgs = vec![];
+
+    let result = swap_internal(
+        deps.storage,
+        deps.api,
+        &info,
+        &mut msgs,
+        &env.contract.address,
+        &recipient,
+        env.block.time.millis(),
+        &pool_key,
+        x_to_y,
+        amount,
+        by_amount_in,
+        sqrt_price_limit,
+        allow_partial,
+    )?;
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_events(swap_events(
+            &info.sender,
+            &recipient,
+            &pool_key,
+            x_to_y,
+            &result,
+        ))
+        .add_attribute("action", "swap")
+        .add_attribute("recipient", recipient)
+        .add_attribute("amount_in", result.amount_in.to_string())
+        .add_attribute("amount_out", result.amount_out.to_string()))
+}
+
+/// Performs atomic swap involving several pools based on the provided parameters.
+///
+/// # Parameters
+/// - `amount_in`: The amount of tokens that the user wants to swap.
+/// - `expected_amount_out`: The amount of tokens that the user wants to receive as a result of the swaps.
+/// - `slippage`: The max acceptable percentage difference between the expected and actual amount of output tokens in a trade, not considering square root of target price as in the case of a swap.
+/// - `swaps`: A vector containing all parameters needed to identify separate swap steps.
+/// - `recipient`: An optional address receiving the final output instead of the caller.
+/// - `deadline`: An optional block time after which the swap is rejected.
+///
+/// # Events
+/// - On every successful swap, emits a `swap` event for the freshly made swap.
+/// - On every successful swap, emits a `cross_tick` event for every single tick crossed.
+///
+/// # Errors
+/// - Fails if the block time is past the deadline.
+/// - Fails if the user attempts to perform a swap with zero amounts.
+/// - Fails if the user would receive zero tokens.
+/// - Fails if the allowance is insufficient or the user balance transfer fails.
+/// - Fails if the minimum amount out after a single swap is insufficient to perform the next swap to achieve the expected amount out.
+/// - Fails if pool does not exist
+///
+/// # External contracts
+#[allow(clippy::too_many_arguments)]
+pub fn swap_route(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    amount_in: TokenAmount,
+    expected_amount_out: TokenAmount,
+    slippage: Percentage,
+    swaps: Vec<SwapHop>,
+    recipient: Option<String>,
+    deadline: Option<Timestamp>,
+) -> Result<Response, ContractError> {
+    check_deadline(&env.block, deadline)?;
+
+    let recipient = match recipient {
+        Some(recipient) => deps.api.addr_validate(&recipient)?,
+        None => info.sender.clone(),
+    };
+
+    let mut msgs = vec![];
+    let results = swap_route_internal(
+        deps.storage,
+        deps.api,
+        env.clone(),
+        &info,
+        &mut msgs,
+        &recipient,
+        amount_in,
+        &swaps,
+    )?;
+    let (_, amount_out) = route_amounts(&results);
+
+    let min_amount_out = calculate_min_amount_out(expected_amount_out, slippage);
+
+    if amount_out < min_amount_out {
+        return Err(ContractError::AmountUnderMinimumAmountOut);
+    }
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_events(route_events(
+            &info.sender,
+            &env.contract.address,
+            &recipient,
+            &swaps,
+            &results,
+        ))
+        .add_attribute("action", "swap_route")
+        .add_attribute("recipient", recipient)
+        .add_attribute("amount_out", amount_out.to_string()))
+}
+
+/// Performs atomic swap involving several pools that delivers an exact amount of the last token.
+/// The hops are sized backwards from `amount_out`, so each pool only receives what the next one needs.
+///
+/// # Parameters
+/// - `amount_out`: The exact amount of tokens that the user wants to receive from the last swap.
+/// - `max_amount_in`: The maximum amount of the first token the user is willing to pay.
+/// - `swaps`: A vector containing all parameters needed to identify separate swap steps.
+/// - `recipient`: An optional address receiving the final output instead of the caller.
+/// - `deadline`: An optional block time after which the swap is rejected.
+///
+/// # Events
+/// - On every successful swap, emits a `swap` event for the freshly made swap, in route order.
+/// - On every successful swap, emits a `cross_tick` event for every single tick crossed.
+///
+/// # Errors
+/// - Fails if the block time is past the deadline.
+/// - Fails if the user attempts to perform a swap with zero amounts.
+/// - Fails if the required input exceeds `max_amount_in`.
+/// - Fails if the allowance is insufficient or the user balance transfer fails.
+/// - Fails if pool does not exist
+#[allow(clippy::too_many_arguments)]
+pub fn swap_route_exact_out(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    amount_out: TokenAmount,
+    max_amount_in: TokenAmount,
+    swaps: Vec<SwapHop>,
+    recipient: Option<String>,
+    deadline: Option<Timestamp>,
+) -> Result<Response, ContractError> {
+    check_deadline(&env.block, deadline)?;
 
-    POOLS.save(deps.storage, &pool_key_db, &pool)?;
+    let recipient = match recipient {
+        Some(recipient) => deps.api.addr_validate(&recipient)?,
+        None => info.sender.clone(),
+    };
 
-    state::add_position(deps.storage, &info.sender, &position)?;
+    let results =
+        execute_swap_route_exact_out(deps.storage, env.block.time.millis(), amount_out, &swaps)?;
+    let (amount_in, amount_out) = route_amounts(&results);
 
-    state::update_tick(deps.storage, &pool_key, lower_tick.index, &lower_tick)?;
-    state::update_tick(deps.storage, &pool_key, upper_tick.index, &upper_tick)?;
+    if amount_in > max_amount_in {
+        return Err(ContractError::AmountOverMaximumAmountIn);
+    }
 
-    let asset_0 = Asset {
-        info: AssetInfo::from_denom(deps.api, pool_key.token_x.as_str()),
-        amount: x.into(),
+    let asset_in = Asset {
+        info: AssetInfo::from_denom(deps.api, swaps[0].token_in()),
+        amount: amount_in.into(),
     };
 
-    let asset_1 = Asset {
-        info: AssetInfo::from_denom(deps.api, pool_key.token_y.as_str()),
-        amount: y.into(),
+    let asset_out = Asset {
+        info: AssetInfo::from_denom(deps.api, swaps[swaps.len() - 1].token_out()),
+        amount: amount_out.into(),
     };
 
     let mut msgs = vec![];
-    asset_0.transfer_from(&mut msgs, &info, env.contract.address.to_string())?;
-    asset_1.transfer_from(&mut msgs, &info, env.contract.address.to_string())?;
-
-    let event_attributes = vec![
-        attr("action", "create_position"),
-        attr("address", info.sender.as_str()),
-        attr("liquidity", liquidity_delta.to_string()),
-        attr("lower_tick", lower_tick.index.to_string()),
-        attr("upper_tick", upper_tick.index.to_string()),
-        attr("current_sqrt_price", pool.sqrt_price.to_string()),
-    ];
+    asset_in.transfer_from(&mut msgs, &info, env.contract.address.to_string())?;
+    asset_out.transfer_to(&mut msgs, &recipient)?;
 
     Ok(Response::new()
         .add_messages(msgs)
-        .add_attributes(event_attributes))
+        .add_events(route_events(
+            &info.sender,
+            &env.contract.address,
+            &recipient,
+            &swaps,
+            &results,
+        ))
+        .add_attribute("action", "swap_route_exact_out")
+        .add_attribute("recipient", recipient)
+        .add_attribute("amount_in", amount_in.to_string())
+        .add_attribute("amount_out", amount_out.to_string()))
 }
 
-/// Performs a single swap based on the provided parameters.
+/// Performs a swap split into several routes between the same tokens,
+/// e.g. across pools of the same pair in different fee tiers.
 ///
 /// # Parameters
-/// - `pool_key`: A unique key that identifies the specified pool.
-/// - `x_to_y`: A boolean specifying the swap direction.
-/// - `amount`: TokenAmount that the user wants to swap.
-/// - `by_amount_in`: A boolean specifying whether the user provides the amount to swap or expects the amount out.
-/// - `sqrt_price_limit`: A square root of price limit allowing the price to move for the swap to occur.
+/// - `legs`: The routes with the amount of the first token each of them swaps.
+/// - `min_amount_out`: The minimum total amount of the last token the user is willing to receive.
+/// - `recipient`: An optional address receiving the output instead of the caller.
+/// - `deadline`: An optional block time after which the swap is rejected.
 ///
 /// # Events
-/// - On a successful swap, emits a `Swap` event for the freshly made swap.
-/// - On a successful swap, emits a `Cross Tick` event for every single tick crossed.
+/// - On every successful swap, emits a `swap` event for the freshly made swap, in leg and route order.
+/// - On every successful swap, emits a `cross_tick` event for every single tick crossed.
 ///
 /// # Errors
+/// - Fails if the block time is past the deadline.
+/// - Fails if there are no legs, a leg has no swaps or the legs do not share the first and last token.
+/// - Fails if there are more than `MAX_SPLIT_ROUTES` legs.
 /// - Fails if the user attempts to perform a swap with zero amounts.
-/// - Fails if the price has reached the specified price limit (or price associated with specified square root of price).
-/// - Fails if the user would receive zero tokens.
+/// - Fails if the total amount out is below `min_amount_out`.
 /// - Fails if the allowance is insufficient or the user balance transfer fails.
-/// - Fails if there is insufficient liquidity in pool
-/// - Fails if pool does not
+/// - Fails if pool does not exist
+pub fn swap_split(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    legs: Vec<(Vec<SwapHop>, TokenAmount)>,
+    min_amount_out: TokenAmount,
+    recipient: Option<String>,
+    deadline: Option<Timestamp>,
+) -> Result<Response, ContractError> {
+    check_deadline(&env.block, deadline)?;
+
+    let recipient = match recipient {
+        Some(recipient) => deps.api.addr_validate(&recipient)?,
+        None => info.sender.clone(),
+    };
+
+    let mut msgs = vec![];
+    let results = swap_split_internal(
+        deps.storage,
+        deps.api,
+        env.clone(),
+        &info,
+        &mut msgs,
+        &recipient,
+        &legs,
+    )?;
+    let (amount_in, amount_out) = split_amounts(&results);
+
+    if amount_out < min_amount_out {
+        return Err(ContractError::AmountUnderMinimumAmountOut);
+    }
+
+    let events = legs
+        .iter()
+        .zip(&results)
+        .flat_map(|((swaps, _), leg_results)| {
+            route_events(
+                &info.sender,
+                &env.contract.address,
+                &recipient,
+                swaps,
+                leg_results,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_events(events)
+        .add_attribute("action", "swap_split")
+        .add_attribute("recipient", recipient)
+        .add_attribute("amount_in", amount_in.to_string())
+        .add_attribute("amount_out", amount_out.to_string()))
+}
+
+/// Handles a cw20 `Send`, funding the hooked action with the received tokens.
+///
+/// # Parameters
+/// - `cw20_msg`: The receive message forwarded by the cw20 contract, `msg` holds a `Cw20HookMsg`.
+///
+/// # Errors
+/// - Fails if the hook message can not be parsed.
+/// - Fails if the sending cw20 contract is not the input token of the hooked action.
+/// - Fails if the received amount does not cover the hooked action.
+pub fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let token = info.sender;
+    let amount = TokenAmount(cw20_msg.amount.u128());
+    // the hooked action is performed on behalf of the original sender
+    let sender_info = MessageInfo {
+        sender: deps.api.addr_validate(&cw20_msg.sender)?,
+        funds: vec![],
+    };
+
+    let hook_msg: Cw20HookMsg = from_binary(&cw20_msg.msg)?;
+    match hook_msg {
+        Cw20HookMsg::Swap {
+            pool_key,
+            x_to_y,
+            amount: swap_amount,
+            by_amount_in,
+            sqrt_price_limit,
+        } => receive_swap(
+            deps,
+            env,
+            sender_info,
+            token,
+            amount,
+            pool_key,
+            x_to_y,
+            swap_amount,
+            by_amount_in,
+            sqrt_price_limit,
+        ),
+        Cw20HookMsg::SwapRoute {
+            expected_amount_out,
+            slippage,
+            swaps,
+        } => receive_swap_route(
+            deps,
+            env,
+            sender_info,
+            token,
+            amount,
+            expected_amount_out,
+            slippage,
+            swaps,
+        ),
+        Cw20HookMsg::CreatePosition {
+            pool_key,
+            lower_tick,
+            upper_tick,
+            liquidity_delta,
+            slippage_limit_lower,
+            slippage_limit_upper,
+        } => receive_create_position(
+            deps,
+            env,
+            sender_info,
+            token,
+            amount,
+            pool_key,
+            lower_tick,
+            upper_tick,
+            liquidity_delta,
+            slippage_limit_lower,
+            slippage_limit_upper,
+        ),
+    }
+}
+
+/// Refunds the part of the received cw20 amount that was not used by the hooked action.
+fn refund_cw20(
+    msgs: &mut Vec<CosmosMsg>,
+    info: &MessageInfo,
+    token: &Addr,
+    received: TokenAmount,
+    used: TokenAmount,
+) -> Result<(), ContractError> {
+    if used > received {
+        return Err(ContractError::InvalidFunds {
+            transfer_amount: used.into(),
+        });
+    }
+
+    let refund = Asset {
+        info: AssetInfo::Token {
+            contract_addr: token.clone(),
+        },
+        amount: (received - used).into(),
+    };
+    refund.transfer(msgs, info)
+}
+
 #[allow(clippy::too_many_arguments)]
-pub fn swap(
+fn receive_swap(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
+    token: Addr,
+    received: TokenAmount,
     pool_key: PoolKey,
     x_to_y: bool,
     amount: TokenAmount,
     by_amount_in: bool,
     sqrt_price_limit: SqrtPrice,
 ) -> Result<Response, ContractError> {
-    let mut msgs = vec![];
+    let token_in = if x_to_y {
+        &pool_key.token_x
+    } else {
+        &pool_key.token_y
+    };
+    if token.as_str() != token_in.as_str() {
+        return Err(ContractError::InvalidCw20Token);
+    }
 
-    let CalculateSwapResult {
-        amount_in,
-        amount_out,
-        ..
-    } = swap_internal(
+    let result = execute_swap(
         deps.storage,
-        deps.api,
-        &info,
-        &mut msgs,
-        &env.contract.address,
         env.block.time.millis(),
         &pool_key,
         x_to_y,
         amount,
         by_amount_in,
         sqrt_price_limit,
+        false,
     )?;
+    let (amount_in, amount_out) = (result.amount_in, result.amount_out);
+
+    let token_out = if x_to_y {
+        &pool_key.token_y
+    } else {
+        &pool_key.token_x
+    };
+    let asset_out = Asset {
+        info: AssetInfo::from_denom(deps.api, token_out.as_str()),
+        amount: amount_out.into(),
+    };
+
+    let mut msgs = vec![];
+    refund_cw20(&mut msgs, &info, &token, received, amount_in)?;
+    asset_out.transfer(&mut msgs, &info)?;
 
     Ok(Response::new()
         .add_messages(msgs)
+        .add_events(swap_events(
+            &info.sender,
+            &info.sender,
+            &pool_key,
+            x_to_y,
+            &result,
+        ))
         .add_attribute("action", "swap")
         .add_attribute("amount_in", amount_in.to_string())
         .add_attribute("amount_out", amount_out.to_string()))
 }
 
-/// Performs atomic swap involving several pools based on the provided parameters.
-///
-/// # Parameters
-/// - `amount_in`: The amount of tokens that the user wants to swap.
-/// - `expected_amount_out`: The amount of tokens that the user wants to receive as a result of the swaps.
-/// - `slippage`: The max acceptable percentage difference between the expected and actual amount of output tokens in a trade, not considering square root of target price as in the case of a swap.
-/// - `swaps`: A vector containing all parameters needed to identify separate swap steps.
-///
-/// # Events
-/// - On every successful swap, emits a `Swap` event for the freshly made swap.
-/// - On every successful swap, emits a `Cross Tick` event for every single tick crossed.
-///
-/// # Errors
-/// - Fails if the user attempts to perform a swap with zero amounts.
-/// - Fails if the user would receive zero tokens.
-/// - Fails if the allowance is insufficient or the user balance transfer fails.
-/// - Fails if the minimum amount out after a single swap is insufficient to perform the next swap to achieve the expected amount out.
-/// - Fails if pool does not exist
-///
-/// # External contracts
-pub fn swap_route(
+#[allow(clippy::too_many_arguments)]
+fn receive_swap_route(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    amount_in: TokenAmount,
+    token: Addr,
+    received: TokenAmount,
     expected_amount_out: TokenAmount,
     slippage: Percentage,
     swaps: Vec<SwapHop>,
 ) -> Result<Response, ContractError> {
-    let mut msgs = vec![];
-    let amount_out = swap_route_internal(
-        deps.storage,
-        deps.api,
-        env,
-        &info,
-        &mut msgs,
-        amount_in,
-        swaps,
-    )?;
+    let first_hop = swaps.first().ok_or(ContractError::EmptySwapRoute)?;
+    if token.as_str() != first_hop.token_in() {
+        return Err(ContractError::InvalidCw20Token);
+    }
 
-    let min_amount_out = calculate_min_amount_out(expected_amount_out, slippage);
+    let results = execute_swap_route(deps.storage, env.block.time.millis(), received, &swaps)?;
+    let (amount_in, amount_out) = route_amounts(&results);
 
+    let min_amount_out = calculate_min_amount_out(expected_amount_out, slippage);
     if amount_out < min_amount_out {
         return Err(ContractError::AmountUnderMinimumAmountOut);
     }
 
+    let asset_out = Asset {
+        info: AssetInfo::from_denom(deps.api, swaps[swaps.len() - 1].token_out()),
+        amount: amount_out.into(),
+    };
+
+    let mut msgs = vec![];
+    refund_cw20(&mut msgs, &info, &token, received, amount_in)?;
+    asset_out.transfer(&mut msgs, &info)?;
+
     Ok(Response::new()
         .add_messages(msgs)
+        .add_events(route_events(
+            &info.sender,
+            &env.contract.address,
+            &info.sender,
+            &swaps,
+            &results,
+        ))
         .add_attribute("action", "swap_route")
         .add_attribute("amount_out", amount_out.to_string()))
 }
 
+#[allow(clippy::too_many_arguments)]
+fn receive_create_position(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token: Addr,
+    received: TokenAmount,
+    pool_key: PoolKey,
+    lower_tick: i32,
+    upper_tick: i32,
+    liquidity_delta: Liquidity,
+    slippage_limit_lower: SqrtPrice,
+    slippage_limit_upper: SqrtPrice,
+) -> Result<Response, ContractError> {
+    let received_x = token.as_str() == pool_key.token_x;
+    if !received_x && token.as_str() != pool_key.token_y {
+        return Err(ContractError::InvalidCw20Token);
+    }
+
+    let (position_id, position, pool, x, y) = create_position_internal(
+        deps.storage,
+        env.block.time.millis(),
+        env.block.height,
+        &info.sender,
+        &pool_key,
+        lower_tick,
+        upper_tick,
+        liquidity_delta,
+        slippage_limit_lower,
+        slippage_limit_upper,
+    )?;
+
+    // the received token is already held by the contract, the other side is pulled as usual
+    let (used, other_token, other_amount) = if received_x {
+        (x, &pool_key.token_y, y)
+    } else {
+        (y, &pool_key.token_x, x)
+    };
+
+    let other_asset = Asset {
+        info: AssetInfo::from_denom(deps.api, other_token.as_str()),
+        amount: other_amount.into(),
+    };
+
+    let mut msgs = vec![];
+    refund_cw20(&mut msgs, &info, &token, received, used)?;
+    other_asset.transfer_from(&mut msgs, &info, env.contract.address.to_string())?;
+
+    let event_attributes = vec![
+        attr("action", "create_position"),
+        attr("address", info.sender.as_str()),
+        attr(
+            "token_id",
+            Binary::from(state::token_id(position_id)).to_string(),
+        ),
+        attr("liquidity", liquidity_delta.to_string()),
+        attr("lower_tick", position.lower_tick_index.to_string()),
+        attr("upper_tick", position.upper_tick_index.to_string()),
+        attr("current_sqrt_price", pool.sqrt_price.to_string()),
+    ];
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attributes(event_attributes))
+}
+
 /// Transfers a position between users.
 ///
 /// # Parameters
@@ -367,14 +1696,21 @@ pub fn transfer_position(
 
     let mut position = state::get_position(deps.storage, &caller, index)?;
 
-    state::remove_position(deps.storage, &caller, index)?;
-
     let receiver_addr = deps.api.addr_validate(&receiver)?;
+    let receiver_index = state::transfer_position(deps.storage, &caller, index, &receiver_addr)?;
     // reset approvals
     position.approvals = vec![];
-    state::add_position(deps.storage, &receiver_addr, &position)?;
+    state::update_position(deps.storage, &receiver_addr, receiver_index, &position)?;
 
-    Ok(Response::new().add_attribute("action", "transfer_position"))
+    Ok(Response::new()
+        .add_attribute("action", "transfer_position")
+        .add_attributes(pool_key_attributes(&position.pool_key))
+        .add_attribute("sender", caller.as_str())
+        .add_attribute("receiver", receiver_addr.as_str())
+        .add_attribute("index", index.to_string())
+        .add_attribute("liquidity", position.liquidity.to_string())
+        .add_attribute("lower_tick", position.lower_tick_index.to_string())
+        .add_attribute("upper_tick", position.upper_tick_index.to_string()))
 }
 
 /// Allows an authorized user (owner of the position) to claim collected fees.
@@ -444,6 +1780,46 @@ pub fn claim_fee(
         .add_attribute("amount_y", y.to_string()))
 }
 
+/// Claims the incentive rewards accrued by the position.
+///
+/// # Parameters
+/// - `index`: The index of the user position.
+///
+/// # Errors
+/// - Fails if position cannot be found
+pub fn claim_incentives(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    index: u32,
+) -> Result<Response, ContractError> {
+    let current_timestamp = env.block.time.millis();
+
+    let mut position = state::get_position(deps.storage, &info.sender, index)?;
+    let lower_tick = state::get_tick(deps.storage, &position.pool_key, position.lower_tick_index)?;
+    let upper_tick = state::get_tick(deps.storage, &position.pool_key, position.upper_tick_index)?;
+    let pool_key_db = position.pool_key.key();
+    let mut pool = POOLS.load(deps.storage, &pool_key_db)?;
+
+    let rewards =
+        position.claim_incentives(&mut pool, &lower_tick, &upper_tick, current_timestamp)?;
+
+    state::update_position(deps.storage, &info.sender, index, &position)?;
+    POOLS.save(deps.storage, &pool_key_db, &pool)?;
+
+    let mut msgs = vec![];
+    let mut event_attributes = vec![attr("action", "claim_incentives")];
+    for reward in &rewards {
+        reward.transfer(&mut msgs, &info)?;
+        event_attributes.push(attr("reward_token", reward.info.denom()));
+        event_attributes.push(attr("reward_amount", reward.amount.to_string()));
+    }
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attributes(event_attributes))
+}
+
 /// Removes a position. Sends tokens associated with specified position to the owner.
 ///
 /// # Parameters
@@ -472,6 +1848,7 @@ pub fn remove_position(
 
     let pool_key_db = position.pool_key.key();
     let mut pool = POOLS.load(deps.storage, &pool_key_db)?;
+    state::write_observation(deps.storage, &position.pool_key, &pool, current_timestamp)?;
 
     let (amount_x, amount_y, deinitialize_lower_tick, deinitialize_upper_tick) = position.remove(
         &mut pool,
@@ -480,6 +1857,9 @@ pub fn remove_position(
         &mut upper_tick,
         position.pool_key.fee_tier.tick_spacing,
     )?;
+    // the position is gone afterwards, so its incentive rewards are paid out with it
+    let rewards =
+        position.claim_incentives(&mut pool, &lower_tick, &upper_tick, current_timestamp)?;
 
     POOLS.save(deps.storage, &pool_key_db, &pool)?;
 
@@ -519,6 +1899,9 @@ pub fn remove_position(
     let mut msgs = vec![];
     asset_0.transfer(&mut msgs, &info)?;
     asset_1.transfer(&mut msgs, &info)?;
+    for reward in &rewards {
+        reward.transfer(&mut msgs, &info)?;
+    }
 
     let event_attributes = vec![
         attr("action", "remove_position"),
@@ -551,10 +1934,14 @@ pub fn remove_position(
 /// - Fails if Pool with same tokens and fee tier already exist.
 /// - Fails if the init tick is not divisible by the tick spacing.
 /// - Fails if the init sqrt price is not related to the init tick.
+/// - Fails if the pool creation policy does not allow the caller to create the pool.
+/// - Fails if the pool creation fee is not paid or other funds are attached, a fee overpayment is
+///   refunded.
 #[allow(clippy::too_many_arguments)]
 pub fn create_pool(
     deps: DepsMut,
     env: Env,
+    info: MessageInfo,
     token_0: String,
     token_1: String,
     fee_tier: FeeTier,
@@ -572,13 +1959,61 @@ pub fn create_pool(
     check_tick(init_tick, fee_tier.tick_spacing)?;
 
     let pool_key = PoolKey::new(token_0, token_1, fee_tier)?;
+    state::check_not_paused(deps.storage, &PauseScope::PositionCreation, &pool_key)?;
     let db_key = pool_key.key();
 
     if POOLS.has(deps.storage, &db_key) {
         return Err(ContractError::PoolAlreadyExist);
     }
 
-    let config = CONFIG.load(deps.storage)?;
+    let pool_creation_fee = if info.sender == config.admin {
+        None
+    } else {
+        if !config.pool_creation_policy.allows(&pool_key) {
+            return Err(ContractError::PoolCreationNotAllowed);
+        }
+        config.pool_creation_fee.as_ref()
+    };
+
+    let mut msgs: Vec<CosmosMsg> = vec![];
+    match pool_creation_fee {
+        None => {
+            if !info.funds.is_empty() {
+                return Err(ContractError::InvalidFunds {
+                    transfer_amount: Uint128::zero(),
+                });
+            }
+        }
+        Some(fee) => {
+            let paid = match &info.funds[..] {
+                [coin] if coin.denom == fee.denom => coin.amount,
+                _ => Uint128::zero(),
+            };
+            if paid < fee.amount {
+                return Err(ContractError::InvalidFunds {
+                    transfer_amount: fee.amount,
+                });
+            }
+
+            msgs.push(
+                BankMsg::Send {
+                    to_address: fee.treasury.to_string(),
+                    amount: coins(fee.amount.u128(), &fee.denom),
+                }
+                .into(),
+            );
+            let refund = paid - fee.amount;
+            if !refund.is_zero() {
+                msgs.push(
+                    BankMsg::Send {
+                        to_address: info.sender.to_string(),
+                        amount: coins(refund.u128(), &fee.denom),
+                    }
+                    .into(),
+                );
+            }
+        }
+    }
 
     let pool = Pool::create(
         init_sqrt_price,
@@ -590,8 +2025,87 @@ pub fn create_pool(
     .map_err(|_| ContractError::CreatePoolError)?;
 
     POOLS.save(deps.storage, &db_key, &pool)?;
+    state::add_token_pools(deps.storage, &pool_key)?;
+    state::initialize_oracle(deps.storage, &pool_key, current_timestamp)?;
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attribute("action", "create_pool")
+        .add_attributes(pool_key_attributes(&pool_key))
+        .add_attribute("sender", info.sender.as_str())
+        .add_attribute("init_sqrt_price", init_sqrt_price.to_string())
+        .add_attribute("init_tick", init_tick.to_string()))
+}
+
+/// Creates a new pool at the given price, deriving the initial tick from it.
+///
+/// # Parameters
+/// - `token_0`: The address of the first token.
+/// - `token_1`: The address of the second token.
+/// - `fee_tier`: A struct identifying the pool fee and tick spacing.
+/// - `init_sqrt_price`: The square root of the price for the initial pool.
+///
+/// # Errors
+/// - Fails if the sqrt price is out of range.
+/// - Fails for the same reasons as `create_pool`.
+pub fn create_pool_with_sqrt_price(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_0: String,
+    token_1: String,
+    fee_tier: FeeTier,
+    init_sqrt_price: SqrtPrice,
+) -> Result<Response, ContractError> {
+    // the tick is rounded down to the closest multiple of the tick spacing
+    let init_tick = get_tick_at_sqrt_price(init_sqrt_price, fee_tier.tick_spacing)?;
+
+    // the chosen tick is part of the `create_pool` attributes
+    create_pool(
+        deps,
+        env,
+        info,
+        token_0,
+        token_1,
+        fee_tier,
+        init_sqrt_price,
+        init_tick,
+    )
+}
+
+/// Grows the oracle ring buffer of a pool so it keeps a longer history of observations.
+/// The call is permissionless, the caller pays for the extra storage.
+///
+/// # Parameters
+/// - `pool_key`: A unique key that identifies the specified pool.
+/// - `observation_cardinality_next`: The desired number of observations kept for the pool.
+///
+/// # Errors
+/// - Fails if pool does not exist
+/// - Fails if the cardinality exceeds `MAX_OBSERVATION_CARDINALITY`.
+pub fn increase_observation_cardinality(
+    deps: DepsMut,
+    env: Env,
+    pool_key: PoolKey,
+    observation_cardinality_next: u16,
+) -> Result<Response, ContractError> {
+    if !POOLS.has(deps.storage, &pool_key.key()) {
+        return Err(ContractError::PoolNotFound);
+    }
+
+    let oracle = state::increase_observation_cardinality(
+        deps.storage,
+        &pool_key,
+        env.block.time.millis(),
+        observation_cardinality_next,
+    )?;
 
-    Ok(Response::new().add_attribute("action", "create_pool"))
+    Ok(Response::new()
+        .add_attribute("action", "increase_observation_cardinality")
+        .add_attribute(
+            "observation_cardinality_next",
+            oracle.cardinality_next.to_string(),
+        ))
 }
 
 /// Allows admin to add a custom fee tier.
@@ -629,7 +2143,10 @@ pub fn add_fee_tier(
 
     CONFIG.save(deps.storage, &config)?;
 
-    Ok(Response::new().add_attribute("action", "add_fee_tier"))
+    Ok(Response::new()
+        .add_attribute("action", "add_fee_tier")
+        .add_attribute("fee", fee_tier.fee.to_string())
+        .add_attribute("tick_spacing", fee_tier.tick_spacing.to_string()))
 }
 
 /// Removes an existing fee tier.
@@ -763,10 +2280,10 @@ pub fn handle_burn(
     info: MessageInfo,
     token_id: Binary,
 ) -> Result<Response, ContractError> {
-    let pos = state::get_position_by_key(deps.storage, &token_id)?;
-    let owner_raw = &token_id[..token_id.len() - 4];
-    let index = u32::from_be_bytes(token_id[token_id.len() - 4..].try_into().unwrap());
-    check_can_send(deps.as_ref(), &env, &info, owner_raw, &pos)?;
+    let position_id = state::parse_token_id(&token_id)?;
+    let (owner, index) = state::get_position_owner(deps.storage, position_id)?;
+    let pos = state::get_position_by_id(deps.storage, position_id)?;
+    check_can_send(deps.as_ref(), &env, &info, owner.as_bytes(), &pos)?;
 
     // remain action to help sync backend
     remove_position(deps, env, info.clone(), index)
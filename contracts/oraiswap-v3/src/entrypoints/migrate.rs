@@ -0,0 +1,171 @@
+use cosmwasm_std::{Addr, DepsMut, Env, Order, Storage};
+use cw_storage_plus::Map;
+
+use crate::{
+    msg::MigrateMsg,
+    state::{
+        self, CONFIG, MAX_LIMIT, NEXT_POSITION_ID, OWNER_POSITIONS, POOLS, POOL_POSITIONS,
+        POSITIONS, POSITION_OWNERS,
+    },
+    ContractError, PoolKey, Position,
+};
+
+use super::TimeStampExt;
+
+pub type Version = (u64, u64, u64);
+
+/// A state transform upgrading the storage written by versions older than `version`.
+struct Migration {
+    version: Version,
+    name: &'static str,
+    migrate: fn(DepsMut, &Env, &MigrateMsg) -> Result<(), ContractError>,
+}
+
+/// State transforms in ascending version order, each runs once when a migration crosses its version.
+/// Deployed contracts record 0.1.0 or 0.2.0, both store the same state, so everything introduced
+/// since then is upgraded when crossing 0.3.0.
+const MIGRATIONS: &[Migration] = &[Migration {
+    version: (0, 3, 0),
+    name: "0.3.0",
+    migrate: migrate_0_3_0,
+}];
+
+/// Positions keyed by `position_key(owner, index)`, written before 0.3.0.
+const LEGACY_POSITIONS: Map<&[u8], Position> = Map::new("positions");
+
+/// Parses a `major.minor.patch` version, pre-release and build suffixes are ignored.
+pub fn parse_version(version: &str) -> Result<Version, ContractError> {
+    let core = version.split(['-', '+']).next().unwrap_or_default();
+    let parts = core
+        .split('.')
+        .map(|part| part.parse::<u64>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| ContractError::InvalidVersion {
+            version: version.to_string(),
+        })?;
+
+    match parts[..] {
+        [major, minor, patch] => Ok((major, minor, patch)),
+        _ => Err(ContractError::InvalidVersion {
+            version: version.to_string(),
+        }),
+    }
+}
+
+/// Runs every state transform in `(from, to]` in order.
+///
+/// # Parameters
+/// - `from`: version of the stored state.
+/// - `to`: version of the new code.
+///
+/// # Errors
+/// - Fails if `from` is newer than `to`.
+pub fn run_migrations(
+    mut deps: DepsMut,
+    env: &Env,
+    msg: &MigrateMsg,
+    from: Version,
+    to: Version,
+) -> Result<Vec<&'static str>, ContractError> {
+    if from > to {
+        return Err(ContractError::MigrationDowngrade);
+    }
+
+    let mut applied = vec![];
+    for migration in MIGRATIONS
+        .iter()
+        .filter(|migration| migration.version > from && migration.version <= to)
+    {
+        (migration.migrate)(deps.branch(), env, msg)?;
+        applied.push(migration.name);
+    }
+
+    Ok(applied)
+}
+
+/// Upgrades state written before pause controls, per-pool oracles, the token pool index and stable
+/// position ids existed. The new fields of `Config`, `Pool`, `Tick` and `Position` deserialize to
+/// their defaults, so the guardian is set, the existing pools get their oracle and are indexed by
+/// token, and the first `MigrateMsg::position_limit` positions are moved to stable ids. The rest is
+/// moved with `ExecuteMsg::MigratePositions` so a large position set does not have to fit in a
+/// single transaction.
+fn migrate_0_3_0(deps: DepsMut, env: &Env, msg: &MigrateMsg) -> Result<(), ContractError> {
+    if let Some(guardian) = &msg.guardian {
+        let mut config = CONFIG.load(deps.storage)?;
+        config.guardian = Some(deps.api.addr_validate(guardian)?);
+        CONFIG.save(deps.storage, &config)?;
+    }
+
+    let pool_keys = POOLS
+        .keys_raw(deps.storage, None, None, Order::Ascending)
+        .map(|raw_key| PoolKey::from_bytes(&raw_key))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let current_timestamp = env.block.time.millis();
+    let cardinality = msg.observation_cardinality.unwrap_or(1);
+    for pool_key in pool_keys {
+        state::increase_observation_cardinality(
+            deps.storage,
+            &pool_key,
+            current_timestamp,
+            cardinality,
+        )?;
+        state::add_token_pools(deps.storage, &pool_key)?;
+    }
+
+    migrate_legacy_positions(deps.storage, msg.position_limit.unwrap_or(MAX_LIMIT))?;
+
+    Ok(())
+}
+
+/// Moves up to `limit` legacy positions to stable ids and builds their pool position index, the
+/// order of the legacy keys decides the ids. Owner indexes are kept, so index based messages keep
+/// addressing the same positions. Moved positions leave the legacy map, which makes the remaining
+/// legacy keys the cursor of the next batch.
+///
+/// Returns the number of moved positions and whether legacy positions remain.
+pub fn migrate_legacy_positions(
+    store: &mut dyn Storage,
+    limit: u32,
+) -> Result<(u32, bool), ContractError> {
+    let mut positions = LEGACY_POSITIONS
+        .range_raw(store, None, None, Order::Ascending)
+        .take(limit as usize + 1)
+        .collect::<Result<Vec<_>, _>>()?;
+    let pending = positions.len() > limit as usize;
+    positions.truncate(limit as usize);
+
+    let mut position_id = NEXT_POSITION_ID.may_load(store)?.unwrap_or_default();
+    let migrated = positions.len() as u32;
+    for (legacy_key, position) in positions {
+        let (owner_raw, index_raw) = legacy_key.split_at(legacy_key.len() - 4);
+        let owner = Addr::unchecked(String::from_utf8(owner_raw.to_vec())?);
+        let index = u32::from_be_bytes(index_raw.try_into().unwrap());
+
+        POSITIONS.save(store, position_id, &position)?;
+        POOL_POSITIONS.save(store, (&position.pool_key.key(), position_id), &())?;
+        OWNER_POSITIONS.save(store, &legacy_key, &position_id)?;
+        POSITION_OWNERS.save(store, position_id, &(owner, index))?;
+
+        LEGACY_POSITIONS.remove(store, &legacy_key);
+
+        position_id += 1;
+    }
+    NEXT_POSITION_ID.save(store, &position_id)?;
+
+    Ok((migrated, pending))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(parse_version("0.1.0").unwrap(), (0, 1, 0));
+        assert_eq!(parse_version("1.12.3-rc.1").unwrap(), (1, 12, 3));
+        parse_version("1.2").unwrap_err();
+        parse_version("1.x.0").unwrap_err();
+        assert!(parse_version("0.10.0").unwrap() > parse_version("0.9.9").unwrap());
+    }
+}
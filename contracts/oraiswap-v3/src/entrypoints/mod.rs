@@ -1,7 +1,11 @@
 mod common;
+mod events;
 mod execute;
+mod migrate;
 mod query;
 
 pub use common::*;
+pub use events::*;
 pub use execute::*;
+pub use migrate::*;
 pub use query::*;
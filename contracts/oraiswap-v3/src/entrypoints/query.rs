@@ -1,22 +1,31 @@
-use cosmwasm_std::{Addr, Binary, Deps, Env, Order, StdResult};
+use cosmwasm_std::{Addr, Binary, Deps, Env, Order, StdResult, Storage};
 use cw_storage_plus::Bound;
+use decimal::Decimal;
 
 use crate::{
     get_max_chunk, get_min_chunk,
     interface::{
-        AllNftInfoResponse, Approval, ApprovedForAllResponse, NftInfoResponse, OwnerOfResponse,
-        PoolWithPoolKey, QuoteResult, SwapHop, TokensResponse,
+        AllNftInfoResponse, Approval, ApprovedForAllResponse, Asset, NftInfoResponse,
+        ObserveResult, OwnerOfResponse, PoolPosition, PoolWithPoolKey, QuoteResult, RouteQuote,
+        SwapHop, SwapSplitQuote, TokensResponse,
     },
     percentage::Percentage,
+    seconds_per_liquidity::{calculate_seconds_per_liquidity_inside, SecondsPerLiquidity},
     sqrt_price::{get_max_tick, get_min_tick, SqrtPrice},
-    state::{self, CONFIG, MAX_LIMIT},
+    state::{
+        self, CONFIG, MAX_LIMIT, MAX_ROUTE_CANDIDATES, MAX_ROUTE_HOPS, MAX_SPLIT_PARTS, POOLS,
+    },
     tick_to_position,
     token_amount::TokenAmount,
-    ContractError, FeeTier, LiquidityTick, Pool, PoolKey, Position, PositionTick, Tick, CHUNK_SIZE,
-    LIQUIDITY_TICK_LIMIT, POSITION_TICK_LIMIT,
+    ContractError, FeeTier, LiquidityTick, PauseScope, PendingAdmin, Pool, PoolCreationFee,
+    PoolCreationPolicy, PoolKey, Position, PositionTick, Tick, CHUNK_SIZE, LIQUIDITY_TICK_LIMIT,
+    POSITION_TICK_LIMIT,
 };
 
-use super::{calculate_swap, humanize_approvals, route, tickmap_slice, TimeStampExt};
+use super::{
+    calculate_swap, check_split_routes, humanize_approvals, route, route_exact_out, tickmap_slice,
+    TimeStampExt,
+};
 
 /// Retrieves the admin of contract.
 pub fn query_admin(deps: Deps) -> Result<Addr, ContractError> {
@@ -24,6 +33,43 @@ pub fn query_admin(deps: Deps) -> Result<Addr, ContractError> {
     Ok(config.admin)
 }
 
+/// Retrieves the admin transfer waiting for acceptance, if any.
+pub fn query_pending_admin(deps: Deps) -> Result<Option<PendingAdmin>, ContractError> {
+    Ok(state::PENDING_ADMIN.may_load(deps.storage)?)
+}
+
+/// Retrieves who can create pools.
+pub fn query_pool_creation_policy(deps: Deps) -> Result<PoolCreationPolicy, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(config.pool_creation_policy)
+}
+
+/// Retrieves how many initialized ticks a single swap may cross.
+pub fn query_max_tick_cross(deps: Deps) -> Result<u32, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(config.max_tick_cross())
+}
+
+/// Retrieves the fee charged on pool creation, if any.
+pub fn query_pool_creation_fee(deps: Deps) -> Result<Option<PoolCreationFee>, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(config.pool_creation_fee)
+}
+
+/// Retrieves the guardian of contract.
+pub fn query_guardian(deps: Deps) -> Result<Option<Addr>, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(config.guardian)
+}
+
+/// Checks whether the operations covered by `scope` are paused.
+///
+/// # Parameters
+/// - `scope`: The operations to check.
+pub fn is_paused(deps: Deps, scope: PauseScope) -> Result<bool, ContractError> {
+    Ok(state::is_paused(deps.storage, &scope))
+}
+
 /// Retrieves the protocol fee represented as a percentage.
 pub fn get_protocol_fee(deps: Deps) -> Result<Percentage, ContractError> {
     let config = CONFIG.load(deps.storage)?;
@@ -121,6 +167,37 @@ pub fn get_pools(
     state::get_pools(deps.storage, limit, start_after)
 }
 
+/// Retrieves the positions of a pool across all owners.
+///
+/// # Parameters
+/// - `pool_key`: A unique key that identifies the specified pool.
+/// - `start_after`: The token id after which positions are listed.
+/// - `limit`: Amount of positions to retrieve, at most 100.
+pub fn get_pool_positions(
+    deps: Deps,
+    pool_key: PoolKey,
+    start_after: Option<Binary>,
+    limit: Option<u32>,
+) -> Result<Vec<PoolPosition>, ContractError> {
+    let start_after = start_after
+        .map(|token_id| state::parse_token_id(&token_id))
+        .transpose()?;
+
+    state::get_pool_positions(deps.storage, &pool_key, start_after, limit)?
+        .into_iter()
+        .map(
+            |(position_id, position)| -> Result<PoolPosition, ContractError> {
+                let (owner, _) = state::get_position_owner(deps.storage, position_id)?;
+                Ok(PoolPosition {
+                    token_id: Binary::from(state::token_id(position_id)),
+                    owner,
+                    position,
+                })
+            },
+        )
+        .collect()
+}
+
 /// Retrieves listed pools for provided token pair
 /// - `token_0`: Address of first token
 /// - `token_1`: Address of second token
@@ -334,6 +411,8 @@ pub fn get_liquidity_ticks_amount(
 }
 
 /// Simulates the swap without its execution.
+/// A swap crossing more ticks than allowed is simulated up to the limit and flagged with
+/// `max_ticks_crossed`, as executing it would fail.
 ///
 /// # Parameters
 /// - `pool_key`: A unique key that identifies the specified pool.
@@ -355,6 +434,45 @@ pub fn quote(
     amount: TokenAmount,
     by_amount_in: bool,
     sqrt_price_limit: SqrtPrice,
+) -> Result<QuoteResult, ContractError> {
+    let quote_result = quote_partial(
+        deps,
+        env,
+        pool_key,
+        x_to_y,
+        amount,
+        by_amount_in,
+        sqrt_price_limit,
+    )?;
+    if let Some(reason) = quote_result.partial_fill {
+        return Err(reason.into());
+    }
+
+    Ok(quote_result)
+}
+
+/// Simulates the swap without its execution, stopping where it can no longer be filled instead of failing.
+/// The result covers the maximal executable amount and `partial_fill` tells why the swap stopped early.
+///
+/// # Parameters
+/// - `pool_key`: A unique key that identifies the specified pool.
+/// - `x_to_y`: A boolean specifying the swap direction.
+/// - `amount`: The amount of tokens that the user wants to swap.
+/// - `by_amount_in`: A boolean specifying whether the user provides the amount to swap or expects the amount out.
+/// - `sqrt_price_limit`: A square root of price limit allowing the price to move for the swap to occur.
+///
+/// # Errors
+/// - Fails if the user attempts to perform a swap with zero amounts.
+/// - Fails if the user would receive zero tokens from a swap that was not stopped early.
+/// - Fails if pool does not exist
+pub fn quote_partial(
+    deps: Deps,
+    env: Env,
+    pool_key: PoolKey,
+    x_to_y: bool,
+    amount: TokenAmount,
+    by_amount_in: bool,
+    sqrt_price_limit: SqrtPrice,
 ) -> Result<QuoteResult, ContractError> {
     let calculate_swap_result = calculate_swap(
         deps.storage,
@@ -371,6 +489,9 @@ pub fn quote(
         amount_out: calculate_swap_result.amount_out,
         target_sqrt_price: calculate_swap_result.pool.sqrt_price,
         ticks: calculate_swap_result.ticks,
+        swap_fee: calculate_swap_result.swap_fee,
+        max_ticks_crossed: calculate_swap_result.max_ticks_crossed,
+        partial_fill: calculate_swap_result.partial_fill,
     })
 }
 
@@ -394,14 +515,329 @@ pub fn quote_route(
     Ok(amount_out)
 }
 
+/// Simulates multiple swaps that deliver an exact output without its execution.
+///
+/// # Parameters
+/// - `amount_out`: The amount of tokens that the user wants to receive from the last swap.
+/// - `swaps`: A vector containing all parameters needed to identify separate swap steps.
+///
+/// # Errors
+/// - Fails if the user attempts to perform a swap with zero amounts.
+/// - Fails if there is insufficient liquidity to deliver the requested amount.
+/// - Fails if pool does not exist
+pub fn quote_route_exact_out(
+    deps: Deps,
+    env: Env,
+    amount_out: TokenAmount,
+    swaps: Vec<SwapHop>,
+) -> Result<TokenAmount, ContractError> {
+    let amount_in = route_exact_out(deps.storage, env, amount_out, swaps)?;
+    Ok(amount_in)
+}
+
+/// Greedily splits an input across routes between the same tokens.
+/// The input is divided into `parts` chunks and every chunk goes to the route where it adds the most output.
+///
+/// # Parameters
+/// - `amount_in`: The amount of the first token that the user wants to swap.
+/// - `routes`: The candidate routes, e.g. the pools of a pair in different fee tiers.
+/// - `parts`: The number of chunks, at most 20. More chunks give a finer split for more gas.
+///
+/// Routes are quoted independently of each other, so routes going through the same pool
+/// may deliver less once executed, `min_amount_out` of `SwapSplit` protects against that.
+///
+/// # Errors
+/// - Fails if there are no routes, a route has no swaps or the routes do not share the first and last token.
+/// - Fails if there are more than `MAX_SPLIT_ROUTES` routes.
+/// - Fails if the user attempts to swap zero amounts.
+/// - Fails if the routes do not have the liquidity to swap the whole amount.
+pub fn quote_swap_split(
+    deps: Deps,
+    env: Env,
+    amount_in: TokenAmount,
+    routes: Vec<Vec<SwapHop>>,
+    parts: u8,
+) -> Result<SwapSplitQuote, ContractError> {
+    check_split_routes(&routes)?;
+    if amount_in.is_zero() {
+        return Err(ContractError::AmountIsZero {});
+    }
+
+    let parts = (parts.clamp(1, MAX_SPLIT_PARTS) as u128).min(amount_in.get());
+    let chunk = amount_in.get() / parts;
+
+    let mut amounts = vec![TokenAmount::new(0); routes.len()];
+    let mut amounts_out = vec![TokenAmount::new(0); routes.len()];
+    for part in 0..parts {
+        // the last chunk takes the remainder of the division
+        let chunk = if part == parts - 1 {
+            TokenAmount::new(amount_in.get() - chunk * (parts - 1))
+        } else {
+            TokenAmount::new(chunk)
+        };
+
+        let mut best: Option<(usize, TokenAmount)> = None;
+        for (i, swaps) in routes.iter().enumerate() {
+            let amount_out =
+                match route(deps.storage, env.clone(), amounts[i] + chunk, swaps.clone()) {
+                    Ok(amount_out) => amount_out,
+                    Err(_) => continue,
+                };
+            let gain = amount_out.get().saturating_sub(amounts_out[i].get());
+            let is_better = match best {
+                Some((best_i, best_out)) => {
+                    gain > best_out.get().saturating_sub(amounts_out[best_i].get())
+                }
+                None => true,
+            };
+            if is_better {
+                best = Some((i, amount_out));
+            }
+        }
+
+        let (i, amount_out) = best.ok_or(ContractError::InsufficientLiquidity)?;
+        amounts[i] += chunk;
+        amounts_out[i] = amount_out;
+    }
+
+    let amount_out = amounts_out
+        .into_iter()
+        .fold(TokenAmount::new(0), |total, amount_out| total + amount_out);
+    let legs = routes
+        .into_iter()
+        .zip(amounts)
+        .filter(|(_, amount)| !amount.is_zero())
+        .collect();
+
+    Ok(SwapSplitQuote { legs, amount_out })
+}
+
+/// Searches the routes between two tokens and simulates each of them.
+///
+/// # Parameters
+/// - `offer_token`: The token that the user wants to swap.
+/// - `ask_token`: The token that the user wants to receive.
+/// - `amount`: The amount of `offer_token` to swap.
+/// - `max_hops`: The most pools a route may go through, at most 3.
+/// - `limit`: Amount of routes to return, at most 100.
+///
+/// Only the pools of the tokens along a route are searched and at most `MAX_ROUTE_CANDIDATES`
+/// routes are simulated. Routes through paused pools or without enough liquidity for `amount` are
+/// left out, the rest is ordered by amount out, the shorter route first on ties.
+///
+/// # Errors
+/// - Fails if both tokens are the same.
+/// - Fails if the user attempts to swap zero amounts.
+pub fn find_best_route(
+    deps: Deps,
+    env: Env,
+    offer_token: String,
+    ask_token: String,
+    amount: TokenAmount,
+    max_hops: u8,
+    limit: Option<u32>,
+) -> Result<Vec<RouteQuote>, ContractError> {
+    if offer_token == ask_token {
+        return Err(ContractError::TokensAreSame);
+    }
+    if amount.is_zero() {
+        return Err(ContractError::AmountIsZero {});
+    }
+
+    let fee_tiers = get_fee_tiers(deps)?;
+    let mut paths = vec![];
+    collect_route_paths(
+        deps.storage,
+        &fee_tiers,
+        &ask_token,
+        max_hops.min(MAX_ROUTE_HOPS) as usize,
+        &mut vec![offer_token],
+        &mut vec![],
+        &mut paths,
+    )?;
+
+    let mut routes = paths
+        .into_iter()
+        .filter_map(|swaps| {
+            let amount_out = route(deps.storage, env.clone(), amount, swaps.clone()).ok()?;
+            Some(RouteQuote { swaps, amount_out })
+        })
+        .collect::<Vec<_>>();
+    routes.sort_by(|a, b| {
+        b.amount_out
+            .get()
+            .cmp(&a.amount_out.get())
+            .then(a.swaps.len().cmp(&b.swaps.len()))
+    });
+    routes.truncate(limit.unwrap_or(MAX_LIMIT).min(MAX_LIMIT) as usize);
+
+    Ok(routes)
+}
+
+/// Collects the paths from the last of `visited` tokens to `ask_token` that do not visit a token
+/// twice, until `MAX_ROUTE_CANDIDATES` paths are found.
+fn collect_route_paths(
+    store: &dyn Storage,
+    fee_tiers: &[FeeTier],
+    ask_token: &str,
+    hops_left: usize,
+    visited: &mut Vec<String>,
+    path: &mut Vec<SwapHop>,
+    paths: &mut Vec<Vec<SwapHop>>,
+) -> Result<(), ContractError> {
+    if hops_left == 0 {
+        return Ok(());
+    }
+    let token = visited[visited.len() - 1].clone();
+
+    // the last hop has to end in the ask token, so only the pools of that pair are looked up
+    let pool_keys = if hops_left == 1 {
+        fee_tiers
+            .iter()
+            .map(|fee_tier| PoolKey::new(token.clone(), ask_token.to_string(), *fee_tier))
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .filter(|pool_key| POOLS.has(store, &pool_key.key()))
+            .collect()
+    } else {
+        state::get_token_pools(store, &token)?
+    };
+
+    for pool_key in pool_keys {
+        if paths.len() >= MAX_ROUTE_CANDIDATES {
+            break;
+        }
+        if state::check_not_paused(store, &PauseScope::Swaps, &pool_key).is_err() {
+            continue;
+        }
+
+        let x_to_y = pool_key.token_x == token;
+        let hop = SwapHop { pool_key, x_to_y };
+        let token_out = hop.token_out().to_string();
+        if visited.contains(&token_out) {
+            continue;
+        }
+
+        path.push(hop);
+        if token_out == ask_token {
+            paths.push(path.clone());
+        } else {
+            visited.push(token_out);
+            collect_route_paths(
+                store,
+                fee_tiers,
+                ask_token,
+                hops_left - 1,
+                visited,
+                path,
+                paths,
+            )?;
+            visited.pop();
+        }
+        path.pop();
+    }
+
+    Ok(())
+}
+
+/// Retrieves the oracle cumulatives of a pool at several points in the past.
+///
+/// # Parameters
+/// - `pool_key`: A unique key that identifies the specified pool.
+/// - `seconds_agos`: How many seconds before the current block time each point is taken.
+///
+/// # Errors
+/// - Fails if pool does not exist
+/// - Fails if a point is older than the oldest stored observation.
+pub fn observe(
+    deps: Deps,
+    env: Env,
+    pool_key: PoolKey,
+    seconds_agos: Vec<u64>,
+) -> Result<ObserveResult, ContractError> {
+    let pool = state::get_pool(deps.storage, &pool_key).map_err(|_| ContractError::PoolNotFound)?;
+    let oracle = state::get_oracle(deps.storage, &pool_key)?;
+
+    let mut tick_cumulatives = Vec::with_capacity(seconds_agos.len());
+    let mut seconds_per_liquidity_cumulatives = Vec::with_capacity(seconds_agos.len());
+    for seconds_ago in seconds_agos {
+        let observation = state::observe_single(
+            deps.storage,
+            &pool_key,
+            &pool,
+            &oracle,
+            env.block.time.millis(),
+            seconds_ago,
+        )?;
+        tick_cumulatives.push(observation.tick_cumulative);
+        seconds_per_liquidity_cumulatives.push(observation.seconds_per_liquidity_cumulative);
+    }
+
+    Ok(ObserveResult {
+        tick_cumulatives,
+        seconds_per_liquidity_cumulatives,
+    })
+}
+
+/// Retrieves the seconds per liquidity accumulated inside the position range.
+///
+/// The difference between two readings multiplied by the position liquidity gives the
+/// in-range time weighted by liquidity over that period.
+///
+/// # Parameters
+/// - `owner_id`: An `Addr` identifying the user who owns the position.
+/// - `index`: The index of the user position.
+pub fn get_position_seconds_inside(
+    deps: Deps,
+    env: Env,
+    owner_id: Addr,
+    index: u32,
+) -> Result<SecondsPerLiquidity, ContractError> {
+    let position = state::get_position(deps.storage, &owner_id, index)?;
+    let mut pool = state::get_pool(deps.storage, &position.pool_key)?;
+    let lower_tick = state::get_tick(deps.storage, &position.pool_key, position.lower_tick_index)?;
+    let upper_tick = state::get_tick(deps.storage, &position.pool_key, position.upper_tick_index)?;
+
+    pool.update_seconds_per_liquidity_global(env.block.time.millis())?;
+
+    calculate_seconds_per_liquidity_inside(
+        lower_tick.index,
+        upper_tick.index,
+        pool.current_tick_index,
+        lower_tick.seconds_per_liquidity_outside,
+        upper_tick.seconds_per_liquidity_outside,
+        pool.seconds_per_liquidity_global,
+    )
+}
+
+/// Retrieves the incentive rewards the position can claim as of the current block time.
+///
+/// # Parameters
+/// - `owner_id`: An `Addr` identifying the user who owns the position.
+/// - `index`: The index of the user position.
+pub fn get_position_incentives(
+    deps: Deps,
+    env: Env,
+    owner_id: Addr,
+    index: u32,
+) -> Result<Vec<Asset>, ContractError> {
+    let mut position = state::get_position(deps.storage, &owner_id, index)?;
+    let mut pool = state::get_pool(deps.storage, &position.pool_key)?;
+    let lower_tick = state::get_tick(deps.storage, &position.pool_key, position.lower_tick_index)?;
+    let upper_tick = state::get_tick(deps.storage, &position.pool_key, position.upper_tick_index)?;
+
+    position.claim_incentives(&mut pool, &lower_tick, &upper_tick, env.block.time.millis())
+}
+
 pub fn query_owner_of(
     deps: Deps,
     env: Env,
     token_id: Binary,
     include_expired: bool,
 ) -> Result<OwnerOfResponse, ContractError> {
-    let owner = Addr::unchecked(String::from_utf8(token_id[..token_id.len() - 4].to_vec())?);
-    let pos = state::get_position_by_key(deps.storage, &token_id)?;
+    let position_id = state::parse_token_id(&token_id)?;
+    let (owner, _) = state::get_position_owner(deps.storage, position_id)?;
+    let pos = state::get_position_by_id(deps.storage, position_id)?;
     Ok(OwnerOfResponse {
         owner,
         approvals: humanize_approvals(&env.block, &pos, include_expired),
@@ -437,7 +873,7 @@ pub fn query_all_approvals(
 }
 
 pub fn query_nft_info(deps: Deps, token_id: Binary) -> Result<NftInfoResponse, ContractError> {
-    let pos = state::get_position_by_key(deps.storage, &token_id)?;
+    let pos = state::get_position_by_id(deps.storage, state::parse_token_id(&token_id)?)?;
     Ok(NftInfoResponse { extension: pos })
 }
 
@@ -447,8 +883,9 @@ pub fn query_all_nft_info(
     token_id: Binary,
     include_expired: bool,
 ) -> Result<AllNftInfoResponse, ContractError> {
-    let owner = Addr::unchecked(String::from_utf8(token_id[..token_id.len() - 4].to_vec())?);
-    let pos = state::get_position_by_key(deps.storage, &token_id)?;
+    let position_id = state::parse_token_id(&token_id)?;
+    let (owner, _) = state::get_position_owner(deps.storage, position_id)?;
+    let pos = state::get_position_by_id(deps.storage, position_id)?;
     Ok(AllNftInfoResponse {
         access: OwnerOfResponse {
             owner,
@@ -464,26 +901,38 @@ pub fn query_tokens(
     start_after: Option<u32>,
     limit: Option<u32>,
 ) -> Result<TokensResponse, ContractError> {
-    let tokens = state::get_all_position_keys(deps.storage, &owner, limit, start_after)
+    let tokens = state::get_all_position_ids(deps.storage, &owner, limit, start_after)?
         .into_iter()
-        .map(Binary::from)
+        .map(|position_id| Binary::from(state::token_id(position_id)))
         .collect();
 
     Ok(TokensResponse { tokens })
 }
 
+/// Resolves the token id of the position at `index` among the positions of `owner_id`.
+///
+/// # Errors
+/// - Fails if position cannot be found
+pub fn query_token_id(deps: Deps, owner_id: Addr, index: u32) -> Result<Binary, ContractError> {
+    let position_id = state::get_position_id(deps.storage, &owner_id, index)?;
+    Ok(Binary::from(state::token_id(position_id)))
+}
+
 pub fn query_all_tokens(
     deps: Deps,
     start_after: Option<Binary>,
     limit: Option<u32>,
 ) -> Result<TokensResponse, ContractError> {
     let limit = limit.unwrap_or(MAX_LIMIT).min(MAX_LIMIT) as usize;
-    let start = start_after.map(|x| x.to_vec()).map(Bound::ExclusiveRaw);
+    let start = start_after
+        .map(|token_id| state::parse_token_id(&token_id))
+        .transpose()?
+        .map(Bound::exclusive);
 
-    let tokens: Vec<_> = state::POSITIONS
-        .keys_raw(deps.storage, start, None, Order::Ascending)
+    let tokens = state::POSITIONS
+        .keys(deps.storage, start, None, Order::Ascending)
         .take(limit)
-        .map(Binary::from)
-        .collect();
+        .map(|position_id| Ok(Binary::from(state::token_id(position_id?))))
+        .collect::<StdResult<_>>()?;
     Ok(TokensResponse { tokens })
 }
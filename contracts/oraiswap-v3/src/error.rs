@@ -179,6 +179,81 @@ pub enum ContractError {
     #[error("amount under minimum amount out")]
     AmountUnderMinimumAmountOut,
 
+    #[error("amount over maximum amount in")]
+    AmountOverMaximumAmountIn,
+
+    #[error("position amount under minimum amount")]
+    PositionAmountUnderMinimum,
+
+    #[error("cw20 token is not part of the pool")]
+    InvalidCw20Token,
+
+    #[error("transaction deadline exceeded")]
+    DeadlineExceeded,
+
+    #[error("swap route is empty")]
+    EmptySwapRoute,
+
+    #[error("swap route hops are not chained, a hop must pay in the token of the previous one")]
+    InvalidSwapRoute,
+
+    #[error("oracle not initialized")]
+    OracleNotInitialized,
+
+    #[error("observation is older than the oldest stored one")]
+    ObservationTooOld,
+
+    #[error("invalid observation cardinality")]
+    InvalidObservationCardinality,
+
+    #[error("incentive must start no earlier than now and end after it starts")]
+    InvalidIncentiveDuration,
+
+    #[error("incentive not found")]
+    IncentiveNotFound,
+
+    #[error("pool has reached the maximum number of incentives")]
+    IncentiveLimitReached,
+
+    #[error("operation is paused")]
+    Paused,
+
+    #[error("no admin transfer is pending")]
+    NoPendingAdmin,
+
+    #[error("admin proposal has expired")]
+    AdminProposalExpired,
+
+    #[error("invalid contract version: {version}")]
+    InvalidVersion { version: String },
+
+    #[error("cannot migrate from a different contract: {contract}")]
+    InvalidMigrationContract { contract: String },
+
+    #[error("cannot migrate to an older version")]
+    MigrationDowngrade,
+
+    #[error("invalid dynamic fee")]
+    InvalidDynamicFee,
+
+    #[error("pool creation is not allowed for the caller or tokens")]
+    PoolCreationNotAllowed,
+
+    #[error("invalid pool creation fee")]
+    InvalidPoolCreationFee,
+
+    #[error("swap crosses more ticks than allowed")]
+    TickCrossLimitReached,
+
+    #[error("invalid tick cross limit")]
+    InvalidTickCrossLimit,
+
+    #[error("swap split routes must share the offer and ask tokens")]
+    InvalidSwapSplit,
+
+    #[error("swap split has more routes than allowed")]
+    SwapSplitRouteLimitReached,
+
     #[error("invalid pool key")]
     InvalidPoolKey,
 
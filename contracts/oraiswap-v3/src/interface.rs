@@ -6,8 +6,8 @@ use cosmwasm_std::{
 use cw20::{Cw20ExecuteMsg, Expiration};
 
 use crate::{
-    fee_growth::FeeGrowth, sqrt_price::SqrtPrice, token_amount::TokenAmount, ContractError, Pool,
-    PoolKey, Position, Tick,
+    fee_growth::FeeGrowth, percentage::Percentage, seconds_per_liquidity::SecondsPerLiquidity,
+    sqrt_price::SqrtPrice, token_amount::TokenAmount, ContractError, Pool, PoolKey, Position, Tick,
 };
 
 #[cw_serde]
@@ -17,8 +17,63 @@ pub struct CalculateSwapResult {
     pub start_sqrt_price: SqrtPrice,
     pub target_sqrt_price: SqrtPrice,
     pub fee: TokenAmount,
+    /// part of `fee` taken by the protocol
+    pub protocol_fee: TokenAmount,
+    /// fee rate charged by the swap, differs from the fee tier on dynamic fee pools
+    pub swap_fee: Percentage,
     pub pool: Pool,
     pub ticks: Vec<Tick>,
+    /// the swap stopped after crossing more ticks than allowed, amounts cover the part done so far
+    pub max_ticks_crossed: bool,
+    /// the swap stopped before using the whole amount, amounts cover the part done so far
+    pub partial_fill: Option<PartialFillReason>,
+}
+
+impl CalculateSwapResult {
+    /// Fails unless the whole amount was swapped.
+    pub fn ensure_filled(&self) -> Result<(), ContractError> {
+        if let Some(reason) = self.partial_fill {
+            return Err(reason.into());
+        }
+        if self.max_ticks_crossed {
+            return Err(ContractError::TickCrossLimitReached);
+        }
+        Ok(())
+    }
+}
+
+/// Why a swap could not use the whole amount,
+/// the equivalent of `global_insufficient_liquidity` in the wasm simulator.
+#[cw_serde]
+#[derive(Copy)]
+pub enum PartialFillReason {
+    /// the price reached `sqrt_price_limit`
+    PriceLimit,
+    /// the price reached the lowest or highest tick while there was still liquidity
+    TickLimit,
+    /// there is no liquidity left in the swap direction
+    LiquidityExhausted,
+}
+
+impl PartialFillReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::PriceLimit => "price_limit",
+            Self::TickLimit => "tick_limit",
+            Self::LiquidityExhausted => "liquidity_exhausted",
+        }
+    }
+}
+
+impl From<PartialFillReason> for ContractError {
+    fn from(reason: PartialFillReason) -> Self {
+        match reason {
+            PartialFillReason::PriceLimit => ContractError::PriceLimitReached,
+            PartialFillReason::TickLimit | PartialFillReason::LiquidityExhausted => {
+                ContractError::TickLimitReached
+            }
+        }
+    }
 }
 
 #[cw_serde]
@@ -27,6 +82,26 @@ pub struct SwapHop {
     pub x_to_y: bool,
 }
 
+impl SwapHop {
+    /// The token paid into the pool by this hop.
+    pub fn token_in(&self) -> &str {
+        if self.x_to_y {
+            &self.pool_key.token_x
+        } else {
+            &self.pool_key.token_y
+        }
+    }
+
+    /// The token received from the pool by this hop.
+    pub fn token_out(&self) -> &str {
+        if self.x_to_y {
+            &self.pool_key.token_y
+        } else {
+            &self.pool_key.token_x
+        }
+    }
+}
+
 /// AssetInfo contract_addr is usually passed from the cw20 hook
 /// so we can trust the contract_addr is properly validated.
 #[cw_serde]
@@ -45,6 +120,14 @@ impl AssetInfo {
             }
         }
     }
+
+    /// The contract address or native denom, the inverse of `from_denom`.
+    pub fn denom(&self) -> String {
+        match self {
+            Self::Token { contract_addr } => contract_addr.to_string(),
+            Self::NativeToken { denom } => denom.to_string(),
+        }
+    }
 }
 
 #[cw_serde]
@@ -58,6 +141,14 @@ impl Asset {
         &self,
         msgs: &mut Vec<CosmosMsg>,
         info: &MessageInfo,
+    ) -> Result<(), ContractError> {
+        self.transfer_to(msgs, &info.sender)
+    }
+
+    pub fn transfer_to(
+        &self,
+        msgs: &mut Vec<CosmosMsg>,
+        recipient: &Addr,
     ) -> Result<(), ContractError> {
         if !self.amount.is_zero() {
             match &self.info {
@@ -66,7 +157,7 @@ impl Asset {
                         WasmMsg::Execute {
                             contract_addr: contract_addr.to_string(),
                             msg: to_binary(&Cw20ExecuteMsg::Transfer {
-                                recipient: info.sender.to_string(),
+                                recipient: recipient.to_string(),
                                 amount: self.amount,
                             })?,
                             funds: vec![],
@@ -76,7 +167,7 @@ impl Asset {
                 }
                 AssetInfo::NativeToken { denom } => msgs.push(
                     BankMsg::Send {
-                        to_address: info.sender.to_string(),
+                        to_address: recipient.to_string(),
                         amount: vec![Coin {
                             amount: self.amount,
                             denom: denom.to_string(),
@@ -95,9 +186,9 @@ impl Asset {
         info: &MessageInfo,
         recipient: String,
     ) -> Result<(), ContractError> {
-        if !self.amount.is_zero() {
-            match &self.info {
-                AssetInfo::Token { contract_addr } => {
+        match &self.info {
+            AssetInfo::Token { contract_addr } => {
+                if !self.amount.is_zero() {
                     msgs.push(
                         WasmMsg::Execute {
                             contract_addr: contract_addr.to_string(),
@@ -111,36 +202,34 @@ impl Asset {
                         .into(),
                     );
                 }
-                AssetInfo::NativeToken { denom } => {
-                    match info.funds.iter().find(|x| x.denom.eq(denom)) {
-                        Some(coin) => {
-                            if coin.amount >= self.amount {
-                                let refund_amount = coin.amount - self.amount;
-                                // refund for user
-                                if !refund_amount.is_zero() {
-                                    msgs.push(
-                                        BankMsg::Send {
-                                            to_address: info.sender.to_string(),
-                                            amount: vec![Coin {
-                                                amount: refund_amount,
-                                                denom: denom.to_string(),
-                                            }],
-                                        }
-                                        .into(),
-                                    )
-                                }
-                            } else {
-                                return Err(ContractError::InvalidFunds {
-                                    transfer_amount: self.amount,
-                                });
-                            }
-                        }
-                        None => {
-                            return Err(ContractError::InvalidFunds {
-                                transfer_amount: self.amount,
-                            });
+            }
+            AssetInfo::NativeToken { denom } => {
+                // the excess is refunded even when nothing is required
+                let sent = info
+                    .funds
+                    .iter()
+                    .find(|x| x.denom.eq(denom))
+                    .map(|coin| coin.amount)
+                    .unwrap_or_default();
+                if sent < self.amount {
+                    return Err(ContractError::InvalidFunds {
+                        transfer_amount: self.amount,
+                    });
+                }
+
+                let refund_amount = sent - self.amount;
+                // refund for user
+                if !refund_amount.is_zero() {
+                    msgs.push(
+                        BankMsg::Send {
+                            to_address: info.sender.to_string(),
+                            amount: vec![Coin {
+                                amount: refund_amount,
+                                denom: denom.to_string(),
+                            }],
                         }
-                    }
+                        .into(),
+                    )
                 }
             }
         }
@@ -165,18 +254,56 @@ pub struct PositionTick {
     pub seconds_outside: u64,
 }
 
+/// A route between two tokens with the amount it would deliver.
+#[cw_serde]
+pub struct RouteQuote {
+    pub swaps: Vec<SwapHop>,
+    pub amount_out: TokenAmount,
+}
+
+/// An input split across routes, `legs` can be passed to `SwapSplit` as they are.
+#[cw_serde]
+pub struct SwapSplitQuote {
+    pub legs: Vec<(Vec<SwapHop>, TokenAmount)>,
+    pub amount_out: TokenAmount,
+}
+
 #[cw_serde]
 pub struct PoolWithPoolKey {
     pub pool: Pool,
     pub pool_key: PoolKey,
 }
 
+/// A position of a pool together with its token id and owner.
+#[cw_serde]
+pub struct PoolPosition {
+    pub token_id: Binary,
+    pub owner: Addr,
+    pub position: Position,
+}
+
 #[cw_serde]
 pub struct QuoteResult {
     pub amount_in: TokenAmount,
     pub amount_out: TokenAmount,
     pub target_sqrt_price: SqrtPrice,
     pub ticks: Vec<Tick>,
+    /// fee rate the swap would be charged at the current block
+    pub swap_fee: Percentage,
+    /// the swap would cross more ticks than allowed and fail, amounts cover the part up to the limit
+    pub max_ticks_crossed: bool,
+    /// set by partial quotes when the amount cannot be swapped in full,
+    /// amounts then cover the maximal executable part
+    pub partial_fill: Option<PartialFillReason>,
+}
+
+/// Cumulative oracle values for every requested `seconds_ago`, in the same order.
+/// The arithmetic mean tick between two points is the difference of their tick cumulatives
+/// divided by the elapsed seconds.
+#[cw_serde]
+pub struct ObserveResult {
+    pub tick_cumulatives: Vec<i64>,
+    pub seconds_per_liquidity_cumulatives: Vec<SecondsPerLiquidity>,
 }
 
 #[cw_serde]
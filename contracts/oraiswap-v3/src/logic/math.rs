@@ -35,7 +35,8 @@ pub fn get_liquidity(
     let lower_sqrt_price = calculate_sqrt_price(lower_tick)?;
     let upper_sqrt_price = calculate_sqrt_price(upper_tick)?;
 
-    if upper_sqrt_price < current_sqrt_price {
+    // a price sitting on a bound of the range only takes one token
+    if upper_sqrt_price <= current_sqrt_price {
         // single token y
         let result_by_y = get_liquidity_by_y_sqrt_price(
             y,
@@ -49,7 +50,7 @@ pub fn get_liquidity(
             y,
             l: result_by_y.l,
         });
-    } else if current_sqrt_price < lower_sqrt_price {
+    } else if current_sqrt_price <= lower_sqrt_price {
         // single token x
         let result_by_x = get_liquidity_by_x_sqrt_price(
             x,
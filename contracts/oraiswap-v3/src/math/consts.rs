@@ -5,3 +5,6 @@ pub const MAX_SQRT_PRICE: u128 = 65535383934512647000000000000;
 pub const MIN_SQRT_PRICE: u128 = 15258932000000000000;
 
 pub const TICK_SEARCH_RANGE: i32 = 256;
+
+/// default number of initialized ticks a single swap may cross, matches the wasm simulator
+pub const MAX_TICK_CROSS: u32 = 173;
@@ -1,17 +1,19 @@
 #![allow(unused_imports)]
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Binary};
-use cw20::Expiration;
+use cosmwasm_std::{Addr, Binary, Timestamp};
+use cw20::{Cw20ReceiveMsg, Expiration};
 
 use crate::interface::{
-    AllNftInfoResponse, ApprovedForAllResponse, NftInfoResponse, OwnerOfResponse, PoolWithPoolKey,
-    PositionTick, QuoteResult, TokensResponse,
+    AllNftInfoResponse, ApprovedForAllResponse, Asset, AssetInfo, NftInfoResponse, ObserveResult,
+    OwnerOfResponse, PoolPosition, PoolWithPoolKey, PositionTick, QuoteResult, RouteQuote,
+    SwapSplitQuote, TokensResponse,
 };
 #[allow(unused_imports)]
 use crate::{
     fee_growth::FeeGrowth, interface::SwapHop, liquidity::Liquidity, percentage::Percentage,
-    sqrt_price::SqrtPrice, token_amount::TokenAmount, FeeTier, LiquidityTick, Pool, PoolKey,
-    Position, Tick,
+    seconds_per_liquidity::SecondsPerLiquidity, sqrt_price::SqrtPrice, token_amount::TokenAmount,
+    DynamicFeeConfig, FeeTier, LiquidityTick, PauseScope, PendingAdmin, Pool, PoolCreationFee,
+    PoolCreationPolicy, PoolKey, Position, Tick,
 };
 
 #[cw_serde]
@@ -29,21 +31,91 @@ pub struct NftExtensionMsg {
     pub slippage_limit_upper: SqrtPrice,
 }
 
+/// Actions that can be funded by a single cw20 `Send`.
+/// The amount sent is used as the input, unspent tokens are refunded to the sender.
+#[cw_serde]
+pub enum Cw20HookMsg {
+    Swap {
+        pool_key: PoolKey,
+        x_to_y: bool,
+        amount: TokenAmount,
+        by_amount_in: bool,
+        sqrt_price_limit: SqrtPrice,
+    },
+    SwapRoute {
+        expected_amount_out: TokenAmount,
+        slippage: Percentage,
+        swaps: Vec<SwapHop>,
+    },
+    CreatePosition {
+        pool_key: PoolKey,
+        lower_tick: i32,
+        upper_tick: i32,
+        liquidity_delta: Liquidity,
+        slippage_limit_lower: SqrtPrice,
+        slippage_limit_upper: SqrtPrice,
+    },
+}
+
 #[cw_serde]
 pub enum ExecuteMsg {
+    /// Alias of `ProposeAdmin` without expiry, the new admin still has to accept
     ChangeAdmin {
         new_admin: Addr,
     },
+    /// Proposes a new admin, the transfer completes once the proposed address accepts it
+    ProposeAdmin {
+        new_admin: String,
+        expires: Option<Expiration>,
+    },
+    AcceptAdmin {},
+    CancelAdminProposal {},
     WithdrawProtocolFee {
         pool_key: PoolKey,
     },
     ChangeProtocolFee {
         protocol_fee: Percentage,
     },
+    /// Overrides the protocol fee of a single pool, `None` restores the global protocol fee
+    ChangePoolProtocolFee {
+        pool_key: PoolKey,
+        protocol_fee: Option<Percentage>,
+    },
+    /// Scales the pool fee with the recent price movement, `None` restores the fee tier fee
+    ChangePoolDynamicFee {
+        pool_key: PoolKey,
+        dynamic_fee: Option<DynamicFeeConfig>,
+    },
+    ChangePoolCreationPolicy {
+        policy: PoolCreationPolicy,
+    },
+    /// Limits the initialized ticks a single swap may cross, `None` restores the default
+    ChangeMaxTickCross {
+        max_tick_cross: Option<u32>,
+    },
+    /// Charges a native fee forwarded to the treasury on pool creation, `None` makes it free
+    ChangePoolCreationFee {
+        fee: Option<PoolCreationFee>,
+    },
     ChangeFeeReceiver {
         pool_key: PoolKey,
         fee_receiver: Addr,
     },
+    /// Appoints the guardian that can pause and unpause alongside the admin, `None` removes it
+    ChangeGuardian {
+        guardian: Option<String>,
+    },
+    /// Halts the operations covered by the scope, callable by the admin or the guardian
+    Pause {
+        scope: PauseScope,
+    },
+    Unpause {
+        scope: PauseScope,
+    },
+    /// Moves up to `limit` positions left behind by the 0.3.0 migration to stable ids
+    MigratePositions {
+        limit: Option<u32>,
+    },
     CreatePosition {
         pool_key: PoolKey,
         lower_tick: i32,
@@ -52,19 +124,72 @@ pub enum ExecuteMsg {
         slippage_limit_lower: SqrtPrice,
         slippage_limit_upper: SqrtPrice,
     },
+    CreatePositionByAmounts {
+        pool_key: PoolKey,
+        lower_tick: i32,
+        upper_tick: i32,
+        amount_x_desired: TokenAmount,
+        amount_y_desired: TokenAmount,
+        amount_x_min: TokenAmount,
+        amount_y_min: TokenAmount,
+    },
+    IncreaseLiquidity {
+        index: u32,
+        liquidity_delta: Liquidity,
+        slippage_limit_lower: SqrtPrice,
+        slippage_limit_upper: SqrtPrice,
+    },
+    DecreaseLiquidity {
+        index: u32,
+        liquidity_delta: Liquidity,
+        min_amount_x: TokenAmount,
+        min_amount_y: TokenAmount,
+    },
     Swap {
         pool_key: PoolKey,
         x_to_y: bool,
         amount: TokenAmount,
         by_amount_in: bool,
         sqrt_price_limit: SqrtPrice,
+        /// Receives the swap output, defaults to the sender
+        recipient: Option<String>,
+        /// The swap is rejected once the block time is past the deadline
+        deadline: Option<Timestamp>,
+        /// Fill as much as possible up to `sqrt_price_limit` instead of failing,
+        /// only the consumed input is taken from the sender
+        #[serde(default)]
+        allow_partial: bool,
     },
     SwapRoute {
         amount_in: TokenAmount,
         expected_amount_out: TokenAmount,
         slippage: Percentage,
         swaps: Vec<SwapHop>,
-    },
+        /// Receives the swap output, defaults to the sender
+        recipient: Option<String>,
+        /// The swap is rejected once the block time is past the deadline
+        deadline: Option<Timestamp>,
+    },
+    SwapRouteExactOut {
+        amount_out: TokenAmount,
+        max_amount_in: TokenAmount,
+        swaps: Vec<SwapHop>,
+        /// Receives the swap output, defaults to the sender
+        recipient: Option<String>,
+        /// The swap is rejected once the block time is past the deadline
+        deadline: Option<Timestamp>,
+    },
+    /// Swaps through several routes between the same tokens, each with its own input amount
+    SwapSplit {
+        legs: Vec<(Vec<SwapHop>, TokenAmount)>,
+        min_amount_out: TokenAmount,
+        /// Receives the swap output, defaults to the sender
+        recipient: Option<String>,
+        /// The swap is rejected once the block time is past the deadline
+        deadline: Option<Timestamp>,
+    },
+    /// Entry point for cw20 `Send`, the attached hook decides which action is funded
+    Receive(Cw20ReceiveMsg),
     TransferPosition {
         index: u32,
         receiver: String,
@@ -72,6 +197,10 @@ pub enum ExecuteMsg {
     ClaimFee {
         index: u32,
     },
+    /// Pays out the incentive rewards accrued by the position
+    ClaimIncentives {
+        index: u32,
+    },
     RemovePosition {
         index: u32,
     },
@@ -82,6 +211,33 @@ pub enum ExecuteMsg {
         init_sqrt_price: SqrtPrice,
         init_tick: i32,
     },
+    /// Creates a pool at `init_sqrt_price`, the initial tick is derived from the price and
+    /// returned in the `init_tick` attribute
+    CreatePoolWithSqrtPrice {
+        token_0: String,
+        token_1: String,
+        fee_tier: FeeTier,
+        init_sqrt_price: SqrtPrice,
+    },
+    /// Grows the number of oracle observations kept for the pool, anyone can pay for it
+    IncreaseObservationCardinality {
+        pool_key: PoolKey,
+        observation_cardinality_next: u16,
+    },
+    /// Streams `reward_per_sec` of `reward_token` to the in-range liquidity of the pool until
+    /// `end_timestamp` (seconds), the admin funds the whole program up front
+    CreateIncentive {
+        pool_key: PoolKey,
+        reward_token: AssetInfo,
+        reward_per_sec: TokenAmount,
+        start_timestamp: Option<u64>,
+        end_timestamp: u64,
+    },
+    /// Sends the emission of an incentive program that found no in-range liquidity to the admin
+    WithdrawUnallocatedIncentive {
+        pool_key: PoolKey,
+        incentive_id: u64,
+    },
     AddFeeTier {
         fee_tier: FeeTier,
     },
@@ -132,8 +288,21 @@ pub enum ExecuteMsg {
     },
 }
 
+/// Parameters of the state transforms, each field is only read by the upgrade it belongs to.
 #[cw_serde]
-pub struct MigrateMsg {}
+#[derive(Default)]
+pub struct MigrateMsg {
+    /// 0.2.0: guardian allowed to pause the contract.
+    #[serde(default)]
+    pub guardian: Option<String>,
+    /// 0.2.0: observation cardinality the oracles of existing pools start with.
+    #[serde(default)]
+    pub observation_cardinality: Option<u16>,
+    /// 0.3.0: legacy positions moved to stable ids by the migration itself, 100 by default.
+    /// The remaining positions are moved with `ExecuteMsg::MigratePositions`.
+    #[serde(default)]
+    pub position_limit: Option<u32>,
+}
 
 #[cw_serde]
 #[derive(QueryResponses)]
@@ -147,6 +316,11 @@ pub enum QueryMsg {
     #[returns(Position)]
     Position { owner_id: Addr, index: u32 },
 
+    /// Resolves the token id of the position at `index` among the positions of `owner_id`.
+    /// Token ids never change, while indexes shift when the owner removes or transfers a position.
+    #[returns(Binary)]
+    TokenId { owner_id: Addr, index: u32 },
+
     #[returns(Vec<Position>)]
     Positions {
         owner_id: Addr,
@@ -170,6 +344,14 @@ pub enum QueryMsg {
         start_after: Option<PoolKey>,
     },
 
+    /// Lists the positions of a pool in token id order, from every owner.
+    #[returns(Vec<PoolPosition>)]
+    PoolPositions {
+        pool_key: PoolKey,
+        start_after: Option<Binary>,
+        limit: Option<u32>,
+    },
+
     #[returns(Tick)]
     Tick { key: PoolKey, index: i32 },
 
@@ -218,12 +400,84 @@ pub enum QueryMsg {
         sqrt_price_limit: SqrtPrice,
     },
 
+    /// Like `Quote`, but returns the maximal executable part with a `partial_fill` reason
+    /// instead of failing when the amount cannot be swapped in full
+    #[returns(QuoteResult)]
+    QuotePartial {
+        pool_key: PoolKey,
+        x_to_y: bool,
+        amount: TokenAmount,
+        by_amount_in: bool,
+        sqrt_price_limit: SqrtPrice,
+    },
+
     #[returns(TokenAmount)]
     QuoteRoute {
         amount_in: TokenAmount,
         swaps: Vec<SwapHop>,
     },
 
+    /// Returns the amount of the first token required to receive exactly `amount_out`
+    #[returns(TokenAmount)]
+    QuoteRouteExactOut {
+        amount_out: TokenAmount,
+        swaps: Vec<SwapHop>,
+    },
+
+    /// Splits `amount_in` across `routes` in `parts` chunks, each chunk going to the route where
+    /// it adds the most output
+    #[returns(SwapSplitQuote)]
+    QuoteSwapSplit {
+        amount_in: TokenAmount,
+        routes: Vec<Vec<SwapHop>>,
+        parts: u8,
+    },
+
+    /// Returns up to `limit` routes from `offer_token` to `ask_token` through at most `max_hops`
+    /// pools that can swap `amount`, the best amount out first
+    #[returns(Vec<RouteQuote>)]
+    FindBestRoute {
+        offer_token: String,
+        ask_token: String,
+        amount: TokenAmount,
+        max_hops: u8,
+        limit: Option<u32>,
+    },
+
+    /// Returns the oracle cumulatives as of each `seconds_ago` from the current block time
+    #[returns(ObserveResult)]
+    Observe {
+        pool_key: PoolKey,
+        seconds_agos: Vec<u64>,
+    },
+
+    /// Returns the seconds per liquidity accumulated inside the position range as of the current block time
+    #[returns(SecondsPerLiquidity)]
+    PositionSecondsInside { owner_id: Addr, index: u32 },
+
+    /// Returns the incentive rewards the position can claim as of the current block time
+    #[returns(Vec<Asset>)]
+    PositionIncentives { owner_id: Addr, index: u32 },
+
+    #[returns(Option<PendingAdmin>)]
+    PendingAdmin {},
+
+    #[returns(PoolCreationPolicy)]
+    PoolCreationPolicy {},
+
+    #[returns(Option<PoolCreationFee>)]
+    PoolCreationFee {},
+
+    /// Initialized ticks a single swap may cross.
+    #[returns(u32)]
+    MaxTickCross {},
+
+    #[returns(Option<Addr>)]
+    Guardian {},
+
+    #[returns(bool)]
+    IsPaused { scope: PauseScope },
+
     ///
     ///
     ///  NFT methods
@@ -6,18 +6,30 @@ use crate::{
     flip_bit_at_position, get_bit_at_position, get_search_limit,
     interface::PoolWithPoolKey,
     sqrt_price::{calculate_sqrt_price, SqrtPrice},
-    tick_to_position, Config, ContractError, Pool, PoolKey, Position, Tick, CHUNK_SIZE, MAX_TICK,
+    tick_to_position, Config, ContractError, Observation, Oracle, PauseScope, PendingAdmin, Pool,
+    PoolKey, Position, Tick, CHUNK_SIZE, MAX_OBSERVATION_CARDINALITY, MAX_TICK,
 };
 
 pub const CONFIG: Item<Config> = Item::new("config");
+pub const PENDING_ADMIN: Item<PendingAdmin> = Item::new("pending_admin");
 
 pub const POOLS: Map<&[u8], Pool> = Map::new("pools");
 pub const POOL_KEYS: Map<&[u8], u16> = Map::new("pool_keys");
 pub const POOL_KEYS_BY_INDEX: Map<u16, PoolKey> = Map::new("pool_keys_by_index");
 pub const POOL_KEYS_LENGTH: Item<u16> = Item::new("pool_keys_length");
+/// pools each token trades in, keyed by the token and the pool key
+pub const TOKEN_POOLS: Map<(&str, &[u8]), ()> = Map::new("token_pools");
 
 pub const POSITIONS_LENGTH: Map<&[u8], u32> = Map::new("positions_length");
-pub const POSITIONS: Map<&[u8], Position> = Map::new("positions");
+/// positions keyed by their id, ids are never reused and double as cw721 token ids
+pub const POSITIONS: Map<u64, Position> = Map::new("positions_by_id");
+pub const NEXT_POSITION_ID: Item<u64> = Item::new("next_position_id");
+/// position ids of every owner, keyed by `position_key(owner, index)`
+pub const OWNER_POSITIONS: Map<&[u8], u64> = Map::new("owner_positions");
+/// owner of every position and its index among the positions of the owner
+pub const POSITION_OWNERS: Map<u64, (Addr, u32)> = Map::new("position_owners");
+/// positions of every pool, keyed by the pool key and the position id
+pub const POOL_POSITIONS: Map<(&[u8], u64), ()> = Map::new("pool_positions_by_id");
 
 pub const TICKS: Map<&[u8], Tick> = Map::new("ticks");
 
@@ -25,13 +37,47 @@ pub const BITMAP: Map<&[u8], u64> = Map::new("bitmap");
 
 pub const OPERATORS: Map<(&[u8], &[u8]), Expiration> = Map::new("operators");
 
+pub const ORACLES: Map<&[u8], Oracle> = Map::new("oracles");
+pub const OBSERVATIONS: Map<&[u8], Observation> = Map::new("observations");
+
+pub const PAUSED: Map<&[u8], bool> = Map::new("paused");
+
 pub const MAX_LIMIT: u32 = 100;
+/// longest route `FindBestRoute` searches, the number of candidate routes grows with every hop
+pub const MAX_ROUTE_HOPS: u8 = 3;
+/// most candidate routes `FindBestRoute` simulates, the search stops once that many are found
+pub const MAX_ROUTE_CANDIDATES: usize = 50;
+/// most chunks `QuoteSwapSplit` divides an input into, every chunk quotes every route
+pub const MAX_SPLIT_PARTS: u8 = 20;
+/// most routes a split swap or its quote goes through, the quote simulates every route per chunk
+pub const MAX_SPLIT_ROUTES: usize = 5;
+/// most incentive programs a pool keeps, every swap and liquidity change accrues all of them
+pub const MAX_POOL_INCENTIVES: usize = 10;
 
 pub fn get_pool(store: &dyn Storage, pool_key: &PoolKey) -> Result<Pool, ContractError> {
     let pool = POOLS.load(store, &pool_key.key())?;
     Ok(pool)
 }
 
+pub fn is_paused(store: &dyn Storage, scope: &PauseScope) -> bool {
+    PAUSED.has(store, &scope.key())
+}
+
+/// Fails when the global `scope` or the pool itself is paused.
+pub fn check_not_paused(
+    store: &dyn Storage,
+    scope: &PauseScope,
+    pool_key: &PoolKey,
+) -> Result<(), ContractError> {
+    let pool_scope = PauseScope::Pool {
+        pool_key: pool_key.clone(),
+    };
+    if is_paused(store, scope) || is_paused(store, &pool_scope) {
+        return Err(ContractError::Paused);
+    }
+    Ok(())
+}
+
 pub fn get_pools(
     store: &dyn Storage,
     limit: Option<u32>,
@@ -57,6 +103,22 @@ pub fn get_pools(
     Ok(pools)
 }
 
+/// Indexes a new pool under both of its tokens.
+pub fn add_token_pools(store: &mut dyn Storage, pool_key: &PoolKey) -> StdResult<()> {
+    let db_key = pool_key.key();
+    TOKEN_POOLS.save(store, (pool_key.token_x.as_str(), &db_key), &())?;
+    TOKEN_POOLS.save(store, (pool_key.token_y.as_str(), &db_key), &())
+}
+
+/// Lists the pools `token` trades in.
+pub fn get_token_pools(store: &dyn Storage, token: &str) -> Result<Vec<PoolKey>, ContractError> {
+    TOKEN_POOLS
+        .prefix(token)
+        .keys_raw(store, None, None, Order::Ascending)
+        .map(|raw_key| PoolKey::from_bytes(&raw_key))
+        .collect()
+}
+
 pub fn tick_key(pool_key: &PoolKey, index: i32) -> Vec<u8> {
     let mut db_key = pool_key.key();
     db_key.extend_from_slice(&index.to_be_bytes());
@@ -132,17 +194,75 @@ pub fn position_key(account_id: &Addr, index: u32) -> Vec<u8> {
     db_key
 }
 
+/// Encodes a position id as its cw721 token id.
+pub fn token_id(position_id: u64) -> Vec<u8> {
+    position_id.to_be_bytes().to_vec()
+}
+
+pub fn parse_token_id(token_id: &[u8]) -> Result<u64, ContractError> {
+    let bytes: [u8; 8] = token_id
+        .try_into()
+        .map_err(|_| ContractError::PositionNotFound)?;
+    Ok(u64::from_be_bytes(bytes))
+}
+
+/// Appends `position_id` to the positions of `account_id` and returns its index.
+fn push_owner_position(
+    store: &mut dyn Storage,
+    account_id: &Addr,
+    position_id: u64,
+) -> Result<u32, ContractError> {
+    let positions_length = get_position_length(store, account_id);
+    OWNER_POSITIONS.save(
+        store,
+        &position_key(account_id, positions_length),
+        &position_id,
+    )?;
+    POSITION_OWNERS.save(store, position_id, &(account_id.clone(), positions_length))?;
+    POSITIONS_LENGTH.save(store, account_id.as_bytes(), &(positions_length + 1))?;
+    Ok(positions_length)
+}
+
+/// Removes the position at `index` from the positions of `account_id`, the last position of the
+/// owner takes its index. Returns the id of the removed position.
+fn swap_remove_owner_position(
+    store: &mut dyn Storage,
+    account_id: &Addr,
+    index: u32,
+) -> Result<u64, ContractError> {
+    let position_id = get_position_id(store, account_id, index)?;
+    let positions_length = get_position_length(store, account_id) - 1;
+
+    if index < positions_length {
+        let last_key = position_key(account_id, positions_length);
+        let last_position_id = OWNER_POSITIONS.load(store, &last_key)?;
+        OWNER_POSITIONS.remove(store, &last_key);
+        OWNER_POSITIONS.save(store, &position_key(account_id, index), &last_position_id)?;
+        POSITION_OWNERS.save(store, last_position_id, &(account_id.clone(), index))?;
+    } else {
+        OWNER_POSITIONS.remove(store, &position_key(account_id, index));
+    }
+
+    POSITION_OWNERS.remove(store, position_id);
+    POSITIONS_LENGTH.save(store, account_id.as_bytes(), &positions_length)?;
+
+    Ok(position_id)
+}
+
+/// Stores a new position under the next position id and returns the id.
 pub fn add_position(
     store: &mut dyn Storage,
     account_id: &Addr,
     position: &Position,
-) -> Result<(), ContractError> {
-    let positions_length: u32 = get_position_length(store, account_id);
-    let db_key = position_key(account_id, positions_length);
-    POSITIONS.save(store, &db_key, position)?;
+) -> Result<u64, ContractError> {
+    let position_id = NEXT_POSITION_ID.may_load(store)?.unwrap_or_default();
+    NEXT_POSITION_ID.save(store, &(position_id + 1))?;
 
-    POSITIONS_LENGTH.save(store, account_id.as_bytes(), &(positions_length + 1))?;
-    Ok(())
+    POSITIONS.save(store, position_id, position)?;
+    POOL_POSITIONS.save(store, (&position.pool_key.key(), position_id), &())?;
+    push_owner_position(store, account_id, position_id)?;
+
+    Ok(position_id)
 }
 
 pub fn update_position(
@@ -151,15 +271,9 @@ pub fn update_position(
     index: u32,
     position: &Position,
 ) -> Result<(), ContractError> {
-    let positions_length = get_position_length(store, account_id);
-
-    if index >= positions_length {
-        return Err(ContractError::PositionNotFound);
-    }
-
-    let db_key = position_key(account_id, index);
+    let position_id = get_position_id(store, account_id, index)?;
 
-    POSITIONS.save(store, &db_key, position)?;
+    POSITIONS.save(store, position_id, position)?;
 
     Ok(())
 }
@@ -169,38 +283,62 @@ pub fn remove_position(
     account_id: &Addr,
     index: u32,
 ) -> Result<Position, ContractError> {
-    let positions_length = get_position_length(store, account_id) - 1;
-    let db_key = position_key(account_id, index);
-    let position = POSITIONS
-        .load(store, &db_key)
-        .map_err(|_| ContractError::PositionNotFound)?;
+    let position_id = swap_remove_owner_position(store, account_id, index)?;
+    let position = get_position_by_id(store, position_id)?;
 
-    if index < positions_length {
-        let prev_db_key = position_key(account_id, positions_length);
-        let last_position = POSITIONS.load(store, &prev_db_key)?;
-        POSITIONS.remove(store, &prev_db_key);
-        POSITIONS.save(store, &db_key, &last_position)?;
-    } else {
-        POSITIONS.remove(store, &db_key);
-    }
-
-    POSITIONS_LENGTH.save(store, account_id.as_bytes(), &(positions_length))?;
+    POSITIONS.remove(store, position_id);
+    POOL_POSITIONS.remove(store, (&position.pool_key.key(), position_id));
 
     Ok(position)
 }
 
+/// Moves the position at `index` of `account_id` to `receiver`, keeping its id.
+/// Returns the index of the position among the positions of `receiver`.
+pub fn transfer_position(
+    store: &mut dyn Storage,
+    account_id: &Addr,
+    index: u32,
+    receiver: &Addr,
+) -> Result<u32, ContractError> {
+    let position_id = swap_remove_owner_position(store, account_id, index)?;
+    push_owner_position(store, receiver, position_id)
+}
+
+pub fn get_position_id(
+    store: &dyn Storage,
+    account_id: &Addr,
+    index: u32,
+) -> Result<u64, ContractError> {
+    OWNER_POSITIONS
+        .load(store, &position_key(account_id, index))
+        .map_err(|_| ContractError::PositionNotFound)
+}
+
+/// Returns the owner of a position and its index among the positions of the owner.
+pub fn get_position_owner(
+    store: &dyn Storage,
+    position_id: u64,
+) -> Result<(Addr, u32), ContractError> {
+    POSITION_OWNERS
+        .load(store, position_id)
+        .map_err(|_| ContractError::PositionNotFound)
+}
+
 pub fn get_position(
     store: &dyn Storage,
     account_id: &Addr,
     index: u32,
 ) -> Result<Position, ContractError> {
-    let db_key = position_key(account_id, index);
-    get_position_by_key(store, &db_key)
+    let position_id = get_position_id(store, account_id, index)?;
+    get_position_by_id(store, position_id)
 }
 
-pub fn get_position_by_key(store: &dyn Storage, db_key: &[u8]) -> Result<Position, ContractError> {
+pub fn get_position_by_id(
+    store: &dyn Storage,
+    position_id: u64,
+) -> Result<Position, ContractError> {
     let position = POSITIONS
-        .load(store, db_key)
+        .load(store, position_id)
         .map_err(|_| ContractError::PositionNotFound)?;
 
     Ok(position)
@@ -220,17 +358,38 @@ pub fn get_all_positions(
         .collect()
 }
 
-pub fn get_all_position_keys(
+pub fn get_all_position_ids(
     store: &dyn Storage,
     account_id: &Addr,
     limit: Option<u32>,
     offset: Option<u32>,
-) -> Vec<Vec<u8>> {
+) -> Result<Vec<u64>, ContractError> {
     let from_idx = offset.unwrap_or(0);
     // maximum 100 items
     let to_idx = get_position_length(store, account_id).min(from_idx + limit.unwrap_or(MAX_LIMIT));
     (from_idx..to_idx)
-        .map(|index| position_key(account_id, index))
+        .map(|index| get_position_id(store, account_id, index))
+        .collect()
+}
+
+/// Lists the positions of a pool in id order, starting after `start_after`.
+pub fn get_pool_positions(
+    store: &dyn Storage,
+    pool_key: &PoolKey,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> Result<Vec<(u64, Position)>, ContractError> {
+    let limit = limit.unwrap_or(MAX_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    POOL_POSITIONS
+        .prefix(&pool_key.key())
+        .keys(store, start, None, Order::Ascending)
+        .take(limit)
+        .map(|position_id| -> Result<(u64, Position), ContractError> {
+            let position_id = position_id?;
+            Ok((position_id, get_position_by_id(store, position_id)?))
+        })
         .collect()
 }
 
@@ -425,6 +584,170 @@ pub fn flip_bitmap(
     Ok(())
 }
 
+pub fn observation_key(pool_key: &PoolKey, index: u16) -> Vec<u8> {
+    let mut db_key = pool_key.key();
+    db_key.extend_from_slice(&index.to_be_bytes());
+    db_key
+}
+
+pub fn get_observation(
+    store: &dyn Storage,
+    pool_key: &PoolKey,
+    index: u16,
+) -> Result<Observation, ContractError> {
+    OBSERVATIONS
+        .load(store, &observation_key(pool_key, index))
+        .map_err(|_| ContractError::OracleNotInitialized)
+}
+
+pub fn get_oracle(store: &dyn Storage, pool_key: &PoolKey) -> Result<Oracle, ContractError> {
+    ORACLES
+        .load(store, &pool_key.key())
+        .map_err(|_| ContractError::OracleNotInitialized)
+}
+
+pub fn initialize_oracle(
+    store: &mut dyn Storage,
+    pool_key: &PoolKey,
+    current_timestamp: u64,
+) -> Result<Oracle, ContractError> {
+    let oracle = Oracle {
+        index: 0,
+        cardinality: 1,
+        cardinality_next: 1,
+    };
+    ORACLES.save(store, &pool_key.key(), &oracle)?;
+    OBSERVATIONS.save(
+        store,
+        &observation_key(pool_key, 0),
+        &Observation::new(current_timestamp / 1000),
+    )?;
+
+    Ok(oracle)
+}
+
+/// Records the pool state before it changes, at most once per second.
+/// Pools created before the oracle existed are initialized on their first write.
+pub fn write_observation(
+    store: &mut dyn Storage,
+    pool_key: &PoolKey,
+    pool: &Pool,
+    current_timestamp: u64,
+) -> Result<(), ContractError> {
+    let mut oracle = match ORACLES.may_load(store, &pool_key.key())? {
+        Some(oracle) => oracle,
+        None => {
+            initialize_oracle(store, pool_key, current_timestamp)?;
+            return Ok(());
+        }
+    };
+
+    let timestamp = current_timestamp / 1000;
+    let last = get_observation(store, pool_key, oracle.index)?;
+    if last.timestamp >= timestamp {
+        return Ok(());
+    }
+
+    // the buffer only grows once the last populated slot has been written
+    if oracle.cardinality_next > oracle.cardinality && oracle.index == oracle.cardinality - 1 {
+        oracle.cardinality = oracle.cardinality_next;
+    }
+    oracle.index = (oracle.index + 1) % oracle.cardinality;
+
+    let observation = last.transform(timestamp, pool.current_tick_index, pool.liquidity)?;
+    OBSERVATIONS.save(
+        store,
+        &observation_key(pool_key, oracle.index),
+        &observation,
+    )?;
+    ORACLES.save(store, &pool_key.key(), &oracle)?;
+
+    Ok(())
+}
+
+pub fn increase_observation_cardinality(
+    store: &mut dyn Storage,
+    pool_key: &PoolKey,
+    current_timestamp: u64,
+    cardinality_next: u16,
+) -> Result<Oracle, ContractError> {
+    if cardinality_next > MAX_OBSERVATION_CARDINALITY {
+        return Err(ContractError::InvalidObservationCardinality);
+    }
+
+    let mut oracle = match ORACLES.may_load(store, &pool_key.key())? {
+        Some(oracle) => oracle,
+        None => initialize_oracle(store, pool_key, current_timestamp)?,
+    };
+
+    if cardinality_next > oracle.cardinality_next {
+        oracle.cardinality_next = cardinality_next;
+        ORACLES.save(store, &pool_key.key(), &oracle)?;
+    }
+
+    Ok(oracle)
+}
+
+/// Returns the observation at `seconds_ago` before `current_timestamp`.
+/// Points between two observations are interpolated, points after the last one are extrapolated
+/// from the current pool state.
+pub fn observe_single(
+    store: &dyn Storage,
+    pool_key: &PoolKey,
+    pool: &Pool,
+    oracle: &Oracle,
+    current_timestamp: u64,
+    seconds_ago: u64,
+) -> Result<Observation, ContractError> {
+    let target = (current_timestamp / 1000)
+        .checked_sub(seconds_ago)
+        .ok_or(ContractError::ObservationTooOld)?;
+
+    let last = get_observation(store, pool_key, oracle.index)?;
+    if last.timestamp <= target {
+        return last.transform(target, pool.current_tick_index, pool.liquidity);
+    }
+
+    // the slot after the newest one is the oldest once the buffer has wrapped around
+    let oldest_index = (oracle.index + 1) % oracle.cardinality;
+    let oldest = match OBSERVATIONS.may_load(store, &observation_key(pool_key, oldest_index))? {
+        Some(observation) => observation,
+        None => get_observation(store, pool_key, 0)?,
+    };
+    if target < oldest.timestamp {
+        return Err(ContractError::ObservationTooOld);
+    }
+
+    let cardinality = oracle.cardinality as u32;
+    let mut left = oracle.index as u32 + 1;
+    let mut right = left + cardinality - 1;
+
+    loop {
+        let middle = (left + right) / 2;
+        let before = match OBSERVATIONS.may_load(
+            store,
+            &observation_key(pool_key, (middle % cardinality) as u16),
+        )? {
+            Some(observation) => observation,
+            None => {
+                left = middle + 1;
+                continue;
+            }
+        };
+
+        if before.timestamp > target {
+            right = middle - 1;
+            continue;
+        }
+
+        let after = get_observation(store, pool_key, ((middle + 1) % cardinality) as u16)?;
+        if target <= after.timestamp {
+            return before.interpolate(&after, target);
+        }
+        left = middle + 1;
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
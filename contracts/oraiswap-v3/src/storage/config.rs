@@ -1,11 +1,99 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Uint128};
+use cw20::Expiration;
 
-use crate::{math::types::percentage::Percentage, FeeTier};
+use crate::{math::types::percentage::Percentage, FeeTier, PoolKey, MAX_TICK_CROSS};
 
 #[cw_serde]
 pub struct Config {
     pub admin: Addr,
     pub fee_tiers: Vec<FeeTier>,
     pub protocol_fee: Percentage,
+    /// can pause and unpause operations alongside the admin
+    #[serde(default)]
+    pub guardian: Option<Addr>,
+    #[serde(default)]
+    pub pool_creation_policy: PoolCreationPolicy,
+    /// charged to everyone but the admin when creating a pool
+    #[serde(default)]
+    pub pool_creation_fee: Option<PoolCreationFee>,
+    /// initialized ticks a single swap may cross, `MAX_TICK_CROSS` when unset
+    #[serde(default)]
+    pub max_tick_cross: Option<u32>,
+}
+
+impl Config {
+    pub fn max_tick_cross(&self) -> u32 {
+        self.max_tick_cross.unwrap_or(MAX_TICK_CROSS)
+    }
+}
+
+/// Who can create pools, the admin always can.
+#[cw_serde]
+#[derive(Default)]
+pub enum PoolCreationPolicy {
+    #[default]
+    Open,
+    AdminOnly,
+    /// both tokens of the pool must be listed, native denoms or cw20 addresses
+    Whitelist {
+        tokens: Vec<String>,
+    },
+}
+
+/// A native fee paid on pool creation and forwarded to `treasury`.
+#[cw_serde]
+pub struct PoolCreationFee {
+    pub denom: String,
+    pub amount: Uint128,
+    pub treasury: Addr,
+}
+
+impl PoolCreationPolicy {
+    pub fn allows(&self, pool_key: &PoolKey) -> bool {
+        match self {
+            Self::Open => true,
+            Self::AdminOnly => false,
+            Self::Whitelist { tokens } => {
+                tokens.contains(&pool_key.token_x) && tokens.contains(&pool_key.token_y)
+            }
+        }
+    }
+}
+
+/// An admin transfer waiting for the proposed address to accept it.
+#[cw_serde]
+pub struct PendingAdmin {
+    pub address: Addr,
+    pub expires: Expiration,
+}
+
+/// Operations that can be halted during an incident.
+/// Removing positions and claiming fees are never paused so liquidity providers can always exit.
+#[cw_serde]
+pub enum PauseScope {
+    /// every swap, including routes and cw20 hooks
+    Swaps,
+    /// opening new positions and creating pools
+    PositionCreation,
+    /// swaps and position creation in a single pool
+    Pool { pool_key: PoolKey },
+}
+
+impl PauseScope {
+    pub fn key(&self) -> Vec<u8> {
+        match self {
+            Self::Swaps => b"swaps".to_vec(),
+            Self::PositionCreation => b"position_creation".to_vec(),
+            Self::Pool { pool_key } => [b"pool".as_slice(), &pool_key.key()].concat(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Swaps => "swaps",
+            Self::PositionCreation => "position_creation",
+            Self::Pool { .. } => "pool",
+        }
+    }
 }
@@ -0,0 +1,156 @@
+use crate::{math::types::percentage::Percentage, ContractError};
+use cosmwasm_schema::cw_serde;
+use decimal::*;
+
+/// Bounds of a pool fee that scales with the distance the price moved recently.
+#[cw_serde]
+#[derive(Eq, Copy)]
+pub struct DynamicFeeConfig {
+    pub min_fee: Percentage,
+    pub max_fee: Percentage,
+    /// length of a volatility window in seconds
+    pub window: u64,
+    /// ticks moved within a window at which the fee reaches `max_fee`
+    pub max_volatility: u32,
+}
+
+/// Ticks the price moved in the current and the previous window, timestamps are block times in
+/// seconds.
+#[cw_serde]
+#[derive(Eq, Copy, Default)]
+pub struct Volatility {
+    pub window_start: u64,
+    pub current: u32,
+    pub previous: u32,
+}
+
+impl DynamicFeeConfig {
+    pub fn validate(&self) -> Result<(), ContractError> {
+        if self.min_fee > self.max_fee || self.max_fee >= Percentage::from_integer(1) {
+            return Err(ContractError::InvalidFee);
+        }
+
+        if self.window == 0 || self.max_volatility == 0 {
+            return Err(ContractError::InvalidDynamicFee);
+        }
+
+        Ok(())
+    }
+
+    /// Interpolates between `min_fee` and `max_fee` by the larger of the two tracked windows.
+    pub fn fee(&self, volatility: &Volatility) -> Percentage {
+        let observed = volatility
+            .current
+            .max(volatility.previous)
+            .min(self.max_volatility);
+        let range = (self.max_fee.get() - self.min_fee.get()) as u128;
+
+        Percentage::new(
+            self.min_fee.get() + (range * observed as u128 / self.max_volatility as u128) as u64,
+        )
+    }
+}
+
+impl Volatility {
+    pub fn new(current_timestamp: u64) -> Self {
+        Self {
+            window_start: current_timestamp,
+            ..Self::default()
+        }
+    }
+
+    /// Moves to the window containing `current_timestamp`, windows without swaps count as calm.
+    pub fn roll(&mut self, window: u64, current_timestamp: u64) {
+        if current_timestamp < self.window_start + window {
+            return;
+        }
+
+        let elapsed = current_timestamp - self.window_start;
+        self.previous = if elapsed < 2 * window {
+            self.current
+        } else {
+            0
+        };
+        self.current = 0;
+        self.window_start = current_timestamp - elapsed % window;
+    }
+
+    pub fn record(&mut self, start_tick: i32, end_tick: i32) {
+        self.current = self.current.saturating_add(start_tick.abs_diff(end_tick));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dynamic_fee() {
+        let config = DynamicFeeConfig {
+            min_fee: Percentage::from_scale(1, 3),
+            max_fee: Percentage::from_scale(1, 2),
+            window: 60,
+            max_volatility: 100,
+        };
+        config.validate().unwrap();
+
+        let mut volatility = Volatility::new(0);
+        assert_eq!(config.fee(&volatility), config.min_fee);
+
+        volatility.record(0, -50);
+        assert_eq!(config.fee(&volatility), Percentage::from_scale(55, 4));
+
+        // capped at the max fee
+        volatility.record(-50, 100);
+        assert_eq!(config.fee(&volatility), config.max_fee);
+
+        // the previous window still counts
+        volatility.roll(60, 70);
+        assert_eq!(volatility.window_start, 60);
+        assert_eq!(volatility.current, 0);
+        assert_eq!(volatility.previous, 200);
+        assert_eq!(config.fee(&volatility), config.max_fee);
+
+        // a quiet window resets the fee
+        volatility.roll(60, 200);
+        assert_eq!(volatility.window_start, 180);
+        assert_eq!(volatility.previous, 0);
+        assert_eq!(config.fee(&volatility), config.min_fee);
+    }
+
+    #[test]
+    fn test_validate_dynamic_fee() {
+        let config = DynamicFeeConfig {
+            min_fee: Percentage::from_scale(1, 2),
+            max_fee: Percentage::from_scale(1, 3),
+            window: 60,
+            max_volatility: 100,
+        };
+        config.validate().unwrap_err();
+
+        DynamicFeeConfig {
+            min_fee: Percentage::from_scale(1, 3),
+            max_fee: Percentage::from_scale(1, 2),
+            window: 0,
+            max_volatility: 100,
+        }
+        .validate()
+        .unwrap_err();
+
+        // a max fee of 100% would take the whole input
+        let config = DynamicFeeConfig {
+            min_fee: Percentage::from_scale(1, 3),
+            max_fee: Percentage::from_integer(1),
+            window: 60,
+            max_volatility: 100,
+        };
+        config.validate().unwrap_err();
+
+        DynamicFeeConfig {
+            max_fee: Percentage::new(Percentage::from_integer(1).get() - 1),
+            ..config
+        }
+        .validate()
+        .unwrap();
+    }
+}
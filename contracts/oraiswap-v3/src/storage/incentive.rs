@@ -0,0 +1,166 @@
+use crate::{
+    interface::AssetInfo,
+    math::types::{fee_growth::FeeGrowth, liquidity::Liquidity, token_amount::TokenAmount},
+    ContractError,
+};
+
+use cosmwasm_schema::cw_serde;
+use decimal::*;
+
+/// A reward program streaming `reward_per_sec` of `reward_token` to the in-range liquidity of a
+/// pool between `start_timestamp` and `end_timestamp`, timestamps are block times in seconds.
+#[cw_serde]
+pub struct IncentiveRecord {
+    pub id: u64,
+    pub reward_token: AssetInfo,
+    pub reward_per_sec: TokenAmount,
+    pub start_timestamp: u64,
+    pub end_timestamp: u64,
+    pub last_updated: u64,
+    pub incentive_growth_global: FeeGrowth,
+    /// emission of the periods without in-range liquidity, the admin can withdraw it
+    pub unallocated: TokenAmount,
+}
+
+/// Incentive growth on the other side of a tick, analogous to `fee_growth_outside_x`.
+#[cw_serde]
+#[derive(Eq, Default)]
+pub struct TickIncentive {
+    pub incentive_id: u64,
+    pub incentive_growth_outside: FeeGrowth,
+}
+
+/// Per-position checkpoint of an incentive program.
+#[cw_serde]
+#[derive(Default)]
+pub struct PositionIncentive {
+    pub incentive_id: u64,
+    pub incentive_growth_inside: FeeGrowth,
+    pub pending_rewards: TokenAmount,
+}
+
+impl IncentiveRecord {
+    /// Distributes the emission since `last_updated` over `liquidity`.
+    pub fn update_growth(
+        &mut self,
+        liquidity: Liquidity,
+        current_timestamp: u64,
+    ) -> Result<(), ContractError> {
+        let from = self.last_updated.max(self.start_timestamp);
+        let to = current_timestamp.min(self.end_timestamp);
+
+        if to > from {
+            let reward = TokenAmount::new(
+                self.reward_per_sec
+                    .get()
+                    .checked_mul((to - from) as u128)
+                    .ok_or(ContractError::Mul)?,
+            );
+            if liquidity.is_zero() {
+                // emissions while the price is outside of every position are not distributed
+                self.unallocated = TokenAmount::new(
+                    self.unallocated
+                        .get()
+                        .checked_add(reward.get())
+                        .ok_or(ContractError::Add)?,
+                );
+            } else {
+                self.incentive_growth_global = self
+                    .incentive_growth_global
+                    .unchecked_add(FeeGrowth::from_fee(liquidity, reward)?);
+            }
+        }
+
+        self.last_updated = self.last_updated.max(current_timestamp);
+        Ok(())
+    }
+}
+
+pub fn calculate_incentive_growth_inside(
+    tick_lower: i32,
+    tick_lower_incentive_growth_outside: FeeGrowth,
+    tick_upper: i32,
+    tick_upper_incentive_growth_outside: FeeGrowth,
+    tick_current: i32,
+    incentive_growth_global: FeeGrowth,
+) -> FeeGrowth {
+    let incentive_growth_below = if tick_current >= tick_lower {
+        tick_lower_incentive_growth_outside
+    } else {
+        incentive_growth_global.unchecked_sub(tick_lower_incentive_growth_outside)
+    };
+
+    let incentive_growth_above = if tick_current < tick_upper {
+        tick_upper_incentive_growth_outside
+    } else {
+        incentive_growth_global.unchecked_sub(tick_upper_incentive_growth_outside)
+    };
+
+    incentive_growth_global
+        .unchecked_sub(incentive_growth_below)
+        .unchecked_sub(incentive_growth_above)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::Addr;
+
+    #[test]
+    fn test_update_growth() {
+        let mut record = IncentiveRecord {
+            id: 0,
+            reward_token: AssetInfo::Token {
+                contract_addr: Addr::unchecked("reward"),
+            },
+            reward_per_sec: TokenAmount(10),
+            start_timestamp: 100,
+            end_timestamp: 200,
+            last_updated: 50,
+            incentive_growth_global: FeeGrowth::new(0),
+            unallocated: TokenAmount(0),
+        };
+
+        // nothing is emitted before the start
+        record
+            .update_growth(Liquidity::from_integer(5), 80)
+            .unwrap();
+        assert_eq!(record.incentive_growth_global, FeeGrowth::new(0));
+        assert_eq!(record.last_updated, 80);
+
+        record
+            .update_growth(Liquidity::from_integer(5), 150)
+            .unwrap();
+        assert_eq!(record.incentive_growth_global, FeeGrowth::from_integer(100));
+
+        // nothing is distributed without liquidity, the emission is kept aside
+        record.update_growth(Liquidity::new(0), 160).unwrap();
+        assert_eq!(record.incentive_growth_global, FeeGrowth::from_integer(100));
+        assert_eq!(record.unallocated, TokenAmount(100));
+
+        // emission stops at the end
+        record
+            .update_growth(Liquidity::from_integer(5), 300)
+            .unwrap();
+        assert_eq!(record.incentive_growth_global, FeeGrowth::from_integer(180));
+        assert_eq!(record.unallocated, TokenAmount(100));
+        assert_eq!(record.last_updated, 300);
+    }
+
+    #[test]
+    fn test_calculate_incentive_growth_inside() {
+        let global = FeeGrowth::from_integer(15);
+        let lower_outside = FeeGrowth::from_integer(5);
+        let upper_outside = FeeGrowth::from_integer(3);
+
+        // in range
+        let inside =
+            calculate_incentive_growth_inside(-10, lower_outside, 10, upper_outside, 0, global);
+        assert_eq!(inside, FeeGrowth::from_integer(7));
+
+        // current tick below the range
+        let inside =
+            calculate_incentive_growth_inside(-10, lower_outside, 10, upper_outside, -20, global);
+        assert_eq!(inside, lower_outside.unchecked_sub(upper_outside));
+    }
+}
@@ -0,0 +1,159 @@
+use crate::{
+    math::types::{liquidity::Liquidity, seconds_per_liquidity::SecondsPerLiquidity},
+    ContractError,
+};
+
+use cosmwasm_schema::cw_serde;
+use decimal::*;
+
+/// Upper bound for the number of observations stored per pool.
+pub const MAX_OBSERVATION_CARDINALITY: u16 = 1000;
+
+/// A single oracle observation, timestamps are block times in seconds.
+#[cw_serde]
+#[derive(Default, Copy)]
+pub struct Observation {
+    pub timestamp: u64,
+    pub tick_cumulative: i64,
+    pub seconds_per_liquidity_cumulative: SecondsPerLiquidity,
+}
+
+/// The ring buffer state of a pool oracle.
+#[cw_serde]
+#[derive(Default, Copy)]
+pub struct Oracle {
+    /// Index of the most recently written observation.
+    pub index: u16,
+    /// Number of populated slots in the ring buffer.
+    pub cardinality: u16,
+    /// Number of slots the ring buffer grows to once the last slot is written.
+    pub cardinality_next: u16,
+}
+
+impl Observation {
+    pub fn new(timestamp: u64) -> Self {
+        Self {
+            timestamp,
+            ..Self::default()
+        }
+    }
+
+    /// Extends the observation up to `timestamp` assuming `tick` and `liquidity` were constant since.
+    pub fn transform(
+        &self,
+        timestamp: u64,
+        tick: i32,
+        liquidity: Liquidity,
+    ) -> Result<Self, ContractError> {
+        if timestamp <= self.timestamp {
+            return Ok(*self);
+        }
+        let delta = timestamp - self.timestamp;
+
+        // an empty range still accrues time, the liquidity is clamped to the smallest unit
+        let liquidity = if liquidity.is_zero() {
+            Liquidity::new(1)
+        } else {
+            liquidity
+        };
+
+        Ok(Self {
+            timestamp,
+            tick_cumulative: self
+                .tick_cumulative
+                .wrapping_add((tick as i64).wrapping_mul(delta as i64)),
+            seconds_per_liquidity_cumulative: self.seconds_per_liquidity_cumulative.unchecked_add(
+                SecondsPerLiquidity::calculate_seconds_per_liquidity_global(
+                    liquidity,
+                    timestamp,
+                    self.timestamp,
+                )?,
+            ),
+        })
+    }
+
+    /// Linearly interpolates between two observations at `timestamp`.
+    pub fn interpolate(&self, after: &Self, timestamp: u64) -> Result<Self, ContractError> {
+        if timestamp <= self.timestamp {
+            return Ok(*self);
+        }
+        if timestamp >= after.timestamp {
+            return Ok(*after);
+        }
+
+        let delta = after.timestamp - self.timestamp;
+        let observed = timestamp - self.timestamp;
+
+        let tick_cumulative_delta = after.tick_cumulative.wrapping_sub(self.tick_cumulative);
+        let seconds_per_liquidity_delta = after
+            .seconds_per_liquidity_cumulative
+            .unchecked_sub(self.seconds_per_liquidity_cumulative);
+
+        Ok(Self {
+            timestamp,
+            tick_cumulative: self
+                .tick_cumulative
+                .wrapping_add(tick_cumulative_delta / delta as i64 * observed as i64),
+            seconds_per_liquidity_cumulative: self.seconds_per_liquidity_cumulative.unchecked_add(
+                SecondsPerLiquidity::new(
+                    U256::from(seconds_per_liquidity_delta.get())
+                        .checked_mul(U256::from(observed))
+                        .ok_or(ContractError::Mul)?
+                        .checked_div(U256::from(delta))
+                        .ok_or(ContractError::Div)?
+                        .try_into()
+                        .map_err(|_| ContractError::Cast)?,
+                ),
+            ),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transform() {
+        let observation = Observation::new(100);
+
+        let next = observation
+            .transform(110, -20, Liquidity::from_integer(10))
+            .unwrap();
+        assert_eq!(next.timestamp, 110);
+        assert_eq!(next.tick_cumulative, -200);
+        assert_eq!(
+            next.seconds_per_liquidity_cumulative,
+            SecondsPerLiquidity::from_integer(1)
+        );
+
+        // same timestamp is a no-op
+        let same = next
+            .transform(110, 50, Liquidity::from_integer(10))
+            .unwrap();
+        assert_eq!(same, next);
+
+        // zero liquidity still accrues the tick
+        let empty = next.transform(120, 5, Liquidity::new(0)).unwrap();
+        assert_eq!(empty.tick_cumulative, -150);
+    }
+
+    #[test]
+    fn test_interpolate() {
+        let before = Observation::new(100);
+        let after = before
+            .transform(120, 10, Liquidity::from_integer(4))
+            .unwrap();
+
+        let middle = before.interpolate(&after, 110).unwrap();
+        assert_eq!(middle.timestamp, 110);
+        assert_eq!(middle.tick_cumulative, 100);
+        assert_eq!(
+            middle.seconds_per_liquidity_cumulative,
+            SecondsPerLiquidity::from_scale(25, 1)
+        );
+
+        assert_eq!(before.interpolate(&after, 100).unwrap(), before);
+        assert_eq!(before.interpolate(&after, 120).unwrap(), after);
+    }
+}
@@ -1,4 +1,4 @@
-use super::{FeeTier, Tick};
+use super::{DynamicFeeConfig, FeeTier, IncentiveRecord, Tick, Volatility};
 use crate::math::types::sqrt_price::check_tick_to_sqrt_price_relationship;
 use crate::{
     math::{
@@ -6,7 +6,8 @@ use crate::{
         log::get_tick_at_sqrt_price,
         types::{
             fee_growth::FeeGrowth, liquidity::Liquidity, percentage::Percentage,
-            sqrt_price::SqrtPrice, token_amount::TokenAmount,
+            seconds_per_liquidity::SecondsPerLiquidity, sqrt_price::SqrtPrice,
+            token_amount::TokenAmount,
         },
     },
     ContractError,
@@ -29,6 +30,20 @@ pub struct Pool {
     pub start_timestamp: u64,
     pub last_timestamp: u64,
     pub fee_receiver: String,
+    /// accumulated seconds per unit of in-range liquidity since pool creation
+    #[serde(default)]
+    pub seconds_per_liquidity_global: SecondsPerLiquidity,
+    /// reward programs distributed to the in-range liquidity, ids are indexes in this list
+    #[serde(default)]
+    pub incentives: Vec<IncentiveRecord>,
+    /// overrides the protocol fee from the config for this pool when set
+    #[serde(default)]
+    pub protocol_fee: Option<Percentage>,
+    /// scales the swap fee with the recent price movement when set, replacing the fee tier fee
+    #[serde(default)]
+    pub dynamic_fee: Option<DynamicFeeConfig>,
+    #[serde(default)]
+    pub volatility: Volatility,
 }
 
 #[derive(PartialEq, Eq, Debug, Clone)]
@@ -182,6 +197,57 @@ impl Pool {
         Ok((total_amount, remaining_amount, has_crossed))
     }
 
+    /// Accrues the time passed since `last_timestamp` (block times in milliseconds) to
+    /// `seconds_per_liquidity_global`, must be called before the pool liquidity changes.
+    pub fn update_seconds_per_liquidity_global(
+        &mut self,
+        current_timestamp: u64,
+    ) -> Result<(), ContractError> {
+        let current_seconds = current_timestamp / 1000;
+        let last_seconds = self.last_timestamp / 1000;
+
+        if !self.liquidity.is_zero() && current_seconds > last_seconds {
+            let seconds_per_liquidity =
+                SecondsPerLiquidity::calculate_seconds_per_liquidity_global(
+                    self.liquidity,
+                    current_seconds,
+                    last_seconds,
+                )?;
+            self.seconds_per_liquidity_global = self
+                .seconds_per_liquidity_global
+                .unchecked_add(seconds_per_liquidity);
+        }
+
+        self.last_timestamp = current_timestamp;
+        Ok(())
+    }
+
+    /// Returns the fee charged by a swap starting at `current_timestamp` (block time in
+    /// milliseconds), the dynamic fee is fixed for the whole swap.
+    pub fn swap_fee(&mut self, fee_tier_fee: Percentage, current_timestamp: u64) -> Percentage {
+        match self.dynamic_fee {
+            Some(dynamic_fee) => {
+                self.volatility
+                    .roll(dynamic_fee.window, current_timestamp / 1000);
+                dynamic_fee.fee(&self.volatility)
+            }
+            None => fee_tier_fee,
+        }
+    }
+
+    /// Accrues the incentive emissions up to `current_timestamp` (block time in milliseconds),
+    /// must be called before the pool liquidity changes.
+    pub fn update_global_incentives(
+        &mut self,
+        current_timestamp: u64,
+    ) -> Result<(), ContractError> {
+        let current_seconds = current_timestamp / 1000;
+        for record in self.incentives.iter_mut() {
+            record.update_growth(self.liquidity, current_seconds)?;
+        }
+        Ok(())
+    }
+
     pub fn withdraw_protocol_fee(&mut self) -> (TokenAmount, TokenAmount) {
         let fee_protocol_token_x = self.fee_protocol_token_x;
         let fee_protocol_token_y = self.fee_protocol_token_y;
@@ -549,4 +615,35 @@ mod tests {
             assert_eq!(pool.liquidity, Liquidity::from_integer(5))
         }
     }
+
+    #[test]
+    fn test_update_seconds_per_liquidity_global() {
+        // liquidity in range accrues the elapsed seconds
+        {
+            let mut pool = Pool {
+                liquidity: Liquidity::from_integer(4),
+                last_timestamp: 10_000,
+                ..Default::default()
+            };
+            pool.update_seconds_per_liquidity_global(30_000).unwrap();
+            assert_eq!(
+                pool.seconds_per_liquidity_global,
+                SecondsPerLiquidity::from_integer(5)
+            );
+            assert_eq!(pool.last_timestamp, 30_000);
+        }
+        // empty pool only moves the timestamp
+        {
+            let mut pool = Pool {
+                last_timestamp: 10_000,
+                ..Default::default()
+            };
+            pool.update_seconds_per_liquidity_global(30_000).unwrap();
+            assert_eq!(
+                pool.seconds_per_liquidity_global,
+                SecondsPerLiquidity::new(0)
+            );
+            assert_eq!(pool.last_timestamp, 30_000);
+        }
+    }
 }
@@ -1,6 +1,6 @@
-use super::{Pool, PoolKey, Tick};
+use super::{calculate_incentive_growth_inside, Pool, PoolKey, PositionIncentive, Tick};
 use crate::{
-    interface::Approval,
+    interface::{Approval, Asset},
     math::{
         clamm::*,
         types::{
@@ -30,6 +30,9 @@ pub struct Position {
     /// approvals are stored here, as we clear them all upon transfer and cannot accumulate much
     #[serde(default)]
     pub approvals: Vec<Approval>,
+    /// reward checkpoints for the incentive programs of the pool
+    #[serde(default)]
+    pub incentives: Vec<PositionIncentive>,
 }
 
 impl Position {
@@ -44,7 +47,8 @@ impl Position {
         current_timestamp: u64,
         tick_spacing: u16,
     ) -> Result<(TokenAmount, TokenAmount), ContractError> {
-        pool.last_timestamp = current_timestamp;
+        pool.update_seconds_per_liquidity_global(current_timestamp)?;
+        pool.update_global_incentives(current_timestamp)?;
 
         // calculate dynamically limit allows easy modification
         let max_liquidity_per_tick = calculate_max_liquidity_per_tick(tick_spacing);
@@ -67,6 +71,9 @@ impl Position {
             pool.fee_growth_global_y,
         );
 
+        // incentives accrue on the liquidity before the change
+        self.update_incentives(pool, lower_tick, upper_tick)?;
+
         self.update(
             add,
             liquidity_delta,
@@ -106,6 +113,79 @@ impl Position {
         Ok(())
     }
 
+    pub fn update_incentives(
+        &mut self,
+        pool: &Pool,
+        lower_tick: &Tick,
+        upper_tick: &Tick,
+    ) -> Result<(), ContractError> {
+        for record in &pool.incentives {
+            let incentive_growth_inside = calculate_incentive_growth_inside(
+                lower_tick.index,
+                lower_tick.incentive_growth_outside(record.id),
+                upper_tick.index,
+                upper_tick.incentive_growth_outside(record.id),
+                pool.current_tick_index,
+                record.incentive_growth_global,
+            );
+
+            // a missing checkpoint is the zero growth the program started with
+            let incentive = match self
+                .incentives
+                .iter()
+                .position(|incentive| incentive.incentive_id == record.id)
+            {
+                Some(index) => &mut self.incentives[index],
+                None => {
+                    self.incentives.push(PositionIncentive {
+                        incentive_id: record.id,
+                        ..Default::default()
+                    });
+                    self.incentives.last_mut().unwrap()
+                }
+            };
+
+            let rewards = incentive_growth_inside
+                .unchecked_sub(incentive.incentive_growth_inside)
+                .to_fee(self.liquidity)?;
+            incentive.incentive_growth_inside = incentive_growth_inside;
+            incentive.pending_rewards += rewards;
+        }
+
+        Ok(())
+    }
+
+    pub fn claim_incentives(
+        &mut self,
+        pool: &mut Pool,
+        lower_tick: &Tick,
+        upper_tick: &Tick,
+        current_timestamp: u64,
+    ) -> Result<Vec<Asset>, ContractError> {
+        pool.update_global_incentives(current_timestamp)?;
+        self.update_incentives(pool, lower_tick, upper_tick)?;
+
+        let mut rewards = vec![];
+        for incentive in self.incentives.iter_mut() {
+            if incentive.pending_rewards.is_zero() {
+                continue;
+            }
+            if let Some(record) = pool
+                .incentives
+                .iter()
+                .find(|record| record.id == incentive.incentive_id)
+            {
+                rewards.push(Asset {
+                    info: record.reward_token.clone(),
+                    amount: incentive.pending_rewards.into(),
+                });
+                incentive.pending_rewards = TokenAmount(0);
+            }
+        }
+
+        Ok(rewards)
+    }
+
     fn calculate_new_liquidity(
         &mut self,
         sign: bool,
@@ -182,6 +262,7 @@ impl Position {
             tokens_owed_x: TokenAmount::new(0),
             tokens_owed_y: TokenAmount::new(0),
             approvals: vec![],
+            incentives: vec![],
         };
 
         let (required_x, required_y) = position.modify(
@@ -197,6 +278,38 @@ impl Position {
         Ok((position, required_x, required_y))
     }
 
+    #[allow(clippy::too_many_arguments)]
+    pub fn increase(
+        &mut self,
+        pool: &mut Pool,
+        lower_tick: &mut Tick,
+        upper_tick: &mut Tick,
+        current_timestamp: u64,
+        liquidity_delta: Liquidity,
+        slippage_limit_lower: SqrtPrice,
+        slippage_limit_upper: SqrtPrice,
+        block_number: u64,
+        tick_spacing: u16,
+    ) -> Result<(TokenAmount, TokenAmount), ContractError> {
+        if pool.sqrt_price < slippage_limit_lower || pool.sqrt_price > slippage_limit_upper {
+            return Err(ContractError::PriceLimitReached);
+        }
+
+        // accrued fees are settled into tokens owed before the liquidity changes
+        let (required_x, required_y) = self.modify(
+            pool,
+            upper_tick,
+            lower_tick,
+            liquidity_delta,
+            true,
+            current_timestamp,
+            tick_spacing,
+        )?;
+        self.last_block_number = block_number;
+
+        Ok((required_x, required_y))
+    }
+
     pub fn remove(
         &mut self,
         pool: &mut Pool,
@@ -206,19 +319,47 @@ impl Position {
         tick_spacing: u16,
     ) -> Result<(TokenAmount, TokenAmount, bool, bool), ContractError> {
         let liquidity_delta = self.liquidity;
-        let (mut amount_x, mut amount_y) = self.modify(
+        let (mut amount_x, mut amount_y, deinitialize_lower_tick, deinitialize_upper_tick) = self
+            .decrease(
             pool,
-            upper_tick,
+            current_timestamp,
             lower_tick,
+            upper_tick,
             liquidity_delta,
-            false,
-            current_timestamp,
             tick_spacing,
         )?;
 
         amount_x += self.tokens_owed_x;
         amount_y += self.tokens_owed_y;
 
+        Ok((
+            amount_x,
+            amount_y,
+            deinitialize_lower_tick,
+            deinitialize_upper_tick,
+        ))
+    }
+
+    pub fn decrease(
+        &mut self,
+        pool: &mut Pool,
+        current_timestamp: u64,
+        lower_tick: &mut Tick,
+        upper_tick: &mut Tick,
+        liquidity_delta: Liquidity,
+        tick_spacing: u16,
+    ) -> Result<(TokenAmount, TokenAmount, bool, bool), ContractError> {
+        let (amount_x, amount_y) = self.modify(
+            pool,
+            upper_tick,
+            lower_tick,
+            liquidity_delta,
+            false,
+            current_timestamp,
+            tick_spacing,
+        )?;
+
+        // a tick can only be deinitialized once no position references it anymore
         let deinitialize_lower_tick = lower_tick.liquidity_gross.is_zero();
         let deinitialize_upper_tick = upper_tick.liquidity_gross.is_zero();
 
@@ -1,8 +1,9 @@
-use super::Pool;
+use super::{Pool, TickIncentive};
 use crate::{
     math::types::{
         fee_growth::FeeGrowth,
         liquidity::Liquidity,
+        seconds_per_liquidity::SecondsPerLiquidity,
         sqrt_price::{calculate_sqrt_price, SqrtPrice},
     },
     ContractError,
@@ -11,7 +12,7 @@ use cosmwasm_schema::cw_serde;
 use decimal::*;
 
 #[cw_serde]
-#[derive(Eq, Copy, Default)]
+#[derive(Eq, Default)]
 pub struct Tick {
     pub index: i32,
     pub sign: bool,
@@ -21,6 +22,10 @@ pub struct Tick {
     pub fee_growth_outside_x: FeeGrowth,
     pub fee_growth_outside_y: FeeGrowth,
     pub seconds_outside: u64,
+    #[serde(default)]
+    pub seconds_per_liquidity_outside: SecondsPerLiquidity,
+    #[serde(default)]
+    pub incentives: Vec<TickIncentive>,
 }
 
 pub const MAX_RESULT_SIZE: usize = 16 * 1024 * 8;
@@ -76,6 +81,21 @@ impl Tick {
                 true => current_timestamp - pool.start_timestamp,
                 false => 0,
             },
+            seconds_per_liquidity_outside: match below_current_tick {
+                true => pool.seconds_per_liquidity_global,
+                false => SecondsPerLiquidity::new(0),
+            },
+            incentives: pool
+                .incentives
+                .iter()
+                .map(|record| TickIncentive {
+                    incentive_id: record.id,
+                    incentive_growth_outside: match below_current_tick {
+                        true => record.incentive_growth_global,
+                        false => FeeGrowth::new(0),
+                    },
+                })
+                .collect(),
             ..Self::default()
         }
     }
@@ -92,6 +112,28 @@ impl Tick {
             .checked_sub(pool.start_timestamp)
             .ok_or(ContractError::TimestampSubOverflow)?;
         self.seconds_outside = seconds_passed.wrapping_sub(self.seconds_outside);
+        self.seconds_per_liquidity_outside = pool
+            .seconds_per_liquidity_global
+            .unchecked_sub(self.seconds_per_liquidity_outside);
+
+        // programs created after the tick was initialized start with zero growth outside
+        for record in &pool.incentives {
+            match self
+                .incentives
+                .iter_mut()
+                .find(|incentive| incentive.incentive_id == record.id)
+            {
+                Some(incentive) => {
+                    incentive.incentive_growth_outside = record
+                        .incentive_growth_global
+                        .unchecked_sub(incentive.incentive_growth_outside);
+                }
+                None => self.incentives.push(TickIncentive {
+                    incentive_id: record.id,
+                    incentive_growth_outside: record.incentive_growth_global,
+                }),
+            }
+        }
 
         pool.last_timestamp = current_timestamp;
 
@@ -111,6 +153,14 @@ impl Tick {
         Ok(())
     }
 
+    pub fn incentive_growth_outside(&self, incentive_id: u64) -> FeeGrowth {
+        self.incentives
+            .iter()
+            .find(|incentive| incentive.incentive_id == incentive_id)
+            .map(|incentive| incentive.incentive_growth_outside)
+            .unwrap_or_default()
+    }
+
     pub fn update(
         &mut self,
         liquidity_delta: Liquidity,
@@ -142,7 +192,7 @@ impl Tick {
     }
 
     fn calculate_new_liquidity_gross(
-        self,
+        &self,
         sign: bool,
         liquidity_delta: Liquidity,
         max_liquidity_per_tick: Liquidity,
@@ -2,7 +2,9 @@ use crate::msg::{ExecuteMsg, QueryMsg};
 use crate::percentage::Percentage;
 use crate::tests::helper::macros::*;
 use crate::tests::helper::MockApp;
-use cosmwasm_std::Addr;
+use crate::PendingAdmin;
+use cosmwasm_std::{Addr, Timestamp};
+use cw20::Expiration;
 use decimal::Decimal;
 
 #[test]
@@ -26,6 +28,18 @@ fn test_change_admin() {
     );
     assert!(result.is_ok());
 
+    // the admin only changes once bob accepts
+    let admin: Addr = app.query(dex.clone(), &query_msg).unwrap();
+    assert_eq!(admin, Addr::unchecked("alice"));
+
+    app.execute(
+        Addr::unchecked("bob"),
+        Addr::unchecked(dex.clone()),
+        &ExecuteMsg::AcceptAdmin {},
+        &[],
+    )
+    .unwrap();
+
     let admin: Addr = app.query(dex.clone(), &query_msg).unwrap();
     assert_eq!(admin, Addr::unchecked("bob"));
 }
@@ -49,3 +63,148 @@ fn test_change_admin_not_admin() {
 
     assert!(result.contains("error executing WasmMsg"));
 }
+
+#[test]
+fn test_two_step_admin_transfer() {
+    let mut app = MockApp::new(&[]);
+    let dex = create_dex!(app, Percentage::new(0));
+
+    let propose_msg = ExecuteMsg::ProposeAdmin {
+        new_admin: "bob".to_string(),
+        expires: None,
+    };
+
+    // only the admin can propose
+    app.execute(
+        Addr::unchecked("bob"),
+        Addr::unchecked(dex.clone()),
+        &propose_msg,
+        &[],
+    )
+    .unwrap_err();
+
+    app.execute(
+        Addr::unchecked("alice"),
+        Addr::unchecked(dex.clone()),
+        &propose_msg,
+        &[],
+    )
+    .unwrap();
+
+    let pending_admin: Option<PendingAdmin> =
+        app.query(dex.clone(), &QueryMsg::PendingAdmin {}).unwrap();
+    assert_eq!(
+        pending_admin,
+        Some(PendingAdmin {
+            address: Addr::unchecked("bob"),
+            expires: Expiration::Never {},
+        })
+    );
+
+    // the admin does not change until the proposal is accepted
+    let admin: Addr = app.query(dex.clone(), &QueryMsg::Admin {}).unwrap();
+    assert_eq!(admin, Addr::unchecked("alice"));
+
+    // only the proposed address can accept
+    app.execute(
+        Addr::unchecked("alice"),
+        Addr::unchecked(dex.clone()),
+        &ExecuteMsg::AcceptAdmin {},
+        &[],
+    )
+    .unwrap_err();
+
+    app.execute(
+        Addr::unchecked("bob"),
+        Addr::unchecked(dex.clone()),
+        &ExecuteMsg::AcceptAdmin {},
+        &[],
+    )
+    .unwrap();
+
+    let admin: Addr = app.query(dex.clone(), &QueryMsg::Admin {}).unwrap();
+    assert_eq!(admin, Addr::unchecked("bob"));
+    let pending_admin: Option<PendingAdmin> =
+        app.query(dex.clone(), &QueryMsg::PendingAdmin {}).unwrap();
+    assert_eq!(pending_admin, None);
+}
+
+#[test]
+fn test_admin_proposal_expiry_and_cancel() {
+    let mut app = MockApp::new(&[]);
+    let dex = create_dex!(app, Percentage::new(0));
+
+    // already expired
+    let now = app.block_time().seconds();
+    app.execute(
+        Addr::unchecked("alice"),
+        Addr::unchecked(dex.clone()),
+        &ExecuteMsg::ProposeAdmin {
+            new_admin: "bob".to_string(),
+            expires: Some(Expiration::AtTime(Timestamp::from_seconds(now))),
+        },
+        &[],
+    )
+    .unwrap_err();
+
+    app.execute(
+        Addr::unchecked("alice"),
+        Addr::unchecked(dex.clone()),
+        &ExecuteMsg::ProposeAdmin {
+            new_admin: "bob".to_string(),
+            expires: Some(Expiration::AtTime(Timestamp::from_seconds(now + 100))),
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.increase_block_time(100);
+
+    app.execute(
+        Addr::unchecked("bob"),
+        Addr::unchecked(dex.clone()),
+        &ExecuteMsg::AcceptAdmin {},
+        &[],
+    )
+    .unwrap_err();
+
+    // only the admin can cancel
+    app.execute(
+        Addr::unchecked("bob"),
+        Addr::unchecked(dex.clone()),
+        &ExecuteMsg::CancelAdminProposal {},
+        &[],
+    )
+    .unwrap_err();
+
+    app.execute(
+        Addr::unchecked("alice"),
+        Addr::unchecked(dex.clone()),
+        &ExecuteMsg::CancelAdminProposal {},
+        &[],
+    )
+    .unwrap();
+
+    let pending_admin: Option<PendingAdmin> =
+        app.query(dex.clone(), &QueryMsg::PendingAdmin {}).unwrap();
+    assert_eq!(pending_admin, None);
+
+    // nothing left to cancel or accept
+    app.execute(
+        Addr::unchecked("alice"),
+        Addr::unchecked(dex.clone()),
+        &ExecuteMsg::CancelAdminProposal {},
+        &[],
+    )
+    .unwrap_err();
+    app.execute(
+        Addr::unchecked("bob"),
+        Addr::unchecked(dex.clone()),
+        &ExecuteMsg::AcceptAdmin {},
+        &[],
+    )
+    .unwrap_err();
+
+    let admin: Addr = app.query(dex.clone(), &QueryMsg::Admin {}).unwrap();
+    assert_eq!(admin, Addr::unchecked("alice"));
+}
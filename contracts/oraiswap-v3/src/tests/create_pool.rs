@@ -336,3 +336,65 @@ fn test_create_many_pools_success() {
         assert!(result.is_ok());
     }
 }
+
+#[test]
+fn test_create_pool_with_sqrt_price() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let other_fee_tier = FeeTier::new(Percentage::from_scale(1, 2), 10).unwrap();
+    add_fee_tier!(app, dex, fee_tier, "alice").unwrap();
+    add_fee_tier!(app, dex, other_fee_tier, "alice").unwrap();
+
+    // the price sits between two initializable ticks
+    let init_sqrt_price = calculate_sqrt_price(15).unwrap();
+    let result = app
+        .create_pool_with_sqrt_price(
+            "alice",
+            dex.as_str(),
+            token_x.as_str(),
+            token_y.as_str(),
+            fee_tier,
+            init_sqrt_price,
+        )
+        .unwrap();
+
+    let init_tick = result
+        .events
+        .iter()
+        .flat_map(|event| event.attributes.iter())
+        .find(|attr| attr.key == "init_tick")
+        .unwrap();
+    assert_eq!(init_tick.value, "10");
+
+    let pool = get_pool!(app, dex, token_x, token_y, fee_tier).unwrap();
+    assert_eq!(pool.current_tick_index, 10);
+    assert_eq!(pool.sqrt_price, init_sqrt_price);
+
+    // negative ticks are rounded down as well
+    let init_sqrt_price = calculate_sqrt_price(-15).unwrap();
+    app.create_pool_with_sqrt_price(
+        "alice",
+        dex.as_str(),
+        token_x.as_str(),
+        token_y.as_str(),
+        other_fee_tier,
+        init_sqrt_price,
+    )
+    .unwrap();
+
+    let pool = get_pool!(app, dex, token_x, token_y, other_fee_tier).unwrap();
+    assert_eq!(pool.current_tick_index, -20);
+
+    // out of range price
+    app.create_pool_with_sqrt_price(
+        "alice",
+        dex.as_str(),
+        token_x.as_str(),
+        token_y.as_str(),
+        fee_tier,
+        SqrtPrice::new(0),
+    )
+    .unwrap_err();
+}
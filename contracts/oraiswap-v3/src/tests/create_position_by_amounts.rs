@@ -0,0 +1,341 @@
+use cosmwasm_std::{coin, Addr};
+use decimal::{Decimal, Factories};
+
+use crate::{
+    calculate_amount_delta,
+    liquidity::Liquidity,
+    logic::get_liquidity,
+    msg::ExecuteMsg,
+    percentage::Percentage,
+    sqrt_price::calculate_sqrt_price,
+    tests::helper::{macros::*, MockApp},
+    token_amount::TokenAmount,
+    FeeTier, PoolKey,
+};
+
+#[test]
+fn test_create_position_by_amounts() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+    init_basic_pool!(app, dex, token_x, token_y);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x.to_string(), token_y.to_string(), fee_tier).unwrap();
+
+    let mint_amount = 10u128.pow(10);
+    approve!(app, token_x, dex, mint_amount, "alice").unwrap();
+    approve!(app, token_y, dex, mint_amount, "alice").unwrap();
+
+    let pool = get_pool!(app, dex, token_x, token_y, fee_tier).unwrap();
+    let amount_x_desired = TokenAmount(500);
+    let amount_y_desired = TokenAmount(2000);
+    let expected = get_liquidity(
+        amount_x_desired,
+        amount_y_desired,
+        -20,
+        10,
+        pool.sqrt_price,
+        false,
+    )
+    .unwrap();
+
+    let user_x_before = balance_of!(app, token_x, "alice");
+    let user_y_before = balance_of!(app, token_y, "alice");
+
+    create_position_by_amounts!(
+        app,
+        dex,
+        pool_key,
+        -20,
+        10,
+        amount_x_desired,
+        amount_y_desired,
+        TokenAmount(0),
+        TokenAmount(0),
+        "alice"
+    )
+    .unwrap();
+
+    let positions = get_all_positions!(app, dex, "alice");
+    assert_eq!(positions.len(), 1);
+    let position = get_position!(app, dex, 0, "alice").unwrap();
+    assert_eq!(position.liquidity, expected.l);
+    assert_eq!(position.lower_tick_index, -20);
+    assert_eq!(position.upper_tick_index, 10);
+
+    // only the required amounts are pulled from the user
+    let dex_x = balance_of!(app, token_x, dex);
+    let dex_y = balance_of!(app, token_y, dex);
+    assert!(dex_y < amount_y_desired.get());
+    assert_eq!(user_x_before - balance_of!(app, token_x, "alice"), dex_x);
+    assert_eq!(user_y_before - balance_of!(app, token_y, "alice"), dex_y);
+}
+
+#[test]
+fn test_create_position_by_amounts_on_rounding_boundary() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+    init_basic_pool!(app, dex, token_x, token_y);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x.to_string(), token_y.to_string(), fee_tier).unwrap();
+
+    let mint_amount = 10u128.pow(10);
+    approve!(app, token_x, dex, mint_amount, "alice").unwrap();
+    approve!(app, token_y, dex, mint_amount, "alice").unwrap();
+
+    // the desired amounts are exactly the rounded up amounts of a liquidity
+    let pool = get_pool!(app, dex, token_x, token_y, fee_tier).unwrap();
+    let liquidity = Liquidity::from_integer(1_000_000);
+    let (amount_x, amount_y, _) = calculate_amount_delta(
+        pool.current_tick_index,
+        pool.sqrt_price,
+        liquidity,
+        true,
+        10,
+        -20,
+    )
+    .unwrap();
+
+    for (index, (amount_x_desired, amount_y_desired)) in [
+        (amount_x, amount_y),
+        (amount_x, TokenAmount(mint_amount / 2)),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        let user_x_before = balance_of!(app, token_x, "alice");
+        let user_y_before = balance_of!(app, token_y, "alice");
+
+        create_position_by_amounts!(
+            app,
+            dex,
+            pool_key,
+            -20,
+            10,
+            amount_x_desired,
+            amount_y_desired,
+            TokenAmount(0),
+            TokenAmount(0),
+            "alice"
+        )
+        .unwrap();
+
+        let position = get_position!(app, dex, index as u32, "alice").unwrap();
+        assert!(position.liquidity >= liquidity);
+
+        // never more than the desired amounts is pulled
+        assert!(user_x_before - balance_of!(app, token_x, "alice") <= amount_x_desired.get());
+        assert!(user_y_before - balance_of!(app, token_y, "alice") <= amount_y_desired.get());
+    }
+}
+
+#[test]
+fn test_create_position_by_amounts_on_range_bounds() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+    init_basic_pool!(app, dex, token_x, token_y);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x.to_string(), token_y.to_string(), fee_tier).unwrap();
+
+    let mint_amount = 10u128.pow(10);
+    approve!(app, token_x, dex, mint_amount, "alice").unwrap();
+    approve!(app, token_y, dex, mint_amount, "alice").unwrap();
+
+    let amount_x_desired = TokenAmount(500);
+    let amount_y_desired = TokenAmount(2000);
+
+    // the pool price sits on the lower tick, only token x is taken
+    let user_x_before = balance_of!(app, token_x, "alice");
+    let user_y_before = balance_of!(app, token_y, "alice");
+    create_position_by_amounts!(
+        app,
+        dex,
+        pool_key,
+        0,
+        10,
+        amount_x_desired,
+        amount_y_desired,
+        TokenAmount(0),
+        TokenAmount(0),
+        "alice"
+    )
+    .unwrap();
+
+    let position = get_position!(app, dex, 0, "alice").unwrap();
+    assert!(position.liquidity > Liquidity::new(0));
+    let spent_x = user_x_before - balance_of!(app, token_x, "alice");
+    assert!(spent_x > 0 && spent_x <= amount_x_desired.get());
+    assert_eq!(balance_of!(app, token_y, "alice"), user_y_before);
+
+    // the pool price sits on the upper tick, only token y is taken
+    let user_x_before = balance_of!(app, token_x, "alice");
+    let user_y_before = balance_of!(app, token_y, "alice");
+    create_position_by_amounts!(
+        app,
+        dex,
+        pool_key,
+        -20,
+        0,
+        amount_x_desired,
+        amount_y_desired,
+        TokenAmount(0),
+        TokenAmount(0),
+        "alice"
+    )
+    .unwrap();
+
+    let position = get_position!(app, dex, 1, "alice").unwrap();
+    assert!(position.liquidity > Liquidity::new(0));
+    assert_eq!(balance_of!(app, token_x, "alice"), user_x_before);
+    let spent_y = user_y_before - balance_of!(app, token_y, "alice");
+    assert!(spent_y > 0 && spent_y <= amount_y_desired.get());
+}
+
+#[test]
+fn test_create_position_by_amounts_refunds_native_funds() {
+    let denom = "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2";
+    let initial_amount = 10u128.pow(10);
+    let mut app = MockApp::new(&[(
+        "alice",
+        &[coin(initial_amount, denom), coin(initial_amount, "orai")],
+    )]);
+    let token = app.create_token("alice", "token", initial_amount);
+    let dex = create_dex!(app, Percentage::from_scale(1, 2));
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    add_fee_tier!(app, dex, fee_tier, "alice").unwrap();
+    let init_sqrt_price = calculate_sqrt_price(0).unwrap();
+    create_pool!(
+        app,
+        dex,
+        token,
+        denom,
+        fee_tier,
+        init_sqrt_price,
+        0,
+        "alice"
+    )
+    .unwrap();
+    approve!(app, token, dex, initial_amount, "alice").unwrap();
+
+    let pool_key = PoolKey::new(token.to_string(), denom.to_string(), fee_tier).unwrap();
+    assert_eq!(pool_key.token_y, denom);
+
+    // the range is above the price, so the native token y is not required
+    let msg = ExecuteMsg::CreatePositionByAmounts {
+        pool_key: pool_key.clone(),
+        lower_tick: 10,
+        upper_tick: 20,
+        amount_x_desired: TokenAmount(500),
+        amount_y_desired: TokenAmount(500),
+        amount_x_min: TokenAmount(0),
+        amount_y_min: TokenAmount(0),
+    };
+    app.execute(
+        Addr::unchecked("alice"),
+        dex.clone(),
+        &msg,
+        &[coin(1000, denom)],
+    )
+    .unwrap();
+
+    let position = get_position!(app, dex, 0, "alice").unwrap();
+    assert!(position.liquidity > Liquidity::new(0));
+    assert_eq!(
+        app.query_balance(Addr::unchecked("alice"), denom.to_string())
+            .unwrap()
+            .u128(),
+        initial_amount
+    );
+    assert!(app
+        .query_balance(dex.clone(), denom.to_string())
+        .unwrap()
+        .is_zero());
+
+    // funds other than the pool tokens are rejected
+    app.execute(
+        Addr::unchecked("alice"),
+        dex.clone(),
+        &msg,
+        &[coin(1000, "orai")],
+    )
+    .unwrap_err();
+}
+
+#[test]
+fn test_create_position_by_amounts_under_minimum() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+    init_basic_pool!(app, dex, token_x, token_y);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x.to_string(), token_y.to_string(), fee_tier).unwrap();
+
+    let mint_amount = 10u128.pow(10);
+    approve!(app, token_x, dex, mint_amount, "alice").unwrap();
+    approve!(app, token_y, dex, mint_amount, "alice").unwrap();
+
+    let err = create_position_by_amounts!(
+        app,
+        dex,
+        pool_key,
+        -20,
+        10,
+        TokenAmount(500),
+        TokenAmount(2000),
+        TokenAmount(0),
+        TokenAmount(2000),
+        "alice"
+    )
+    .unwrap_err();
+    assert!(err.contains("error executing WasmMsg"));
+
+    let positions = get_all_positions!(app, dex, "alice");
+    assert_eq!(positions.len(), 0);
+}
+
+#[test]
+fn test_create_position_by_amounts_invalid_input() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+    init_basic_pool!(app, dex, token_x, token_y);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x.to_string(), token_y.to_string(), fee_tier).unwrap();
+
+    let mint_amount = 10u128.pow(10);
+    approve!(app, token_x, dex, mint_amount, "alice").unwrap();
+    approve!(app, token_y, dex, mint_amount, "alice").unwrap();
+
+    // zero amounts produce zero liquidity
+    create_position_by_amounts!(
+        app,
+        dex,
+        pool_key,
+        -20,
+        10,
+        TokenAmount(0),
+        TokenAmount(0),
+        TokenAmount(0),
+        TokenAmount(0),
+        "alice"
+    )
+    .unwrap_err();
+
+    // inverted ticks
+    create_position_by_amounts!(
+        app,
+        dex,
+        pool_key,
+        10,
+        -20,
+        TokenAmount(500),
+        TokenAmount(1000),
+        TokenAmount(0),
+        TokenAmount(0),
+        "alice"
+    )
+    .unwrap_err();
+}
@@ -0,0 +1,181 @@
+use decimal::{Decimal, Factories};
+
+use crate::{
+    liquidity::Liquidity,
+    percentage::Percentage,
+    tests::helper::{macros::*, MockApp},
+    token_amount::TokenAmount,
+    FeeTier, PoolKey,
+};
+
+#[test]
+fn test_decrease_liquidity() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+    init_basic_pool!(app, dex, token_x, token_y);
+    init_basic_position!(app, dex, token_x, token_y);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x.to_string(), token_y.to_string(), fee_tier).unwrap();
+
+    let position_before = get_position!(app, dex, 0, "alice").unwrap();
+    let withdrawn_liquidity = Liquidity::from_integer(500000);
+
+    let user_x_before = balance_of!(app, token_x, "alice");
+    let user_y_before = balance_of!(app, token_y, "alice");
+    let dex_x_before = balance_of!(app, token_x, dex);
+    let dex_y_before = balance_of!(app, token_y, dex);
+
+    decrease_liquidity!(
+        app,
+        dex,
+        0,
+        withdrawn_liquidity,
+        TokenAmount(0),
+        TokenAmount(0),
+        "alice"
+    )
+    .unwrap();
+
+    let position_after = get_position!(app, dex, 0, "alice").unwrap();
+    assert_eq!(
+        position_after.liquidity,
+        position_before.liquidity - withdrawn_liquidity
+    );
+
+    let pool = get_pool!(app, dex, token_x, token_y, fee_tier).unwrap();
+    assert_eq!(pool.liquidity, position_after.liquidity);
+
+    // ticks are still referenced by the remaining liquidity
+    assert!(is_tick_initialized!(
+        app,
+        dex,
+        pool_key,
+        position_after.lower_tick_index
+    ));
+    assert!(is_tick_initialized!(
+        app,
+        dex,
+        pool_key,
+        position_after.upper_tick_index
+    ));
+    let lower_tick = get_tick!(app, dex, pool_key, position_after.lower_tick_index).unwrap();
+    assert_eq!(lower_tick.liquidity_gross, position_after.liquidity);
+
+    let user_x_after = balance_of!(app, token_x, "alice");
+    let user_y_after = balance_of!(app, token_y, "alice");
+    let dex_x_after = balance_of!(app, token_x, dex);
+    let dex_y_after = balance_of!(app, token_y, dex);
+    assert!(user_x_after > user_x_before);
+    assert!(user_y_after > user_y_before);
+    assert_eq!(user_x_after - user_x_before, dex_x_before - dex_x_after);
+    assert_eq!(user_y_after - user_y_before, dex_y_before - dex_y_after);
+}
+
+#[test]
+fn test_decrease_all_liquidity_removes_position() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+    init_basic_pool!(app, dex, token_x, token_y);
+    init_basic_position!(app, dex, token_x, token_y);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x.to_string(), token_y.to_string(), fee_tier).unwrap();
+
+    let position = get_position!(app, dex, 0, "alice").unwrap();
+
+    decrease_liquidity!(
+        app,
+        dex,
+        0,
+        position.liquidity,
+        TokenAmount(0),
+        TokenAmount(0),
+        "alice"
+    )
+    .unwrap();
+
+    let positions = get_all_positions!(app, dex, "alice");
+    assert_eq!(positions.len(), 0);
+
+    assert!(!is_tick_initialized!(
+        app,
+        dex,
+        pool_key,
+        position.lower_tick_index
+    ));
+    assert!(!is_tick_initialized!(
+        app,
+        dex,
+        pool_key,
+        position.upper_tick_index
+    ));
+
+    let pool = get_pool!(app, dex, token_x, token_y, fee_tier).unwrap();
+    assert_eq!(pool.liquidity, Liquidity::new(0));
+}
+
+#[test]
+fn test_decrease_liquidity_pays_accrued_fees() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+    init_basic_pool!(app, dex, token_x, token_y);
+    init_basic_position!(app, dex, token_x, token_y);
+    init_basic_swap!(app, dex, token_x, token_y);
+
+    decrease_liquidity!(
+        app,
+        dex,
+        0,
+        Liquidity::from_integer(1000),
+        TokenAmount(0),
+        TokenAmount(0),
+        "alice"
+    )
+    .unwrap();
+
+    let position = get_position!(app, dex, 0, "alice").unwrap();
+    assert_eq!(position.tokens_owed_x, TokenAmount(0));
+    assert_eq!(position.tokens_owed_y, TokenAmount(0));
+}
+
+#[test]
+fn test_decrease_liquidity_under_minimum_amount() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+    init_basic_pool!(app, dex, token_x, token_y);
+    init_basic_position!(app, dex, token_x, token_y);
+
+    decrease_liquidity!(
+        app,
+        dex,
+        0,
+        Liquidity::from_integer(500000),
+        TokenAmount(10u128.pow(10)),
+        TokenAmount(0),
+        "alice"
+    )
+    .unwrap_err();
+
+    decrease_liquidity!(
+        app,
+        dex,
+        0,
+        Liquidity::from_integer(2000000),
+        TokenAmount(0),
+        TokenAmount(0),
+        "alice"
+    )
+    .unwrap_err();
+
+    decrease_liquidity!(
+        app,
+        dex,
+        0,
+        Liquidity::from_integer(500000),
+        TokenAmount(0),
+        TokenAmount(0),
+        "bob"
+    )
+    .unwrap_err();
+}
@@ -0,0 +1,97 @@
+use decimal::*;
+
+use crate::{
+    percentage::Percentage,
+    sqrt_price::SqrtPrice,
+    tests::helper::{macros::*, MockApp},
+    token_amount::TokenAmount,
+    DynamicFeeConfig, FeeTier, PoolKey, MIN_SQRT_PRICE,
+};
+
+#[test]
+fn test_dynamic_fee_follows_volatility() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+    init_basic_pool!(app, dex, token_x, token_y);
+    init_basic_position!(app, dex, token_x, token_y);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x.to_string(), token_y.to_string(), fee_tier).unwrap();
+    let dynamic_fee = DynamicFeeConfig {
+        min_fee: Percentage::from_scale(1, 3),
+        max_fee: Percentage::from_scale(1, 2),
+        window: 60,
+        max_volatility: 10,
+    };
+
+    change_pool_dynamic_fee!(app, dex, pool_key, Some(dynamic_fee), "alice").unwrap();
+
+    let amount = TokenAmount(1000);
+    let slippage = SqrtPrice::new(MIN_SQRT_PRICE);
+
+    // a calm pool charges the lower bound
+    let quote = quote!(app, dex, pool_key, true, amount, true, slippage).unwrap();
+    assert_eq!(quote.swap_fee, dynamic_fee.min_fee);
+
+    mint!(app, token_x, "bob", 1000, "alice").unwrap();
+    approve!(app, token_x, dex, 1000, "bob").unwrap();
+    swap!(app, dex, pool_key, true, amount, true, slippage, "bob").unwrap();
+
+    let pool = get_pool!(app, dex, token_x, token_y, fee_tier).unwrap();
+    assert_eq!(
+        pool.volatility.current,
+        pool.current_tick_index.unsigned_abs()
+    );
+
+    // the price moved past `max_volatility` ticks
+    let quote = quote!(app, dex, pool_key, true, amount, true, slippage).unwrap();
+    assert_eq!(quote.swap_fee, dynamic_fee.max_fee);
+
+    // the previous window still counts
+    app.increase_block_time(60);
+    let quote = quote!(app, dex, pool_key, true, amount, true, slippage).unwrap();
+    assert_eq!(quote.swap_fee, dynamic_fee.max_fee);
+
+    app.increase_block_time(120);
+    let quote = quote!(app, dex, pool_key, true, amount, true, slippage).unwrap();
+    assert_eq!(quote.swap_fee, dynamic_fee.min_fee);
+
+    // disabling restores the fee tier fee
+    change_pool_dynamic_fee!(app, dex, pool_key, None, "alice").unwrap();
+    let quote = quote!(app, dex, pool_key, true, amount, true, slippage).unwrap();
+    assert_eq!(quote.swap_fee, fee_tier.fee);
+}
+
+#[test]
+fn test_change_pool_dynamic_fee_validation() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+    init_basic_pool!(app, dex, token_x, token_y);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x.to_string(), token_y.to_string(), fee_tier).unwrap();
+    let dynamic_fee = DynamicFeeConfig {
+        min_fee: Percentage::from_scale(1, 3),
+        max_fee: Percentage::from_scale(1, 2),
+        window: 60,
+        max_volatility: 10,
+    };
+
+    change_pool_dynamic_fee!(app, dex, pool_key, Some(dynamic_fee), "bob").unwrap_err();
+
+    let inverted = DynamicFeeConfig {
+        min_fee: dynamic_fee.max_fee,
+        max_fee: dynamic_fee.min_fee,
+        ..dynamic_fee
+    };
+    change_pool_dynamic_fee!(app, dex, pool_key, Some(inverted), "alice").unwrap_err();
+
+    let no_window = DynamicFeeConfig {
+        window: 0,
+        ..dynamic_fee
+    };
+    change_pool_dynamic_fee!(app, dex, pool_key, Some(no_window), "alice").unwrap_err();
+
+    let pool = get_pool!(app, dex, token_x, token_y, fee_tier).unwrap();
+    assert_eq!(pool.dynamic_fee, None);
+}
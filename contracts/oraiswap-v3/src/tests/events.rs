@@ -0,0 +1,239 @@
+use cosmwasm_std::Event;
+use decimal::*;
+
+use crate::{
+    interface::SwapHop,
+    liquidity::Liquidity,
+    percentage::Percentage,
+    sqrt_price::{calculate_sqrt_price, SqrtPrice},
+    tests::helper::{macros::*, MockApp},
+    token_amount::TokenAmount,
+    FeeTier, PoolKey, MIN_SQRT_PRICE,
+};
+
+fn events_of<'a>(events: &'a [Event], ty: &str) -> Vec<&'a Event> {
+    events.iter().filter(|event| event.ty == ty).collect()
+}
+
+fn attribute<'a>(event: &'a Event, key: &str) -> &'a str {
+    event
+        .attributes
+        .iter()
+        .find(|attr| attr.key == key)
+        .map(|attr| attr.value.as_str())
+        .unwrap()
+}
+
+#[test]
+fn test_swap_events() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+    init_basic_pool!(app, dex, token_x, token_y);
+    init_basic_position!(app, dex, token_x, token_y);
+    init_cross_position!(app, dex, token_x, token_y);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x.to_string(), token_y.to_string(), fee_tier).unwrap();
+
+    let amount = 1000;
+    mint!(app, token_x, "bob", amount, "alice").unwrap();
+    approve!(app, token_x, dex, amount, "bob").unwrap();
+
+    let pool_before = get_pool!(app, dex, token_x, token_y, fee_tier).unwrap();
+    let result = swap!(
+        app,
+        dex,
+        pool_key,
+        true,
+        TokenAmount(amount),
+        true,
+        SqrtPrice::new(MIN_SQRT_PRICE),
+        "bob"
+    )
+    .unwrap();
+    let pool_after = get_pool!(app, dex, token_x, token_y, fee_tier).unwrap();
+
+    let swaps = events_of(&result.events, "wasm-swap");
+    assert_eq!(swaps.len(), 1);
+    let swap = swaps[0];
+    assert_eq!(attribute(swap, "token_x"), pool_key.token_x.as_str());
+    assert_eq!(attribute(swap, "token_y"), pool_key.token_y.as_str());
+    assert_eq!(attribute(swap, "sender"), "bob");
+    assert_eq!(attribute(swap, "recipient"), "bob");
+    assert_eq!(attribute(swap, "x_to_y"), "true");
+    assert_eq!(attribute(swap, "amount_in"), "1000");
+    assert_eq!(attribute(swap, "amount_out"), "990");
+    assert_eq!(attribute(swap, "protocol_fee"), "2");
+    assert_eq!(
+        attribute(swap, "sqrt_price_before"),
+        pool_before.sqrt_price.to_string()
+    );
+    assert_eq!(
+        attribute(swap, "sqrt_price_after"),
+        pool_after.sqrt_price.to_string()
+    );
+    assert_eq!(attribute(swap, "current_tick"), "-20");
+    assert_eq!(
+        attribute(swap, "liquidity"),
+        pool_after.liquidity.to_string()
+    );
+
+    // the swap entered the cross position at tick -10
+    let crosses = events_of(&result.events, "wasm-cross_tick");
+    assert_eq!(crosses.len(), 1);
+    assert_eq!(attribute(crosses[0], "index"), "-10");
+    assert_eq!(
+        attribute(crosses[0], "liquidity_change"),
+        Liquidity::from_integer(1000000).to_string()
+    );
+}
+
+#[test]
+fn test_swap_without_cross_emits_no_cross_tick() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+    init_basic_pool!(app, dex, token_x, token_y);
+    init_basic_position!(app, dex, token_x, token_y);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x.to_string(), token_y.to_string(), fee_tier).unwrap();
+
+    let amount = 10;
+    mint!(app, token_x, "bob", amount, "alice").unwrap();
+    approve!(app, token_x, dex, amount, "bob").unwrap();
+
+    let result = swap!(
+        app,
+        dex,
+        pool_key,
+        true,
+        TokenAmount(amount),
+        true,
+        SqrtPrice::new(MIN_SQRT_PRICE),
+        "bob"
+    )
+    .unwrap();
+
+    assert_eq!(events_of(&result.events, "wasm-swap").len(), 1);
+    assert!(events_of(&result.events, "wasm-cross_tick").is_empty());
+}
+
+#[test]
+fn test_swap_route_events() {
+    let initial_amount = 10u128.pow(10);
+    let mut app = MockApp::new(&[]);
+    let dex = create_dex!(app, Percentage::from_scale(6, 3));
+    let (token_x, token_y, token_z) =
+        create_3_tokens!(app, initial_amount, initial_amount, initial_amount);
+
+    approve!(app, token_x, dex, initial_amount, "alice").unwrap();
+    approve!(app, token_y, dex, initial_amount, "alice").unwrap();
+    approve!(app, token_z, dex, initial_amount, "alice").unwrap();
+
+    let amount = 1000;
+    mint!(app, token_x, "bob", amount, "alice").unwrap();
+    approve!(app, token_x, dex, amount, "bob").unwrap();
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 1).unwrap();
+    add_fee_tier!(app, dex, fee_tier, "alice").unwrap();
+
+    let init_sqrt_price = calculate_sqrt_price(0).unwrap();
+    let mut swaps = vec![];
+    for (token_0, token_1) in [(&token_x, &token_y), (&token_y, &token_z)] {
+        create_pool!(
+            app,
+            dex,
+            token_0,
+            token_1,
+            fee_tier,
+            init_sqrt_price,
+            0,
+            "alice"
+        )
+        .unwrap();
+        let pool_key = PoolKey::new(token_0.to_string(), token_1.to_string(), fee_tier).unwrap();
+        create_position!(
+            app,
+            dex,
+            pool_key,
+            -1,
+            1,
+            Liquidity::new(2u128.pow(63) - 1),
+            init_sqrt_price,
+            init_sqrt_price,
+            "alice"
+        )
+        .unwrap();
+        swaps.push(SwapHop {
+            pool_key,
+            x_to_y: true,
+        });
+    }
+
+    let amount_in = TokenAmount(amount);
+    let expected_amount_out = quote_route!(app, dex, amount_in, swaps.clone()).unwrap();
+    let result = swap_route!(
+        app,
+        dex,
+        amount_in,
+        expected_amount_out,
+        Percentage::new(0),
+        swaps,
+        "bob"
+    )
+    .unwrap();
+
+    // the output of the first hop stays in the contract
+    let swap_events = events_of(&result.events, "wasm-swap");
+    assert_eq!(swap_events.len(), 2);
+    assert_eq!(attribute(swap_events[0], "sender"), "bob");
+    assert_eq!(attribute(swap_events[0], "recipient"), dex.as_str());
+    assert_eq!(attribute(swap_events[1], "sender"), "bob");
+    assert_eq!(attribute(swap_events[1], "recipient"), "bob");
+}
+
+#[test]
+fn test_admin_action_attributes() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let result = add_fee_tier!(app, dex, fee_tier, "alice").unwrap();
+    let wasm = events_of(&result.events, "wasm")[0];
+    assert_eq!(attribute(wasm, "action"), "add_fee_tier");
+    assert_eq!(attribute(wasm, "fee"), fee_tier.fee.to_string());
+    assert_eq!(attribute(wasm, "tick_spacing"), "10");
+
+    let init_sqrt_price = calculate_sqrt_price(0).unwrap();
+    let result = create_pool!(
+        app,
+        dex,
+        token_x,
+        token_y,
+        fee_tier,
+        init_sqrt_price,
+        0,
+        "alice"
+    )
+    .unwrap();
+    let wasm = events_of(&result.events, "wasm")[0];
+    assert_eq!(attribute(wasm, "action"), "create_pool");
+    let pool_key = PoolKey::new(token_x.to_string(), token_y.to_string(), fee_tier).unwrap();
+    assert_eq!(attribute(wasm, "token_x"), pool_key.token_x.as_str());
+    assert_eq!(attribute(wasm, "token_y"), pool_key.token_y.as_str());
+    assert_eq!(attribute(wasm, "sender"), "alice");
+    assert_eq!(
+        attribute(wasm, "init_sqrt_price"),
+        init_sqrt_price.to_string()
+    );
+    assert_eq!(attribute(wasm, "init_tick"), "0");
+
+    init_basic_position!(app, dex, token_x, token_y);
+    let result = transfer_position!(app, dex, 0, "bob", "alice").unwrap();
+    let wasm = events_of(&result.events, "wasm")[0];
+    assert_eq!(attribute(wasm, "action"), "transfer_position");
+    assert_eq!(attribute(wasm, "sender"), "alice");
+    assert_eq!(attribute(wasm, "receiver"), "bob");
+    assert_eq!(attribute(wasm, "lower_tick"), "-20");
+    assert_eq!(attribute(wasm, "upper_tick"), "10");
+}
@@ -0,0 +1,177 @@
+use decimal::*;
+
+use crate::{
+    interface::SwapHop,
+    liquidity::Liquidity,
+    percentage::Percentage,
+    sqrt_price::calculate_sqrt_price,
+    tests::helper::{macros::*, MockApp},
+    token_amount::TokenAmount,
+    FeeTier, PoolKey,
+};
+
+#[test]
+fn test_find_best_route() {
+    let protocol_fee = Percentage::from_scale(6, 3);
+    let initial_amount = 10u128.pow(10);
+    let mut app = MockApp::new(&[]);
+    let dex = create_dex!(app, protocol_fee);
+
+    let (token_x, token_y, token_z) =
+        create_3_tokens!(app, initial_amount, initial_amount, initial_amount);
+
+    approve!(app, token_x, dex, initial_amount, "alice").unwrap();
+    approve!(app, token_y, dex, initial_amount, "alice").unwrap();
+    approve!(app, token_z, dex, initial_amount, "alice").unwrap();
+
+    let fee_tier = FeeTier::new(protocol_fee, 1).unwrap();
+    add_fee_tier!(app, dex, fee_tier, "alice").unwrap();
+
+    let init_tick = 0;
+    let init_sqrt_price = calculate_sqrt_price(init_tick).unwrap();
+    let liquidity_delta = Liquidity::new(2u128.pow(63) - 1);
+    for (token_0, token_1) in [
+        (&token_x, &token_y),
+        (&token_y, &token_z),
+        (&token_x, &token_z),
+    ] {
+        create_pool!(
+            app,
+            dex,
+            token_0,
+            token_1,
+            fee_tier,
+            init_sqrt_price,
+            init_tick,
+            "alice"
+        )
+        .unwrap();
+        let pool_key = PoolKey::new(token_0.to_string(), token_1.to_string(), fee_tier).unwrap();
+        create_position!(
+            app,
+            dex,
+            pool_key,
+            -1,
+            1,
+            liquidity_delta,
+            init_sqrt_price,
+            init_sqrt_price,
+            "alice"
+        )
+        .unwrap();
+    }
+
+    let pool_key_xy = PoolKey::new(token_x.to_string(), token_y.to_string(), fee_tier).unwrap();
+    let pool_key_yz = PoolKey::new(token_y.to_string(), token_z.to_string(), fee_tier).unwrap();
+    let pool_key_xz = PoolKey::new(token_x.to_string(), token_z.to_string(), fee_tier).unwrap();
+
+    let amount = TokenAmount(1000);
+    let routes = app
+        .find_best_route(
+            dex.as_str(),
+            token_x.as_str(),
+            token_z.as_str(),
+            amount,
+            3,
+            None,
+        )
+        .unwrap();
+
+    // the direct pool pays a single fee and comes first
+    assert_eq!(routes.len(), 2);
+    assert_eq!(
+        routes[0].swaps,
+        vec![SwapHop {
+            pool_key: pool_key_xz.clone(),
+            x_to_y: pool_key_xz.token_x == token_x.as_str(),
+        }]
+    );
+    let two_hops = vec![
+        SwapHop {
+            pool_key: pool_key_xy.clone(),
+            x_to_y: pool_key_xy.token_x == token_x.as_str(),
+        },
+        SwapHop {
+            pool_key: pool_key_yz.clone(),
+            x_to_y: pool_key_yz.token_x == token_y.as_str(),
+        },
+    ];
+    assert_eq!(routes[1].swaps, two_hops);
+    assert!(routes[0].amount_out > routes[1].amount_out);
+
+    // amounts match the route quotes
+    for route in &routes {
+        let amount_out = quote_route!(app, dex, amount, route.swaps.clone()).unwrap();
+        assert_eq!(route.amount_out, amount_out);
+    }
+
+    // the limit keeps the best routes
+    let best_routes = app
+        .find_best_route(
+            dex.as_str(),
+            token_x.as_str(),
+            token_z.as_str(),
+            amount,
+            3,
+            Some(1),
+        )
+        .unwrap();
+    assert_eq!(best_routes, routes[..1].to_vec());
+
+    // the hop limit leaves the longer route out
+    let routes = app
+        .find_best_route(
+            dex.as_str(),
+            token_x.as_str(),
+            token_z.as_str(),
+            amount,
+            1,
+            None,
+        )
+        .unwrap();
+    assert_eq!(routes.len(), 1);
+    assert_eq!(routes[0].swaps[0].pool_key, pool_key_xz);
+
+    app.find_best_route(
+        dex.as_str(),
+        token_x.as_str(),
+        token_x.as_str(),
+        amount,
+        3,
+        None,
+    )
+    .unwrap_err();
+}
+
+#[test]
+fn test_find_best_route_skips_pools_without_liquidity() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+    init_basic_pool!(app, dex, token_x, token_y);
+
+    let routes = app
+        .find_best_route(
+            dex.as_str(),
+            token_x.as_str(),
+            token_y.as_str(),
+            TokenAmount(1000),
+            3,
+            None,
+        )
+        .unwrap();
+    assert!(routes.is_empty());
+
+    init_basic_position!(app, dex, token_x, token_y);
+    let routes = app
+        .find_best_route(
+            dex.as_str(),
+            token_x.as_str(),
+            token_y.as_str(),
+            TokenAmount(100),
+            3,
+            None,
+        )
+        .unwrap();
+    assert_eq!(routes.len(), 1);
+    assert!(!routes[0].amount_out.is_zero());
+}
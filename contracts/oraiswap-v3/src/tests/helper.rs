@@ -1,9 +1,10 @@
 use cosmwasm_schema::serde::de::DeserializeOwned;
 use cosmwasm_schema::serde::Serialize;
 use cosmwasm_std::{
-    Addr, AllBalanceResponse, BalanceResponse, BankQuery, Coin, Empty, Event, QuerierWrapper,
-    QueryRequest, StdResult, Uint128,
+    Addr, AllBalanceResponse, BalanceResponse, BankQuery, Binary, Coin, Empty, Event,
+    QuerierWrapper, QueryRequest, StdResult, Timestamp, Uint128,
 };
+use cw2::{query_contract_info, ContractVersion};
 use cw20::TokenInfoResponse;
 use decimal::num_traits::Zero;
 use std::collections::HashMap;
@@ -12,14 +13,19 @@ use cw_multi_test::{next_block, App, AppResponse, Contract, Executor};
 
 use crate::{
     interface::SwapHop,
-    interface::{PoolWithPoolKey, QuoteResult},
+    interface::{
+        Asset, AssetInfo, ObserveResult, PoolPosition, PoolWithPoolKey, QuoteResult, RouteQuote,
+        SwapSplitQuote,
+    },
     liquidity::Liquidity,
     msg::{self},
     percentage::Percentage,
+    seconds_per_liquidity::SecondsPerLiquidity,
     sqrt_price::SqrtPrice,
     state::MAX_LIMIT,
     token_amount::TokenAmount,
-    FeeTier, LiquidityTick, Pool, PoolKey, Position, Tick,
+    DynamicFeeConfig, FeeTier, LiquidityTick, PauseScope, Pool, PoolCreationFee,
+    PoolCreationPolicy, PoolKey, Position, Tick,
 };
 
 #[macro_export]
@@ -58,7 +64,9 @@ impl MockApp {
 
         // default token is cw20_base
         let token_id = app.store_code(Box::new(create_entry_points_testing!(cw20_base)));
-        let dex_id = app.store_code(Box::new(create_entry_points_testing!(crate)));
+        let dex_id = app.store_code(Box::new(
+            create_entry_points_testing!(crate).with_migrate(crate::contract::migrate),
+        ));
 
         Self {
             app,
@@ -88,7 +96,14 @@ impl MockApp {
     ) -> Result<Addr, String> {
         let contract_addr = self
             .app
-            .instantiate_contract(code_id, sender, init_msg, send_funds, label, None)
+            .instantiate_contract(
+                code_id,
+                sender.clone(),
+                init_msg,
+                send_funds,
+                label,
+                Some(sender.to_string()),
+            )
             .map_err(|err| err.to_string())?;
         self.app.update_block(next_block);
         Ok(contract_addr)
@@ -111,6 +126,38 @@ impl MockApp {
         Ok(response)
     }
 
+    pub fn migrate<T: Serialize>(
+        &mut self,
+        sender: Addr,
+        contract_addr: Addr,
+        msg: &T,
+        new_code_id: u64,
+    ) -> Result<AppResponse, String> {
+        let response = self
+            .app
+            .migrate_contract(sender, contract_addr, msg, new_code_id)
+            .map_err(|err| err.to_string())?;
+
+        self.app.update_block(next_block);
+
+        Ok(response)
+    }
+
+    pub fn query_contract_version(&self, contract_addr: &str) -> StdResult<ContractVersion> {
+        query_contract_info(&self.app.wrap(), contract_addr)
+    }
+
+    pub fn block_time(&self) -> Timestamp {
+        self.app.block_info().time
+    }
+
+    pub fn increase_block_time(&mut self, seconds: u64) {
+        self.app.update_block(|block| {
+            block.time = block.time.plus_seconds(seconds);
+            block.height += 1;
+        });
+    }
+
     pub fn query<T: DeserializeOwned, U: Serialize>(
         &self,
         contract_addr: Addr,
@@ -317,6 +364,31 @@ impl MockApp {
         )
     }
 
+    pub fn send_token(
+        &mut self,
+        token: &str,
+        sender: &str,
+        contract: &str,
+        amount: u128,
+        hook_msg: &msg::Cw20HookMsg,
+    ) -> Result<AppResponse, String> {
+        let token_addr = match self.token_map.get(token) {
+            Some(v) => v.to_owned(),
+            None => Addr::unchecked(token),
+        };
+
+        self.execute(
+            Addr::unchecked(sender),
+            token_addr,
+            &cw20::Cw20ExecuteMsg::Send {
+                contract: contract.to_string(),
+                amount: amount.into(),
+                msg: cosmwasm_std::to_binary(hook_msg).unwrap(),
+            },
+            &[],
+        )
+    }
+
     /// external method
 
     pub fn create_dex(&mut self, owner: &str, protocol_fee: Percentage) -> Result<Addr, String> {
@@ -329,6 +401,20 @@ impl MockApp {
         )
     }
 
+    pub fn migrate_dex(
+        &mut self,
+        sender: &str,
+        dex: &str,
+        msg: &msg::MigrateMsg,
+    ) -> Result<AppResponse, String> {
+        self.migrate(
+            Addr::unchecked(sender),
+            Addr::unchecked(dex),
+            msg,
+            self.dex_id,
+        )
+    }
+
     pub fn add_fee_tier(
         &mut self,
         sender: &str,
@@ -381,6 +467,82 @@ impl MockApp {
         )
     }
 
+    pub fn change_pool_creation_policy(
+        &mut self,
+        sender: &str,
+        dex: &str,
+        policy: PoolCreationPolicy,
+    ) -> Result<AppResponse, String> {
+        self.execute(
+            Addr::unchecked(sender),
+            Addr::unchecked(dex),
+            &msg::ExecuteMsg::ChangePoolCreationPolicy { policy },
+            &[],
+        )
+    }
+
+    pub fn change_pool_creation_fee(
+        &mut self,
+        sender: &str,
+        dex: &str,
+        fee: Option<PoolCreationFee>,
+    ) -> Result<AppResponse, String> {
+        self.execute(
+            Addr::unchecked(sender),
+            Addr::unchecked(dex),
+            &msg::ExecuteMsg::ChangePoolCreationFee { fee },
+            &[],
+        )
+    }
+
+    pub fn change_max_tick_cross(
+        &mut self,
+        sender: &str,
+        dex: &str,
+        max_tick_cross: Option<u32>,
+    ) -> Result<AppResponse, String> {
+        self.execute(
+            Addr::unchecked(sender),
+            Addr::unchecked(dex),
+            &msg::ExecuteMsg::ChangeMaxTickCross { max_tick_cross },
+            &[],
+        )
+    }
+
+    pub fn get_max_tick_cross(&self, dex: &str) -> StdResult<u32> {
+        self.query(Addr::unchecked(dex), &msg::QueryMsg::MaxTickCross {})
+    }
+
+    pub fn get_pool_creation_policy(&self, dex: &str) -> StdResult<PoolCreationPolicy> {
+        self.query(Addr::unchecked(dex), &msg::QueryMsg::PoolCreationPolicy {})
+    }
+
+    pub fn get_pool_creation_fee(&self, dex: &str) -> StdResult<Option<PoolCreationFee>> {
+        self.query(Addr::unchecked(dex), &msg::QueryMsg::PoolCreationFee {})
+    }
+
+    pub fn create_pool_with_sqrt_price(
+        &mut self,
+        sender: &str,
+        dex: &str,
+        token_x: &str,
+        token_y: &str,
+        fee_tier: FeeTier,
+        init_sqrt_price: SqrtPrice,
+    ) -> Result<AppResponse, String> {
+        self.execute(
+            Addr::unchecked(sender),
+            Addr::unchecked(dex),
+            &msg::ExecuteMsg::CreatePoolWithSqrtPrice {
+                token_0: Addr::unchecked(token_x).to_string(),
+                token_1: Addr::unchecked(token_y).to_string(),
+                fee_tier,
+                init_sqrt_price,
+            },
+            &[],
+        )
+    }
+
     pub fn withdraw_protocol_fee(
         &mut self,
         sender: &str,
@@ -397,6 +559,42 @@ impl MockApp {
         )
     }
 
+    pub fn change_pool_protocol_fee(
+        &mut self,
+        sender: &str,
+        dex: &str,
+        pool_key: &PoolKey,
+        protocol_fee: Option<Percentage>,
+    ) -> Result<AppResponse, String> {
+        self.execute(
+            Addr::unchecked(sender),
+            Addr::unchecked(dex),
+            &msg::ExecuteMsg::ChangePoolProtocolFee {
+                pool_key: pool_key.clone(),
+                protocol_fee,
+            },
+            &[],
+        )
+    }
+
+    pub fn change_pool_dynamic_fee(
+        &mut self,
+        sender: &str,
+        dex: &str,
+        pool_key: &PoolKey,
+        dynamic_fee: Option<DynamicFeeConfig>,
+    ) -> Result<AppResponse, String> {
+        self.execute(
+            Addr::unchecked(sender),
+            Addr::unchecked(dex),
+            &msg::ExecuteMsg::ChangePoolDynamicFee {
+                pool_key: pool_key.clone(),
+                dynamic_fee,
+            },
+            &[],
+        )
+    }
+
     pub fn change_fee_receiver(
         &mut self,
         sender: &str,
@@ -415,6 +613,58 @@ impl MockApp {
         )
     }
 
+    pub fn change_guardian(
+        &mut self,
+        sender: &str,
+        dex: &str,
+        guardian: Option<&str>,
+    ) -> Result<AppResponse, String> {
+        self.execute(
+            Addr::unchecked(sender),
+            Addr::unchecked(dex),
+            &msg::ExecuteMsg::ChangeGuardian {
+                guardian: guardian.map(|guardian| guardian.to_string()),
+            },
+            &[],
+        )
+    }
+
+    pub fn pause(
+        &mut self,
+        sender: &str,
+        dex: &str,
+        scope: PauseScope,
+    ) -> Result<AppResponse, String> {
+        self.execute(
+            Addr::unchecked(sender),
+            Addr::unchecked(dex),
+            &msg::ExecuteMsg::Pause { scope },
+            &[],
+        )
+    }
+
+    pub fn unpause(
+        &mut self,
+        sender: &str,
+        dex: &str,
+        scope: PauseScope,
+    ) -> Result<AppResponse, String> {
+        self.execute(
+            Addr::unchecked(sender),
+            Addr::unchecked(dex),
+            &msg::ExecuteMsg::Unpause { scope },
+            &[],
+        )
+    }
+
+    pub fn get_guardian(&self, dex: &str) -> StdResult<Option<Addr>> {
+        self.query(Addr::unchecked(dex), &msg::QueryMsg::Guardian {})
+    }
+
+    pub fn is_paused(&self, dex: &str, scope: PauseScope) -> StdResult<bool> {
+        self.query(Addr::unchecked(dex), &msg::QueryMsg::IsPaused { scope })
+    }
+
     pub fn create_position(
         &mut self,
         sender: &str,
@@ -441,107 +691,479 @@ impl MockApp {
         )
     }
 
-    pub fn transfer_position(
+    pub fn create_position_by_amounts(
+        &mut self,
+        sender: &str,
+        dex: &str,
+        pool_key: &PoolKey,
+        lower_tick: i32,
+        upper_tick: i32,
+        amount_x_desired: TokenAmount,
+        amount_y_desired: TokenAmount,
+        amount_x_min: TokenAmount,
+        amount_y_min: TokenAmount,
+    ) -> Result<AppResponse, String> {
+        self.execute(
+            Addr::unchecked(sender),
+            Addr::unchecked(dex),
+            &msg::ExecuteMsg::CreatePositionByAmounts {
+                pool_key: pool_key.clone(),
+                lower_tick,
+                upper_tick,
+                amount_x_desired,
+                amount_y_desired,
+                amount_x_min,
+                amount_y_min,
+            },
+            &[],
+        )
+    }
+
+    pub fn increase_liquidity(
+        &mut self,
+        sender: &str,
+        dex: &str,
+        index: u32,
+        liquidity_delta: Liquidity,
+        slippage_limit_lower: SqrtPrice,
+        slippage_limit_upper: SqrtPrice,
+    ) -> Result<AppResponse, String> {
+        self.execute(
+            Addr::unchecked(sender),
+            Addr::unchecked(dex),
+            &msg::ExecuteMsg::IncreaseLiquidity {
+                index,
+                liquidity_delta,
+                slippage_limit_lower,
+                slippage_limit_upper,
+            },
+            &[],
+        )
+    }
+
+    pub fn decrease_liquidity(
+        &mut self,
+        sender: &str,
+        dex: &str,
+        index: u32,
+        liquidity_delta: Liquidity,
+        min_amount_x: TokenAmount,
+        min_amount_y: TokenAmount,
+    ) -> Result<AppResponse, String> {
+        self.execute(
+            Addr::unchecked(sender),
+            Addr::unchecked(dex),
+            &msg::ExecuteMsg::DecreaseLiquidity {
+                index,
+                liquidity_delta,
+                min_amount_x,
+                min_amount_y,
+            },
+            &[],
+        )
+    }
+
+    pub fn transfer_position(
+        &mut self,
+        sender: &str,
+        dex: &str,
+        index: u32,
+        receiver: &str,
+    ) -> Result<AppResponse, String> {
+        self.execute(
+            Addr::unchecked(sender),
+            Addr::unchecked(dex),
+            &msg::ExecuteMsg::TransferPosition {
+                index,
+                receiver: receiver.to_string(),
+            },
+            &[],
+        )
+    }
+
+    pub fn remove_position(
+        &mut self,
+        sender: &str,
+        dex: &str,
+        index: u32,
+    ) -> Result<AppResponse, String> {
+        self.execute(
+            Addr::unchecked(sender),
+            Addr::unchecked(dex),
+            &msg::ExecuteMsg::RemovePosition { index },
+            &[],
+        )
+    }
+
+    pub fn swap_route(
+        &mut self,
+        sender: &str,
+        dex: &str,
+        amount_in: TokenAmount,
+        expected_amount_out: TokenAmount,
+        slippage: Percentage,
+        swaps: Vec<SwapHop>,
+    ) -> Result<AppResponse, String> {
+        self.execute(
+            Addr::unchecked(sender),
+            Addr::unchecked(dex),
+            &msg::ExecuteMsg::SwapRoute {
+                amount_in,
+                expected_amount_out,
+                slippage,
+                swaps,
+                recipient: None,
+                deadline: None,
+            },
+            &[],
+        )
+    }
+
+    pub fn swap_route_to(
+        &mut self,
+        sender: &str,
+        dex: &str,
+        amount_in: TokenAmount,
+        expected_amount_out: TokenAmount,
+        slippage: Percentage,
+        swaps: Vec<SwapHop>,
+        recipient: Option<&str>,
+        deadline: Option<Timestamp>,
+    ) -> Result<AppResponse, String> {
+        self.execute(
+            Addr::unchecked(sender),
+            Addr::unchecked(dex),
+            &msg::ExecuteMsg::SwapRoute {
+                amount_in,
+                expected_amount_out,
+                slippage,
+                swaps,
+                recipient: recipient.map(|r| r.to_string()),
+                deadline,
+            },
+            &[],
+        )
+    }
+
+    pub fn swap_route_exact_out(
+        &mut self,
+        sender: &str,
+        dex: &str,
+        amount_out: TokenAmount,
+        max_amount_in: TokenAmount,
+        swaps: Vec<SwapHop>,
+    ) -> Result<AppResponse, String> {
+        self.execute(
+            Addr::unchecked(sender),
+            Addr::unchecked(dex),
+            &msg::ExecuteMsg::SwapRouteExactOut {
+                amount_out,
+                max_amount_in,
+                swaps,
+                recipient: None,
+                deadline: None,
+            },
+            &[],
+        )
+    }
+
+    pub fn swap(
+        &mut self,
+        sender: &str,
+        dex: &str,
+        pool_key: &PoolKey,
+        x_to_y: bool,
+        amount: TokenAmount,
+        by_amount_in: bool,
+        sqrt_price_limit: SqrtPrice,
+    ) -> Result<AppResponse, String> {
+        self.execute(
+            Addr::unchecked(sender),
+            Addr::unchecked(dex),
+            &msg::ExecuteMsg::Swap {
+                pool_key: pool_key.clone(),
+                x_to_y,
+                amount,
+                by_amount_in,
+                sqrt_price_limit,
+                recipient: None,
+                deadline: None,
+                allow_partial: false,
+            },
+            &[],
+        )
+    }
+
+    pub fn swap_partial(
+        &mut self,
+        sender: &str,
+        dex: &str,
+        pool_key: &PoolKey,
+        x_to_y: bool,
+        amount: TokenAmount,
+        by_amount_in: bool,
+        sqrt_price_limit: SqrtPrice,
+    ) -> Result<AppResponse, String> {
+        self.execute(
+            Addr::unchecked(sender),
+            Addr::unchecked(dex),
+            &msg::ExecuteMsg::Swap {
+                pool_key: pool_key.clone(),
+                x_to_y,
+                amount,
+                by_amount_in,
+                sqrt_price_limit,
+                recipient: None,
+                deadline: None,
+                allow_partial: true,
+            },
+            &[],
+        )
+    }
+
+    pub fn swap_to(
+        &mut self,
+        sender: &str,
+        dex: &str,
+        pool_key: &PoolKey,
+        x_to_y: bool,
+        amount: TokenAmount,
+        by_amount_in: bool,
+        sqrt_price_limit: SqrtPrice,
+        recipient: Option<&str>,
+        deadline: Option<Timestamp>,
+    ) -> Result<AppResponse, String> {
+        self.execute(
+            Addr::unchecked(sender),
+            Addr::unchecked(dex),
+            &msg::ExecuteMsg::Swap {
+                pool_key: pool_key.clone(),
+                x_to_y,
+                amount,
+                by_amount_in,
+                sqrt_price_limit,
+                recipient: recipient.map(|r| r.to_string()),
+                deadline,
+                allow_partial: false,
+            },
+            &[],
+        )
+    }
+
+    pub fn claim_fee(
+        &mut self,
+        sender: &str,
+        dex: &str,
+        index: u32,
+    ) -> Result<AppResponse, String> {
+        self.execute(
+            Addr::unchecked(sender),
+            Addr::unchecked(dex),
+            &msg::ExecuteMsg::ClaimFee { index },
+            &[],
+        )
+    }
+
+    pub fn create_incentive(
+        &mut self,
+        sender: &str,
+        dex: &str,
+        pool_key: &PoolKey,
+        reward_token: &str,
+        reward_per_sec: TokenAmount,
+        start_timestamp: Option<u64>,
+        end_timestamp: u64,
+    ) -> Result<AppResponse, String> {
+        self.execute(
+            Addr::unchecked(sender),
+            Addr::unchecked(dex),
+            &msg::ExecuteMsg::CreateIncentive {
+                pool_key: pool_key.clone(),
+                reward_token: AssetInfo::Token {
+                    contract_addr: Addr::unchecked(reward_token),
+                },
+                reward_per_sec,
+                start_timestamp,
+                end_timestamp,
+            },
+            &[],
+        )
+    }
+
+    pub fn claim_incentives(
         &mut self,
         sender: &str,
         dex: &str,
         index: u32,
-        receiver: &str,
     ) -> Result<AppResponse, String> {
         self.execute(
             Addr::unchecked(sender),
             Addr::unchecked(dex),
-            &msg::ExecuteMsg::TransferPosition {
-                index,
-                receiver: receiver.to_string(),
-            },
+            &msg::ExecuteMsg::ClaimIncentives { index },
             &[],
         )
     }
 
-    pub fn remove_position(
+    pub fn withdraw_unallocated_incentive(
         &mut self,
         sender: &str,
         dex: &str,
-        index: u32,
+        pool_key: &PoolKey,
+        incentive_id: u64,
     ) -> Result<AppResponse, String> {
         self.execute(
             Addr::unchecked(sender),
             Addr::unchecked(dex),
-            &msg::ExecuteMsg::RemovePosition { index },
+            &msg::ExecuteMsg::WithdrawUnallocatedIncentive {
+                pool_key: pool_key.clone(),
+                incentive_id,
+            },
             &[],
         )
     }
 
-    pub fn swap_route(
+    pub fn position_incentives(
+        &self,
+        dex: &str,
+        owner_id: &str,
+        index: u32,
+    ) -> StdResult<Vec<Asset>> {
+        self.query(
+            Addr::unchecked(dex),
+            &msg::QueryMsg::PositionIncentives {
+                owner_id: Addr::unchecked(owner_id),
+                index,
+            },
+        )
+    }
+
+    pub fn quote_route(
         &mut self,
-        sender: &str,
         dex: &str,
         amount_in: TokenAmount,
-        expected_amount_out: TokenAmount,
-        slippage: Percentage,
         swaps: Vec<SwapHop>,
+    ) -> StdResult<TokenAmount> {
+        self.query(
+            Addr::unchecked(dex),
+            &msg::QueryMsg::QuoteRoute { amount_in, swaps },
+        )
+    }
+
+    pub fn swap_split(
+        &mut self,
+        sender: &str,
+        dex: &str,
+        legs: Vec<(Vec<SwapHop>, TokenAmount)>,
+        min_amount_out: TokenAmount,
     ) -> Result<AppResponse, String> {
         self.execute(
             Addr::unchecked(sender),
             Addr::unchecked(dex),
-            &msg::ExecuteMsg::SwapRoute {
-                amount_in,
-                expected_amount_out,
-                slippage,
-                swaps,
+            &msg::ExecuteMsg::SwapSplit {
+                legs,
+                min_amount_out,
+                recipient: None,
+                deadline: None,
             },
             &[],
         )
     }
 
-    pub fn swap(
+    pub fn quote_swap_split(
+        &self,
+        dex: &str,
+        amount_in: TokenAmount,
+        routes: Vec<Vec<SwapHop>>,
+        parts: u8,
+    ) -> StdResult<SwapSplitQuote> {
+        self.query(
+            Addr::unchecked(dex),
+            &msg::QueryMsg::QuoteSwapSplit {
+                amount_in,
+                routes,
+                parts,
+            },
+        )
+    }
+
+    pub fn find_best_route(
+        &self,
+        dex: &str,
+        offer_token: &str,
+        ask_token: &str,
+        amount: TokenAmount,
+        max_hops: u8,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<RouteQuote>> {
+        self.query(
+            Addr::unchecked(dex),
+            &msg::QueryMsg::FindBestRoute {
+                offer_token: offer_token.to_string(),
+                ask_token: ask_token.to_string(),
+                amount,
+                max_hops,
+                limit,
+            },
+        )
+    }
+
+    pub fn increase_observation_cardinality(
         &mut self,
         sender: &str,
         dex: &str,
         pool_key: &PoolKey,
-        x_to_y: bool,
-        amount: TokenAmount,
-        by_amount_in: bool,
-        sqrt_price_limit: SqrtPrice,
+        observation_cardinality_next: u16,
     ) -> Result<AppResponse, String> {
         self.execute(
             Addr::unchecked(sender),
             Addr::unchecked(dex),
-            &msg::ExecuteMsg::Swap {
+            &msg::ExecuteMsg::IncreaseObservationCardinality {
                 pool_key: pool_key.clone(),
-                x_to_y,
-                amount,
-                by_amount_in,
-                sqrt_price_limit,
+                observation_cardinality_next,
             },
             &[],
         )
     }
 
-    pub fn claim_fee(
-        &mut self,
-        sender: &str,
+    pub fn observe(
+        &self,
+        dex: &str,
+        pool_key: &PoolKey,
+        seconds_agos: Vec<u64>,
+    ) -> StdResult<ObserveResult> {
+        self.query(
+            Addr::unchecked(dex),
+            &msg::QueryMsg::Observe {
+                pool_key: pool_key.clone(),
+                seconds_agos,
+            },
+        )
+    }
+
+    pub fn position_seconds_inside(
+        &self,
         dex: &str,
+        owner_id: &str,
         index: u32,
-    ) -> Result<AppResponse, String> {
-        self.execute(
-            Addr::unchecked(sender),
+    ) -> StdResult<SecondsPerLiquidity> {
+        self.query(
             Addr::unchecked(dex),
-            &msg::ExecuteMsg::ClaimFee { index },
-            &[],
+            &msg::QueryMsg::PositionSecondsInside {
+                owner_id: Addr::unchecked(owner_id),
+                index,
+            },
         )
     }
 
-    pub fn quote_route(
+    pub fn quote_route_exact_out(
         &mut self,
         dex: &str,
-        amount_in: TokenAmount,
+        amount_out: TokenAmount,
         swaps: Vec<SwapHop>,
     ) -> StdResult<TokenAmount> {
         self.query(
             Addr::unchecked(dex),
-            &msg::QueryMsg::QuoteRoute { amount_in, swaps },
+            &msg::QueryMsg::QuoteRouteExactOut { amount_out, swaps },
         )
     }
 
@@ -566,6 +1188,27 @@ impl MockApp {
         )
     }
 
+    pub fn quote_partial(
+        &mut self,
+        dex: &str,
+        pool_key: &PoolKey,
+        x_to_y: bool,
+        amount: TokenAmount,
+        by_amount_in: bool,
+        sqrt_price_limit: SqrtPrice,
+    ) -> StdResult<QuoteResult> {
+        self.query(
+            Addr::unchecked(dex),
+            &msg::QueryMsg::QuotePartial {
+                pool_key: pool_key.clone(),
+                x_to_y,
+                amount,
+                by_amount_in,
+                sqrt_price_limit,
+            },
+        )
+    }
+
     pub fn get_pool(
         &self,
         dex: &str,
@@ -610,6 +1253,33 @@ impl MockApp {
         )
     }
 
+    pub fn get_token_id(&self, dex: &str, owner_id: &str, index: u32) -> StdResult<Binary> {
+        self.query(
+            Addr::unchecked(dex),
+            &msg::QueryMsg::TokenId {
+                owner_id: Addr::unchecked(owner_id),
+                index,
+            },
+        )
+    }
+
+    pub fn get_pool_positions(
+        &self,
+        dex: &str,
+        pool_key: &PoolKey,
+        start_after: Option<Binary>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<PoolPosition>> {
+        self.query(
+            Addr::unchecked(dex),
+            &msg::QueryMsg::PoolPositions {
+                pool_key: pool_key.clone(),
+                start_after,
+                limit,
+            },
+        )
+    }
+
     pub fn get_position(&self, dex: &str, owner_id: &str, index: u32) -> StdResult<Position> {
         self.query(
             Addr::unchecked(dex),
@@ -768,6 +1438,19 @@ pub mod macros {
     }
     pub(crate) use approve;
 
+    macro_rules! send_token {
+        ($app:ident, $token_address:expr, $contract:expr, $value:expr, $hook_msg:expr, $caller:tt) => {{
+            $app.send_token(
+                $token_address.as_str(),
+                $caller,
+                $contract.as_str(),
+                $value,
+                &$hook_msg,
+            )
+        }};
+    }
+    pub(crate) use send_token;
+
     macro_rules! fee_tier_exist {
         ($app:ident, $dex_address:expr, $fee_tier:expr) => {{
             $app.fee_tier_exist($dex_address.as_str(), $fee_tier)
@@ -792,6 +1475,51 @@ pub mod macros {
     }
     pub(crate) use create_position;
 
+    macro_rules! create_position_by_amounts {
+        ($app:ident, $dex_address:expr, $pool_key:expr, $lower_tick:expr, $upper_tick:expr, $amount_x_desired:expr, $amount_y_desired:expr, $amount_x_min:expr, $amount_y_min:expr, $caller:tt) => {{
+            $app.create_position_by_amounts(
+                $caller,
+                $dex_address.as_str(),
+                &$pool_key,
+                $lower_tick,
+                $upper_tick,
+                $amount_x_desired,
+                $amount_y_desired,
+                $amount_x_min,
+                $amount_y_min,
+            )
+        }};
+    }
+    pub(crate) use create_position_by_amounts;
+
+    macro_rules! increase_liquidity {
+        ($app:ident, $dex_address:expr, $index:expr, $liquidity_delta:expr, $slippage_limit_lower:expr, $slippage_limit_upper:expr, $caller:tt) => {{
+            $app.increase_liquidity(
+                $caller,
+                $dex_address.as_str(),
+                $index,
+                $liquidity_delta,
+                $slippage_limit_lower,
+                $slippage_limit_upper,
+            )
+        }};
+    }
+    pub(crate) use increase_liquidity;
+
+    macro_rules! decrease_liquidity {
+        ($app:ident, $dex_address:expr, $index:expr, $liquidity_delta:expr, $min_amount_x:expr, $min_amount_y:expr, $caller:tt) => {{
+            $app.decrease_liquidity(
+                $caller,
+                $dex_address.as_str(),
+                $index,
+                $liquidity_delta,
+                $min_amount_x,
+                $min_amount_y,
+            )
+        }};
+    }
+    pub(crate) use decrease_liquidity;
+
     macro_rules! remove_position {
         ($app:ident,  $dex_address:expr, $index:expr, $caller:tt) => {{
             $app.remove_position($caller, $dex_address.as_str(), $index)
@@ -884,6 +1612,23 @@ pub mod macros {
     }
     pub(crate) use swap;
 
+    macro_rules! swap_to {
+        ($app:ident, $dex_address:expr, $pool_key:expr, $x_to_y:expr, $amount:expr, $by_amount_in:expr, $sqrt_price_limit:expr, $recipient:expr, $deadline:expr, $caller:tt) => {{
+            $app.swap_to(
+                $caller,
+                $dex_address.as_str(),
+                &$pool_key,
+                $x_to_y,
+                $amount,
+                $by_amount_in,
+                $sqrt_price_limit,
+                $recipient,
+                $deadline,
+            )
+        }};
+    }
+    pub(crate) use swap_to;
+
     macro_rules! quote_route {
         ($app:ident, $dex_address:expr, $amount_in:expr, $swaps:expr) => {{
             $app.quote_route($dex_address.as_str(), $amount_in, $swaps)
@@ -891,6 +1636,52 @@ pub mod macros {
     }
     pub(crate) use quote_route;
 
+    macro_rules! increase_observation_cardinality {
+        ($app:ident, $dex_address:expr, $pool_key:expr, $observation_cardinality_next:expr, $caller:tt) => {{
+            $app.increase_observation_cardinality(
+                $caller,
+                $dex_address.as_str(),
+                &$pool_key,
+                $observation_cardinality_next,
+            )
+        }};
+    }
+    pub(crate) use increase_observation_cardinality;
+
+    macro_rules! observe {
+        ($app:ident, $dex_address:expr, $pool_key:expr, $seconds_agos:expr) => {{
+            $app.observe($dex_address.as_str(), &$pool_key, $seconds_agos)
+        }};
+    }
+    pub(crate) use observe;
+
+    macro_rules! position_seconds_inside {
+        ($app:ident, $dex_address:expr, $index:expr, $owner:tt) => {{
+            $app.position_seconds_inside($dex_address.as_str(), $owner, $index)
+        }};
+    }
+    pub(crate) use position_seconds_inside;
+
+    macro_rules! quote_route_exact_out {
+        ($app:ident, $dex_address:expr, $amount_out:expr, $swaps:expr) => {{
+            $app.quote_route_exact_out($dex_address.as_str(), $amount_out, $swaps)
+        }};
+    }
+    pub(crate) use quote_route_exact_out;
+
+    macro_rules! swap_route_exact_out {
+        ($app:ident, $dex_address:expr, $amount_out:expr, $max_amount_in:expr, $swaps:expr, $caller:tt) => {{
+            $app.swap_route_exact_out(
+                $caller,
+                $dex_address.as_str(),
+                $amount_out,
+                $max_amount_in,
+                $swaps,
+            )
+        }};
+    }
+    pub(crate) use swap_route_exact_out;
+
     macro_rules! swap_route {
         ($app:ident, $dex_address:expr, $amount_in:expr, $expected_amount_out:expr, $slippage:expr, $swaps:expr, $caller:tt) => {{
             $app.swap_route(
@@ -905,6 +1696,22 @@ pub mod macros {
     }
     pub(crate) use swap_route;
 
+    macro_rules! swap_route_to {
+        ($app:ident, $dex_address:expr, $amount_in:expr, $expected_amount_out:expr, $slippage:expr, $swaps:expr, $recipient:expr, $deadline:expr, $caller:tt) => {{
+            $app.swap_route_to(
+                $caller,
+                $dex_address.as_str(),
+                $amount_in,
+                $expected_amount_out,
+                $slippage,
+                $swaps,
+                $recipient,
+                $deadline,
+            )
+        }};
+    }
+    pub(crate) use swap_route_to;
+
     macro_rules! claim_fee {
         ($app:ident, $dex_address:expr, $index:expr, $caller:tt) => {{
             $app.claim_fee($caller, $dex_address.as_str(), $index)
@@ -912,6 +1719,47 @@ pub mod macros {
     }
     pub(crate) use claim_fee;
 
+    macro_rules! create_incentive {
+        ($app:ident, $dex_address:expr, $pool_key:expr, $reward_token:expr, $reward_per_sec:expr, $start_timestamp:expr, $end_timestamp:expr, $caller:tt) => {{
+            $app.create_incentive(
+                $caller,
+                $dex_address.as_str(),
+                &$pool_key,
+                $reward_token.as_str(),
+                $reward_per_sec,
+                $start_timestamp,
+                $end_timestamp,
+            )
+        }};
+    }
+    pub(crate) use create_incentive;
+
+    macro_rules! claim_incentives {
+        ($app:ident, $dex_address:expr, $index:expr, $caller:tt) => {{
+            $app.claim_incentives($caller, $dex_address.as_str(), $index)
+        }};
+    }
+    pub(crate) use claim_incentives;
+
+    macro_rules! withdraw_unallocated_incentive {
+        ($app:ident, $dex_address:expr, $pool_key:expr, $incentive_id:expr, $caller:tt) => {{
+            $app.withdraw_unallocated_incentive(
+                $caller,
+                $dex_address.as_str(),
+                &$pool_key,
+                $incentive_id,
+            )
+        }};
+    }
+    pub(crate) use withdraw_unallocated_incentive;
+
+    macro_rules! position_incentives {
+        ($app:ident, $dex_address:expr, $index:expr, $owner:tt) => {{
+            $app.position_incentives($dex_address.as_str(), $owner, $index)
+        }};
+    }
+    pub(crate) use position_incentives;
+
     macro_rules! init_slippage_pool_with_liquidity {
         ($app:ident, $dex_address:ident, $token_x_address:ident, $token_y_address:ident) => {{
             let fee_tier = FeeTier {
@@ -1271,6 +2119,20 @@ pub mod macros {
     }
     pub(crate) use withdraw_protocol_fee;
 
+    macro_rules! change_pool_protocol_fee {
+        ($app:ident, $dex_address:expr, $pool_key:expr, $protocol_fee:expr, $caller:tt) => {{
+            $app.change_pool_protocol_fee($caller, $dex_address.as_str(), &$pool_key, $protocol_fee)
+        }};
+    }
+    pub(crate) use change_pool_protocol_fee;
+
+    macro_rules! change_pool_dynamic_fee {
+        ($app:ident, $dex_address:expr, $pool_key:expr, $dynamic_fee:expr, $caller:tt) => {{
+            $app.change_pool_dynamic_fee($caller, $dex_address.as_str(), &$pool_key, $dynamic_fee)
+        }};
+    }
+    pub(crate) use change_pool_dynamic_fee;
+
     macro_rules! change_fee_receiver {
         ($app:ident,  $dex_address:expr, $pool_key:expr, $fee_receiver:tt, $caller:tt) => {{
             $app.change_fee_receiver($caller, $dex_address.as_str(), &$pool_key, $fee_receiver)
@@ -1278,6 +2140,34 @@ pub mod macros {
     }
     pub(crate) use change_fee_receiver;
 
+    macro_rules! change_guardian {
+        ($app:ident, $dex_address:expr, $guardian:expr, $caller:tt) => {{
+            $app.change_guardian($caller, $dex_address.as_str(), $guardian)
+        }};
+    }
+    pub(crate) use change_guardian;
+
+    macro_rules! pause {
+        ($app:ident, $dex_address:expr, $scope:expr, $caller:tt) => {{
+            $app.pause($caller, $dex_address.as_str(), $scope)
+        }};
+    }
+    pub(crate) use pause;
+
+    macro_rules! unpause {
+        ($app:ident, $dex_address:expr, $scope:expr, $caller:tt) => {{
+            $app.unpause($caller, $dex_address.as_str(), $scope)
+        }};
+    }
+    pub(crate) use unpause;
+
+    macro_rules! is_paused {
+        ($app:ident, $dex_address:expr, $scope:expr) => {{
+            $app.is_paused($dex_address.as_str(), $scope).unwrap()
+        }};
+    }
+    pub(crate) use is_paused;
+
     macro_rules! init_cross_swap {
         ($app:ident, $dex_address:ident, $token_x_address:expr, $token_y_address:expr) => {{
             let fee = Percentage::from_scale(6, 3);
@@ -0,0 +1,353 @@
+use cosmwasm_std::Uint128;
+use decimal::*;
+
+use crate::{
+    liquidity::Liquidity,
+    percentage::Percentage,
+    state::MAX_POOL_INCENTIVES,
+    tests::helper::{macros::*, MockApp},
+    token_amount::TokenAmount,
+    FeeTier, PoolKey,
+};
+
+#[test]
+fn test_claim_incentives() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+    init_basic_pool!(app, dex, token_x, token_y);
+    init_basic_position!(app, dex, token_x, token_y);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x.to_string(), token_y.to_string(), fee_tier).unwrap();
+
+    let initial_amount = 10u128.pow(10);
+    let reward_token = app.create_token("alice", "reward", initial_amount);
+    approve!(app, reward_token, dex, initial_amount, "alice").unwrap();
+
+    let end_timestamp = app.block_time().seconds() + 1000;
+    create_incentive!(
+        app,
+        dex,
+        pool_key,
+        reward_token,
+        TokenAmount(100),
+        None,
+        end_timestamp,
+        "alice"
+    )
+    .unwrap();
+
+    // the whole emission is funded up front
+    assert_eq!(balance_of!(app, reward_token, dex), 100 * 1000);
+
+    let pool = get_pool!(app, dex, token_x, token_y, fee_tier).unwrap();
+    assert_eq!(pool.incentives.len(), 1);
+
+    // 5 seconds passed with the create execute
+    app.increase_block_time(100);
+
+    let rewards = position_incentives!(app, dex, 0, "alice").unwrap();
+    assert_eq!(rewards.len(), 1);
+    assert_eq!(rewards[0].amount, Uint128::new(100 * 105));
+
+    let alice_before = balance_of!(app, reward_token, "alice");
+    claim_incentives!(app, dex, 0, "alice").unwrap();
+    assert_eq!(
+        balance_of!(app, reward_token, "alice") - alice_before,
+        100 * 105
+    );
+
+    // the emission stops at the end time
+    app.increase_block_time(2000);
+    claim_incentives!(app, dex, 0, "alice").unwrap();
+    assert_eq!(
+        balance_of!(app, reward_token, "alice") - alice_before,
+        100 * 1000
+    );
+    assert_eq!(balance_of!(app, reward_token, dex), 0);
+}
+
+#[test]
+fn test_decrease_all_liquidity_pays_incentives() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+    init_basic_pool!(app, dex, token_x, token_y);
+    init_basic_position!(app, dex, token_x, token_y);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x.to_string(), token_y.to_string(), fee_tier).unwrap();
+
+    let initial_amount = 10u128.pow(10);
+    let reward_token = app.create_token("alice", "reward", initial_amount);
+    approve!(app, reward_token, dex, initial_amount, "alice").unwrap();
+
+    let end_timestamp = app.block_time().seconds() + 1000;
+    create_incentive!(
+        app,
+        dex,
+        pool_key,
+        reward_token,
+        TokenAmount(100),
+        None,
+        end_timestamp,
+        "alice"
+    )
+    .unwrap();
+
+    app.increase_block_time(100);
+
+    let rewards = position_incentives!(app, dex, 0, "alice").unwrap();
+    assert_eq!(rewards.len(), 1);
+    let pending = rewards[0].amount.u128();
+    assert!(pending > 0);
+
+    // withdrawing everything removes the position together with its pending rewards
+    let position = get_position!(app, dex, 0, "alice").unwrap();
+    let alice_before = balance_of!(app, reward_token, "alice");
+    decrease_liquidity!(
+        app,
+        dex,
+        0,
+        position.liquidity,
+        TokenAmount(0),
+        TokenAmount(0),
+        "alice"
+    )
+    .unwrap();
+
+    assert_eq!(get_all_positions!(app, dex, "alice").len(), 0);
+    assert!(balance_of!(app, reward_token, "alice") - alice_before >= pending);
+}
+
+#[test]
+fn test_incentives_only_reward_in_range_liquidity() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+    init_basic_pool!(app, dex, token_x, token_y);
+    init_basic_position!(app, dex, token_x, token_y);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x.to_string(), token_y.to_string(), fee_tier).unwrap();
+    let pool = get_pool!(app, dex, token_x, token_y, fee_tier).unwrap();
+
+    // the range sits above the current tick
+    create_position!(
+        app,
+        dex,
+        pool_key,
+        10,
+        20,
+        Liquidity::from_integer(1000000),
+        pool.sqrt_price,
+        pool.sqrt_price,
+        "alice"
+    )
+    .unwrap();
+
+    let initial_amount = 10u128.pow(10);
+    let reward_token = app.create_token("alice", "reward", initial_amount);
+    approve!(app, reward_token, dex, initial_amount, "alice").unwrap();
+
+    let end_timestamp = app.block_time().seconds() + 1000;
+    create_incentive!(
+        app,
+        dex,
+        pool_key,
+        reward_token,
+        TokenAmount(100),
+        None,
+        end_timestamp,
+        "alice"
+    )
+    .unwrap();
+
+    app.increase_block_time(100);
+
+    let in_range = position_incentives!(app, dex, 0, "alice").unwrap();
+    assert_eq!(in_range[0].amount, Uint128::new(100 * 105));
+    let out_of_range = position_incentives!(app, dex, 1, "alice").unwrap();
+    assert!(out_of_range.is_empty());
+
+    // removing the position pays out its rewards as well
+    let alice_before = balance_of!(app, reward_token, "alice");
+    remove_position!(app, dex, 0, "alice").unwrap();
+    assert_eq!(
+        balance_of!(app, reward_token, "alice") - alice_before,
+        100 * 105
+    );
+}
+
+#[test]
+fn test_withdraw_unallocated_incentive() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+    init_basic_pool!(app, dex, token_x, token_y);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x.to_string(), token_y.to_string(), fee_tier).unwrap();
+
+    let initial_amount = 10u128.pow(10);
+    let reward_token = app.create_token("alice", "reward", initial_amount);
+    approve!(app, reward_token, dex, initial_amount, "alice").unwrap();
+
+    let end_timestamp = app.block_time().seconds() + 1000;
+    create_incentive!(
+        app,
+        dex,
+        pool_key,
+        reward_token,
+        TokenAmount(100),
+        None,
+        end_timestamp,
+        "alice"
+    )
+    .unwrap();
+
+    // the pool has no liquidity, nothing is distributed
+    app.increase_block_time(100);
+
+    // only the admin can withdraw
+    withdraw_unallocated_incentive!(app, dex, pool_key, 0, "bob").unwrap_err();
+    // unknown program
+    withdraw_unallocated_incentive!(app, dex, pool_key, 1, "alice").unwrap_err();
+
+    let alice_before = balance_of!(app, reward_token, "alice");
+    withdraw_unallocated_incentive!(app, dex, pool_key, 0, "alice").unwrap();
+    assert_eq!(
+        balance_of!(app, reward_token, "alice") - alice_before,
+        100 * 105
+    );
+
+    // the rest of the emission once the program ended
+    app.increase_block_time(2000);
+    withdraw_unallocated_incentive!(app, dex, pool_key, 0, "alice").unwrap();
+    assert_eq!(
+        balance_of!(app, reward_token, "alice") - alice_before,
+        100 * 1000
+    );
+    assert_eq!(balance_of!(app, reward_token, dex), 0);
+
+    // nothing is left
+    withdraw_unallocated_incentive!(app, dex, pool_key, 0, "alice").unwrap_err();
+}
+
+#[test]
+fn test_create_incentive_limit() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+    init_basic_pool!(app, dex, token_x, token_y);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x.to_string(), token_y.to_string(), fee_tier).unwrap();
+
+    let initial_amount = 10u128.pow(10);
+    let reward_token = app.create_token("alice", "reward", initial_amount);
+    approve!(app, reward_token, dex, initial_amount, "alice").unwrap();
+
+    for _ in 0..MAX_POOL_INCENTIVES {
+        let end_timestamp = app.block_time().seconds() + 1000;
+        create_incentive!(
+            app,
+            dex,
+            pool_key,
+            reward_token,
+            TokenAmount(100),
+            None,
+            end_timestamp,
+            "alice"
+        )
+        .unwrap();
+    }
+
+    // ended programs still count
+    app.increase_block_time(2000);
+    let end_timestamp = app.block_time().seconds() + 1000;
+    create_incentive!(
+        app,
+        dex,
+        pool_key,
+        reward_token,
+        TokenAmount(100),
+        None,
+        end_timestamp,
+        "alice"
+    )
+    .unwrap_err();
+
+    let pool = get_pool!(app, dex, token_x, token_y, fee_tier).unwrap();
+    assert_eq!(pool.incentives.len(), MAX_POOL_INCENTIVES);
+}
+
+#[test]
+fn test_create_incentive_validation() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+    init_basic_pool!(app, dex, token_x, token_y);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x.to_string(), token_y.to_string(), fee_tier).unwrap();
+
+    let initial_amount = 10u128.pow(10);
+    let reward_token = app.create_token("alice", "reward", initial_amount);
+    approve!(app, reward_token, dex, initial_amount, "alice").unwrap();
+
+    let now = app.block_time().seconds();
+
+    // only the admin can create programs
+    create_incentive!(
+        app,
+        dex,
+        pool_key,
+        reward_token,
+        TokenAmount(100),
+        None,
+        now + 1000,
+        "bob"
+    )
+    .unwrap_err();
+
+    let now = app.block_time().seconds();
+
+    // starts in the past
+    create_incentive!(
+        app,
+        dex,
+        pool_key,
+        reward_token,
+        TokenAmount(100),
+        Some(now - 1),
+        now + 1000,
+        "alice"
+    )
+    .unwrap_err();
+
+    // ends before it starts
+    create_incentive!(
+        app,
+        dex,
+        pool_key,
+        reward_token,
+        TokenAmount(100),
+        Some(now + 10),
+        now + 10,
+        "alice"
+    )
+    .unwrap_err();
+
+    // zero emission
+    create_incentive!(
+        app,
+        dex,
+        pool_key,
+        reward_token,
+        TokenAmount(0),
+        None,
+        now + 1000,
+        "alice"
+    )
+    .unwrap_err();
+
+    let pool = get_pool!(app, dex, token_x, token_y, fee_tier).unwrap();
+    assert!(pool.incentives.is_empty());
+    assert_eq!(balance_of!(app, reward_token, "alice"), initial_amount);
+}
@@ -0,0 +1,156 @@
+use decimal::{Decimal, Factories};
+
+use crate::{
+    liquidity::Liquidity,
+    percentage::Percentage,
+    sqrt_price::SqrtPrice,
+    tests::helper::{macros::*, MockApp},
+    token_amount::TokenAmount,
+    FeeTier, PoolKey,
+};
+
+#[test]
+fn test_increase_liquidity() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+    init_basic_pool!(app, dex, token_x, token_y);
+    init_basic_position!(app, dex, token_x, token_y);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x.to_string(), token_y.to_string(), fee_tier).unwrap();
+
+    let pool_before = get_pool!(app, dex, token_x, token_y, fee_tier).unwrap();
+    let position_before = get_position!(app, dex, 0, "alice").unwrap();
+
+    increase_liquidity!(
+        app,
+        dex,
+        0,
+        position_before.liquidity,
+        pool_before.sqrt_price,
+        pool_before.sqrt_price,
+        "alice"
+    )
+    .unwrap();
+
+    let positions = get_all_positions!(app, dex, "alice");
+    assert_eq!(positions.len(), 1);
+
+    let position_after = get_position!(app, dex, 0, "alice").unwrap();
+    assert_eq!(
+        position_after.liquidity,
+        position_before.liquidity + position_before.liquidity
+    );
+    assert_eq!(
+        position_after.lower_tick_index,
+        position_before.lower_tick_index
+    );
+    assert_eq!(
+        position_after.upper_tick_index,
+        position_before.upper_tick_index
+    );
+
+    let pool_after = get_pool!(app, dex, token_x, token_y, fee_tier).unwrap();
+    assert_eq!(
+        pool_after.liquidity,
+        pool_before.liquidity + position_before.liquidity
+    );
+
+    let lower_tick = get_tick!(app, dex, pool_key, position_after.lower_tick_index).unwrap();
+    let upper_tick = get_tick!(app, dex, pool_key, position_after.upper_tick_index).unwrap();
+    assert_eq!(lower_tick.liquidity_gross, position_after.liquidity);
+    assert_eq!(upper_tick.liquidity_gross, position_after.liquidity);
+
+    let dex_x = balance_of!(app, token_x, dex);
+    let dex_y = balance_of!(app, token_y, dex);
+    assert_eq!(dex_x, 1000);
+    assert_eq!(dex_y, 2000);
+}
+
+#[test]
+fn test_increase_liquidity_settles_fees() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+    init_basic_pool!(app, dex, token_x, token_y);
+    init_basic_position!(app, dex, token_x, token_y);
+    init_basic_swap!(app, dex, token_x, token_y);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+
+    increase_liquidity!(
+        app,
+        dex,
+        0,
+        Liquidity::from_integer(1000),
+        SqrtPrice::new(0),
+        SqrtPrice::max_instance(),
+        "alice"
+    )
+    .unwrap();
+
+    let pool = get_pool!(app, dex, token_x, token_y, fee_tier).unwrap();
+    let position = get_position!(app, dex, 0, "alice").unwrap();
+    assert_eq!(position.fee_growth_inside_x, pool.fee_growth_global_x);
+    assert_eq!(position.tokens_owed_x, TokenAmount(5));
+    assert_eq!(position.tokens_owed_y, TokenAmount(0));
+
+    // settled fees are still claimable after the top up
+    let user_amount_before_claim = balance_of!(app, token_x, "alice");
+    claim_fee!(app, dex, 0, "alice").unwrap();
+    let user_amount_after_claim = balance_of!(app, token_x, "alice");
+    assert_eq!(user_amount_after_claim - user_amount_before_claim, 5);
+}
+
+#[test]
+fn test_increase_liquidity_slippage_limit() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+    init_basic_pool!(app, dex, token_x, token_y);
+    init_basic_position!(app, dex, token_x, token_y);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_before = get_pool!(app, dex, token_x, token_y, fee_tier).unwrap();
+
+    init_basic_swap!(app, dex, token_x, token_y);
+
+    increase_liquidity!(
+        app,
+        dex,
+        0,
+        Liquidity::from_integer(1000),
+        pool_before.sqrt_price,
+        pool_before.sqrt_price,
+        "alice"
+    )
+    .unwrap_err();
+}
+
+#[test]
+fn test_increase_liquidity_not_owner_or_zero() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+    init_basic_pool!(app, dex, token_x, token_y);
+    init_basic_position!(app, dex, token_x, token_y);
+
+    increase_liquidity!(
+        app,
+        dex,
+        0,
+        Liquidity::from_integer(1000),
+        SqrtPrice::new(0),
+        SqrtPrice::max_instance(),
+        "bob"
+    )
+    .unwrap_err();
+
+    increase_liquidity!(
+        app,
+        dex,
+        0,
+        Liquidity::new(0),
+        SqrtPrice::new(0),
+        SqrtPrice::max_instance(),
+        "alice"
+    )
+    .unwrap_err();
+}
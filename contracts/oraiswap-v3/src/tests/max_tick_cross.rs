@@ -7,7 +7,7 @@ use crate::{
     sqrt_price::SqrtPrice,
     tests::helper::{macros::*, MockApp},
     token_amount::TokenAmount,
-    FeeTier, PoolKey, MIN_SQRT_PRICE,
+    FeeTier, PoolKey, MAX_TICK_CROSS, MIN_SQRT_PRICE,
 };
 
 #[test]
@@ -81,3 +81,67 @@ fn max_tick_cross() {
         get_tick_at_sqrt_price(quote_result.target_sqrt_price, 10).unwrap()
     );
 }
+
+#[test]
+fn test_tick_cross_limit() {
+    let mut app = MockApp::new(&[("alice", &[])]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+    init_basic_pool!(app, dex, token_x, token_y);
+
+    approve!(app, token_x, dex, u128::MAX, "alice").unwrap();
+    approve!(app, token_y, dex, u128::MAX, "alice").unwrap();
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x.to_string(), token_y.to_string(), fee_tier).unwrap();
+
+    for i in (-200..20).step_by(10) {
+        let pool = get_pool!(app, dex, token_x, token_y, fee_tier).unwrap();
+        create_position!(
+            app,
+            dex,
+            pool_key,
+            i,
+            i + 10,
+            Liquidity::from_integer(10000000),
+            pool.sqrt_price,
+            pool.sqrt_price,
+            "alice"
+        )
+        .unwrap();
+    }
+
+    assert_eq!(
+        app.get_max_tick_cross(dex.as_str()).unwrap(),
+        MAX_TICK_CROSS
+    );
+    app.change_max_tick_cross("bob", dex.as_str(), Some(5))
+        .unwrap_err();
+    app.change_max_tick_cross("alice", dex.as_str(), Some(0))
+        .unwrap_err();
+    app.change_max_tick_cross("alice", dex.as_str(), Some(5))
+        .unwrap();
+    assert_eq!(app.get_max_tick_cross(dex.as_str()).unwrap(), 5);
+
+    let amount = 50_000;
+    mint!(app, token_x, "bob", amount, "alice").unwrap();
+    approve!(app, token_x, dex, amount, "bob").unwrap();
+
+    let swap_amount = TokenAmount::new(amount);
+    let slippage = SqrtPrice::new(MIN_SQRT_PRICE);
+
+    // the quote stops at the limit and reports it
+    let quote_result = quote!(app, dex, pool_key, true, swap_amount, true, slippage).unwrap();
+    assert!(quote_result.max_ticks_crossed);
+    assert_eq!(quote_result.ticks.len(), 6);
+    assert!(quote_result.amount_in < swap_amount);
+
+    swap!(app, dex, pool_key, true, swap_amount, true, slippage, "bob").unwrap_err();
+
+    app.change_max_tick_cross("alice", dex.as_str(), None)
+        .unwrap();
+    let quote_result = quote!(app, dex, pool_key, true, swap_amount, true, slippage).unwrap();
+    assert!(!quote_result.max_ticks_crossed);
+    assert!(quote_result.ticks.len() > 6);
+
+    swap!(app, dex, pool_key, true, swap_amount, true, slippage, "bob").unwrap();
+}
@@ -0,0 +1,376 @@
+use cosmwasm_std::{Addr, Binary, DepsMut, Env, MessageInfo, Response};
+use cw2::set_contract_version;
+use cw_multi_test::ContractWrapper;
+use cw_storage_plus::Map;
+use decimal::*;
+
+use crate::{
+    contract::{self, CONTRACT_NAME, CONTRACT_VERSION},
+    interface::OwnerOfResponse,
+    msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
+    percentage::Percentage,
+    state::{self, POSITIONS_LENGTH},
+    tests::helper::{macros::*, MockApp},
+    token_amount::TokenAmount,
+    ContractError, FeeTier, PoolKey, Position,
+};
+
+fn instantiate_with_version(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+    version: &str,
+) -> Result<Response, ContractError> {
+    let response = contract::instantiate(deps.branch(), env, info, msg)?;
+    set_contract_version(deps.storage, CONTRACT_NAME, version)?;
+    Ok(response)
+}
+
+fn instantiate_v0_1_0(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    instantiate_with_version(deps, env, info, msg, "0.1.0")
+}
+
+fn instantiate_v0_2_0(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    instantiate_with_version(deps, env, info, msg, "0.2.0")
+}
+
+/// Writes a position the way 0.2.0 stored it, keyed by the owner and its index.
+fn instantiate_v0_2_0_with_position(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    let response = instantiate_with_version(deps.branch(), env, info, msg, "0.2.0")?;
+
+    let owner = Addr::unchecked("bob");
+    let legacy_positions: Map<&[u8], Position> = Map::new("positions");
+    legacy_positions.save(
+        deps.storage,
+        &state::position_key(&owner, 0),
+        &Position {
+            lower_tick_index: -10,
+            upper_tick_index: 10,
+            ..Position::default()
+        },
+    )?;
+    POSITIONS_LENGTH.save(deps.storage, owner.as_bytes(), &1)?;
+
+    Ok(response)
+}
+
+/// Writes three positions the way 0.2.0 stored them, two owned by bob and one by carol.
+fn instantiate_v0_2_0_with_positions(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    let response = instantiate_with_version(deps.branch(), env, info, msg, "0.2.0")?;
+
+    let legacy_positions: Map<&[u8], Position> = Map::new("positions");
+    for (owner, index, lower_tick_index) in [("bob", 0, -10), ("bob", 1, -20), ("carol", 0, -30)] {
+        let owner = Addr::unchecked(owner);
+        legacy_positions.save(
+            deps.storage,
+            &state::position_key(&owner, index),
+            &Position {
+                lower_tick_index,
+                upper_tick_index: 10,
+                ..Position::default()
+            },
+        )?;
+        POSITIONS_LENGTH.save(deps.storage, owner.as_bytes(), &(index + 1))?;
+    }
+
+    Ok(response)
+}
+
+fn instantiate_v99_0_0(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    instantiate_with_version(deps, env, info, msg, "99.0.0")
+}
+
+fn create_dex_with_code(
+    app: &mut MockApp,
+    instantiate: fn(DepsMut, Env, MessageInfo, InstantiateMsg) -> Result<Response, ContractError>,
+) -> Addr {
+    let code_id = app.upload(Box::new(ContractWrapper::new(
+        contract::execute,
+        instantiate,
+        contract::query,
+    )));
+    app.instantiate(
+        code_id,
+        Addr::unchecked("alice"),
+        &InstantiateMsg {
+            protocol_fee: Percentage::from_scale(1, 2),
+        },
+        &[],
+        "oraiswap_v3",
+    )
+    .unwrap()
+}
+
+fn applied_migrations(response: &cw_multi_test::AppResponse) -> Option<String> {
+    response
+        .events
+        .iter()
+        .flat_map(|event| event.attributes.iter())
+        .find(|attr| attr.key == "applied")
+        .map(|attr| attr.value.clone())
+}
+
+#[test]
+fn test_migrate_from_legacy_version() {
+    let mut app = MockApp::new(&[]);
+    let (token_x, token_y) = create_tokens!(app, 10u128.pow(10), 10u128.pow(10));
+    let dex = create_dex_with_code(&mut app, instantiate_v0_1_0);
+
+    init_basic_pool!(app, dex, token_x, token_y);
+    init_basic_position!(app, dex, token_x, token_y);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_before = get_pool!(app, dex, token_x, token_y, fee_tier).unwrap();
+
+    let response = app
+        .migrate_dex(
+            "alice",
+            dex.as_str(),
+            &MigrateMsg {
+                guardian: Some("bob".to_string()),
+                observation_cardinality: Some(10),
+                position_limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(applied_migrations(&response).unwrap(), "0.3.0");
+
+    let version = app.query_contract_version(dex.as_str()).unwrap();
+    assert_eq!(version.contract, CONTRACT_NAME);
+    assert_eq!(version.version, CONTRACT_VERSION);
+    assert_eq!(app.get_guardian(dex.as_str()).unwrap().unwrap(), "bob");
+
+    // existing state is untouched
+    let pool_after = get_pool!(app, dex, token_x, token_y, fee_tier).unwrap();
+    assert_eq!(pool_after.liquidity, pool_before.liquidity);
+    assert_eq!(pool_after.sqrt_price, pool_before.sqrt_price);
+    get_position!(app, dex, 0, "alice").unwrap();
+    init_basic_swap!(app, dex, token_x, token_y);
+
+    // the oracle of the existing pool was grown to the requested cardinality
+    let pool_key = PoolKey::new(token_x.to_string(), token_y.to_string(), fee_tier).unwrap();
+    let response = app
+        .increase_observation_cardinality("alice", dex.as_str(), &pool_key, 2)
+        .unwrap();
+    let cardinality_next = response
+        .events
+        .iter()
+        .flat_map(|event| event.attributes.iter())
+        .find(|attr| attr.key == "observation_cardinality_next")
+        .map(|attr| attr.value.clone());
+    assert_eq!(cardinality_next.unwrap(), "10");
+
+    // migrating to the same version runs no transforms
+    let response = app
+        .migrate_dex(
+            "alice",
+            dex.as_str(),
+            &MigrateMsg {
+                guardian: Some("carol".to_string()),
+                observation_cardinality: None,
+                position_limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(applied_migrations(&response), None);
+    assert_eq!(app.get_guardian(dex.as_str()).unwrap().unwrap(), "bob");
+}
+
+#[test]
+fn test_migrate_from_0_2_0_sets_up_guardian_and_oracles() {
+    let mut app = MockApp::new(&[]);
+    let (token_x, token_y) = create_tokens!(app, 10u128.pow(10), 10u128.pow(10));
+    let dex = create_dex_with_code(&mut app, instantiate_v0_2_0);
+
+    init_basic_pool!(app, dex, token_x, token_y);
+
+    let response = app
+        .migrate_dex(
+            "alice",
+            dex.as_str(),
+            &MigrateMsg {
+                guardian: Some("bob".to_string()),
+                observation_cardinality: Some(10),
+                position_limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(applied_migrations(&response).unwrap(), "0.3.0");
+    assert_eq!(app.get_guardian(dex.as_str()).unwrap().unwrap(), "bob");
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x.to_string(), token_y.to_string(), fee_tier).unwrap();
+    let response = app
+        .increase_observation_cardinality("alice", dex.as_str(), &pool_key, 2)
+        .unwrap();
+    let cardinality_next = response
+        .events
+        .iter()
+        .flat_map(|event| event.attributes.iter())
+        .find(|attr| attr.key == "observation_cardinality_next")
+        .map(|attr| attr.value.clone());
+    assert_eq!(cardinality_next.unwrap(), "10");
+}
+
+#[test]
+fn test_migrate_assigns_position_ids() {
+    let mut app = MockApp::new(&[]);
+    let dex = create_dex_with_code(&mut app, instantiate_v0_2_0_with_position);
+
+    let response = app
+        .migrate_dex("alice", dex.as_str(), &MigrateMsg::default())
+        .unwrap();
+    assert_eq!(applied_migrations(&response).unwrap(), "0.3.0");
+
+    // the position keeps its owner index and gets the first id
+    let position = get_position!(app, dex, 0, "bob").unwrap();
+    assert_eq!(position.lower_tick_index, -10);
+    let token_id = app.get_token_id(dex.as_str(), "bob", 0).unwrap();
+    assert_eq!(token_id, Binary::from(0u64.to_be_bytes().to_vec()));
+
+    let OwnerOfResponse { owner, .. } = app
+        .query(
+            dex.clone(),
+            &QueryMsg::OwnerOf {
+                token_id: token_id.clone(),
+                include_expired: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(owner, Addr::unchecked("bob"));
+
+    let pool_positions = app
+        .get_pool_positions(dex.as_str(), &PoolKey::default(), None, None)
+        .unwrap();
+    assert_eq!(pool_positions.len(), 1);
+    assert_eq!(pool_positions[0].token_id, token_id);
+}
+
+#[test]
+fn test_migrate_positions_in_batches() {
+    let mut app = MockApp::new(&[]);
+    let dex = create_dex_with_code(&mut app, instantiate_v0_2_0_with_positions);
+
+    app.migrate_dex(
+        "alice",
+        dex.as_str(),
+        &MigrateMsg {
+            position_limit: Some(1),
+            ..MigrateMsg::default()
+        },
+    )
+    .unwrap();
+
+    // only the first legacy position is moved by the migration
+    assert_eq!(
+        get_position!(app, dex, 0, "bob").unwrap().lower_tick_index,
+        -10
+    );
+    get_position!(app, dex, 1, "bob").unwrap_err();
+    get_position!(app, dex, 0, "carol").unwrap_err();
+
+    // only the admin moves the rest
+    let migrate_positions = ExecuteMsg::MigratePositions { limit: Some(1) };
+    app.execute(Addr::unchecked("bob"), dex.clone(), &migrate_positions, &[])
+        .unwrap_err();
+
+    let pending = |response: &cw_multi_test::AppResponse| {
+        response
+            .events
+            .iter()
+            .flat_map(|event| event.attributes.iter())
+            .find(|attr| attr.key == "pending")
+            .map(|attr| attr.value.clone())
+            .unwrap()
+    };
+
+    let response = app
+        .execute(
+            Addr::unchecked("alice"),
+            dex.clone(),
+            &migrate_positions,
+            &[],
+        )
+        .unwrap();
+    assert_eq!(pending(&response), "true");
+    assert_eq!(
+        get_position!(app, dex, 1, "bob").unwrap().lower_tick_index,
+        -20
+    );
+    get_position!(app, dex, 0, "carol").unwrap_err();
+
+    let response = app
+        .execute(
+            Addr::unchecked("alice"),
+            dex.clone(),
+            &ExecuteMsg::MigratePositions { limit: None },
+            &[],
+        )
+        .unwrap();
+    assert_eq!(pending(&response), "false");
+    assert_eq!(
+        get_position!(app, dex, 0, "carol")
+            .unwrap()
+            .lower_tick_index,
+        -30
+    );
+
+    // ids follow the legacy key order across batches
+    for (owner, index, position_id) in [("bob", 0, 0u64), ("bob", 1, 1), ("carol", 0, 2)] {
+        assert_eq!(
+            app.get_token_id(dex.as_str(), owner, index).unwrap(),
+            Binary::from(position_id.to_be_bytes().to_vec())
+        );
+    }
+    let pool_positions = app
+        .get_pool_positions(dex.as_str(), &PoolKey::default(), None, None)
+        .unwrap();
+    assert_eq!(pool_positions.len(), 3);
+}
+
+#[test]
+fn test_migrate_rejects_downgrade() {
+    let mut app = MockApp::new(&[]);
+    let dex = create_dex_with_code(&mut app, instantiate_v99_0_0);
+
+    app.migrate_dex("alice", dex.as_str(), &MigrateMsg::default())
+        .unwrap_err();
+
+    let version = app.query_contract_version(dex.as_str()).unwrap();
+    assert_eq!(version.version, "99.0.0");
+}
+
+#[test]
+fn test_migrate_rejects_other_contract() {
+    let mut app = MockApp::new(&[]);
+    let token = app.create_token("alice", "token", 100);
+
+    app.migrate_dex("alice", token.as_str(), &MigrateMsg::default())
+        .unwrap_err();
+}
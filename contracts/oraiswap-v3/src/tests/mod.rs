@@ -4,23 +4,42 @@ mod change_fee_receiver;
 mod change_protocol_fee;
 mod claim;
 mod create_pool;
+mod create_position_by_amounts;
 mod cross;
 mod cross_both_size;
+mod decrease_liquidity;
+mod dynamic_fee;
+mod events;
+mod find_best_route;
 mod get_liquidity_ticks;
 mod get_position_ticks;
 mod get_tickmap;
 mod helper;
+mod incentives;
+mod increase_liquidity;
 mod interaction_with_pool_on_removed_fee_tier;
 mod limits;
 mod liquidity_gap;
 mod max_tick_cross;
+mod migrate;
 mod multiple_swap;
 mod nft;
+mod oracle;
+mod pause;
+mod pool_creation_policy;
+mod pool_positions;
 mod position;
 mod position_list;
 mod position_slippage;
 mod protocol_fee;
+mod quote_partial;
+mod receive;
 mod remove_fee_tier;
+mod seconds_per_liquidity;
 mod slippage;
 mod swap;
+mod swap_partial;
+mod swap_recipient;
 mod swap_route;
+mod swap_route_exact_out;
+mod swap_split;
@@ -3,12 +3,11 @@ use decimal::*;
 
 use crate::{
     fee_growth::FeeGrowth,
-    interface::{NftInfoResponse, TokensResponse},
+    interface::{NftInfoResponse, OwnerOfResponse, TokensResponse},
     liquidity::Liquidity,
     msg,
     percentage::Percentage,
     sqrt_price::{calculate_sqrt_price, SqrtPrice},
-    state,
     tests::helper::{macros::*, MockApp},
     token_amount::TokenAmount,
     FeeTier, PoolKey, MIN_SQRT_PRICE,
@@ -107,7 +106,7 @@ fn test_query_nft() {
     )
     .unwrap();
 
-    let token_id = state::position_key(&Addr::unchecked("alice"), 0).into();
+    let token_id = app.get_token_id(dex.as_str(), "alice", 0).unwrap();
 
     let nft_info: NftInfoResponse = app
         .query(dex.clone(), &msg::QueryMsg::NftInfo { token_id })
@@ -214,7 +213,7 @@ fn test_burn_nft() {
     )
     .unwrap();
 
-    let token_id = state::position_key(&Addr::unchecked("alice"), 1).into();
+    let token_id = app.get_token_id(dex.as_str(), "alice", 1).unwrap();
     let NftInfoResponse {
         extension: position_state,
     } = app
@@ -265,7 +264,9 @@ fn test_burn_nft() {
 
     // Remove position
     let sender = Addr::unchecked("alice");
-    let token_id = state::position_key(&sender, remove_position_index).into();
+    let token_id = app
+        .get_token_id(dex.as_str(), sender.as_str(), remove_position_index)
+        .unwrap();
     app.execute(
         sender,
         dex.clone(),
@@ -447,7 +448,9 @@ fn test_transfer_nft() {
                 },
             )
             .unwrap();
-        let token_id = state::position_key(&Addr::unchecked("alice"), transferred_index).into();
+        let token_id = app
+            .get_token_id(dex.as_str(), "alice", transferred_index)
+            .unwrap();
         let NftInfoResponse {
             extension: removed_position,
         } = app
@@ -465,7 +468,9 @@ fn test_transfer_nft() {
             )
             .unwrap();
 
-        let token_id = state::position_key(&Addr::unchecked("alice"), transferred_index).into();
+        let token_id = app
+            .get_token_id(dex.as_str(), "alice", transferred_index)
+            .unwrap();
         app.execute(
             Addr::unchecked("alice"),
             dex.clone(),
@@ -477,7 +482,9 @@ fn test_transfer_nft() {
         )
         .unwrap();
 
-        let token_id = state::position_key(&Addr::unchecked("bob"), transferred_index).into();
+        let token_id = app
+            .get_token_id(dex.as_str(), "bob", transferred_index)
+            .unwrap();
         let NftInfoResponse {
             extension: recipient_position,
         } = app
@@ -507,7 +514,9 @@ fn test_transfer_nft() {
                 },
             )
             .unwrap();
-        let token_id = state::position_key(&Addr::unchecked("alice"), transferred_index).into();
+        let token_id = app
+            .get_token_id(dex.as_str(), "alice", transferred_index)
+            .unwrap();
         let NftInfoResponse {
             extension: owner_first_position_after,
         } = app
@@ -563,7 +572,9 @@ fn test_transfer_nft() {
             )
             .unwrap();
 
-        let token_id = state::position_key(&Addr::unchecked("alice"), transferred_index).into();
+        let token_id = app
+            .get_token_id(dex.as_str(), "alice", transferred_index)
+            .unwrap();
         app.execute(
             Addr::unchecked("alice"),
             dex.clone(),
@@ -599,7 +610,9 @@ fn test_transfer_nft() {
                 },
             )
             .unwrap();
-        let token_id = state::position_key(&Addr::unchecked("alice"), transferred_index).into();
+        let token_id = app
+            .get_token_id(dex.as_str(), "alice", transferred_index)
+            .unwrap();
         let NftInfoResponse {
             extension: owner_first_position_after,
         } = app
@@ -627,14 +640,18 @@ fn test_transfer_nft() {
             )
             .unwrap();
         let transferred_index = (owner_list_before.len() - 1) as u32;
-        let token_id = state::position_key(&Addr::unchecked("alice"), transferred_index).into();
+        let token_id = app
+            .get_token_id(dex.as_str(), "alice", transferred_index)
+            .unwrap();
         let NftInfoResponse {
             extension: removed_position,
         } = app
             .query(dex.clone(), &msg::QueryMsg::NftInfo { token_id })
             .unwrap();
 
-        let token_id = state::position_key(&Addr::unchecked("alice"), transferred_index).into();
+        let token_id = app
+            .get_token_id(dex.as_str(), "alice", transferred_index)
+            .unwrap();
         app.execute(
             Addr::unchecked("alice"),
             dex.clone(),
@@ -658,8 +675,9 @@ fn test_transfer_nft() {
             )
             .unwrap();
         let recipient_position_index = (recipient_list_after.len() - 1) as u32;
-        let token_id =
-            state::position_key(&Addr::unchecked("bob"), recipient_position_index).into();
+        let token_id = app
+            .get_token_id(dex.as_str(), "bob", recipient_position_index)
+            .unwrap();
         let NftInfoResponse {
             extension: recipient_position,
         } = app
@@ -684,14 +702,18 @@ fn test_transfer_nft() {
                 },
             )
             .unwrap();
-        let token_id = state::position_key(&Addr::unchecked("alice"), transferred_index).into();
+        let token_id = app
+            .get_token_id(dex.as_str(), "alice", transferred_index)
+            .unwrap();
         let NftInfoResponse {
             extension: removed_position,
         } = app
             .query(dex.clone(), &msg::QueryMsg::NftInfo { token_id })
             .unwrap();
 
-        let token_id = state::position_key(&Addr::unchecked("alice"), transferred_index).into();
+        let token_id = app
+            .get_token_id(dex.as_str(), "alice", transferred_index)
+            .unwrap();
         app.execute(
             Addr::unchecked("alice"),
             dex.clone(),
@@ -716,8 +738,9 @@ fn test_transfer_nft() {
             )
             .unwrap();
         let recipient_position_index = (recipient_list_after.len() - 1) as u32;
-        let token_id =
-            state::position_key(&Addr::unchecked("bob"), recipient_position_index).into();
+        let token_id = app
+            .get_token_id(dex.as_str(), "bob", recipient_position_index)
+            .unwrap();
         let NftInfoResponse {
             extension: recipient_position,
         } = app
@@ -770,7 +793,9 @@ fn test_transfer_nft() {
                 },
             )
             .unwrap();
-        let token_id = state::position_key(&Addr::unchecked("bob"), transferred_index).into();
+        let token_id = app
+            .get_token_id(dex.as_str(), "bob", transferred_index)
+            .unwrap();
         let NftInfoResponse {
             extension: removed_position,
         } = app
@@ -788,7 +813,9 @@ fn test_transfer_nft() {
             )
             .unwrap();
 
-        let token_id = state::position_key(&Addr::unchecked("bob"), transferred_index).into();
+        let token_id = app
+            .get_token_id(dex.as_str(), "bob", transferred_index)
+            .unwrap();
         app.execute(
             Addr::unchecked("bob"),
             dex.clone(),
@@ -824,14 +851,18 @@ fn test_transfer_nft() {
                 },
             )
             .unwrap();
-        let token_id = state::position_key(&Addr::unchecked("bob"), transferred_index).into();
+        let token_id = app
+            .get_token_id(dex.as_str(), "bob", transferred_index)
+            .unwrap();
         let NftInfoResponse {
             extension: recipient_first_position_after,
         } = app
             .query(dex.clone(), &msg::QueryMsg::NftInfo { token_id })
             .unwrap();
 
-        let token_id = state::position_key(&Addr::unchecked("alice"), transferred_index).into();
+        let token_id = app
+            .get_token_id(dex.as_str(), "alice", transferred_index)
+            .unwrap();
         let NftInfoResponse {
             extension: owner_new_position,
         } = app
@@ -969,7 +1000,9 @@ fn test_only_owner_can_transfer_nft() {
     // Transfer first position
     {
         let transferred_index = 0;
-        let token_id = state::position_key(&Addr::unchecked("bob"), transferred_index).into();
+        let token_id = app
+            .get_token_id(dex.as_str(), "bob", transferred_index)
+            .unwrap();
         app.execute(
             Addr::unchecked("bob"),
             dex.clone(),
@@ -982,3 +1015,120 @@ fn test_only_owner_can_transfer_nft() {
         .unwrap_err();
     }
 }
+
+#[test]
+fn test_token_ids_are_stable() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+    init_basic_pool!(app, dex, token_x, token_y);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x.to_string(), token_y.to_string(), fee_tier).unwrap();
+    let pool = get_pool!(app, dex, token_x, token_y, fee_tier).unwrap();
+
+    approve!(app, token_x, dex, 10u128.pow(10), "alice").unwrap();
+    approve!(app, token_y, dex, 10u128.pow(10), "alice").unwrap();
+    for lower_tick in [-10, -20, -30] {
+        create_position!(
+            app,
+            dex,
+            pool_key,
+            lower_tick,
+            10,
+            Liquidity::from_integer(1000),
+            pool.sqrt_price,
+            pool.sqrt_price,
+            "alice"
+        )
+        .unwrap();
+    }
+
+    let removed_token_id = app.get_token_id(dex.as_str(), "alice", 0).unwrap();
+    let token_id = app.get_token_id(dex.as_str(), "alice", 2).unwrap();
+    app.execute(
+        Addr::unchecked("alice"),
+        dex.clone(),
+        &msg::ExecuteMsg::Approve {
+            spender: Addr::unchecked("carol"),
+            token_id: token_id.clone(),
+            expires: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // the last position takes the removed index but keeps its token id and approvals
+    remove_position!(app, dex, 0, "alice").unwrap();
+    assert_eq!(
+        app.get_token_id(dex.as_str(), "alice", 0).unwrap(),
+        token_id
+    );
+    let OwnerOfResponse { owner, approvals } = app
+        .query(
+            dex.clone(),
+            &msg::QueryMsg::OwnerOf {
+                token_id: token_id.clone(),
+                include_expired: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(owner, Addr::unchecked("alice"));
+    assert_eq!(approvals[0].spender, Addr::unchecked("carol"));
+    app.query::<NftInfoResponse, _>(
+        dex.clone(),
+        &msg::QueryMsg::NftInfo {
+            token_id: removed_token_id.clone(),
+        },
+    )
+    .unwrap_err();
+
+    app.execute(
+        Addr::unchecked("carol"),
+        dex.clone(),
+        &msg::ExecuteMsg::TransferNft {
+            recipient: Addr::unchecked("carol"),
+            token_id: token_id.clone(),
+        },
+        &[],
+    )
+    .unwrap();
+    let NftInfoResponse { extension } = app
+        .query(
+            dex.clone(),
+            &msg::QueryMsg::NftInfo {
+                token_id: token_id.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(extension.lower_tick_index, -30);
+    assert_eq!(
+        app.get_token_id(dex.as_str(), "carol", 0).unwrap(),
+        token_id
+    );
+
+    // ids are never reused
+    let response = create_position!(
+        app,
+        dex,
+        pool_key,
+        -10,
+        10,
+        Liquidity::from_integer(1000),
+        pool.sqrt_price,
+        pool.sqrt_price,
+        "alice"
+    )
+    .unwrap();
+    let new_token_id = app.get_token_id(dex.as_str(), "alice", 1).unwrap();
+    assert_ne!(new_token_id, removed_token_id);
+    assert_ne!(new_token_id, token_id);
+
+    // the token id of a new position is announced on creation
+    let created_token_id = response
+        .events
+        .iter()
+        .flat_map(|event| event.attributes.iter())
+        .find(|attr| attr.key == "token_id")
+        .map(|attr| attr.value.clone());
+    assert_eq!(created_token_id, Some(new_token_id.to_base64()));
+}
@@ -0,0 +1,81 @@
+use decimal::{Decimal, Factories};
+
+use crate::{
+    percentage::Percentage,
+    tests::helper::{macros::*, MockApp},
+    token_amount::TokenAmount,
+    FeeTier, PoolKey, MAX_OBSERVATION_CARDINALITY,
+};
+
+#[test]
+fn test_observe_current_tick() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+    init_basic_pool!(app, dex, token_x, token_y);
+    init_basic_position!(app, dex, token_x, token_y);
+    init_basic_swap!(app, dex, token_x, token_y);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x.to_string(), token_y.to_string(), fee_tier).unwrap();
+
+    app.increase_block_time(100);
+
+    // both points are after the swap so the mean tick is the current one
+    let result = observe!(app, dex, pool_key, vec![0, 50]).unwrap();
+    assert_eq!(result.tick_cumulatives.len(), 2);
+    assert_eq!(
+        (result.tick_cumulatives[0] - result.tick_cumulatives[1]) / 50,
+        -20
+    );
+    assert!(
+        result.seconds_per_liquidity_cumulatives[0] > result.seconds_per_liquidity_cumulatives[1]
+    );
+
+    // a single observation can not answer for points before it
+    observe!(app, dex, pool_key, vec![150]).unwrap_err();
+}
+
+#[test]
+fn test_observe_history() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+    init_basic_pool!(app, dex, token_x, token_y);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x.to_string(), token_y.to_string(), fee_tier).unwrap();
+
+    increase_observation_cardinality!(app, dex, pool_key, 10, "bob").unwrap();
+
+    init_basic_position!(app, dex, token_x, token_y);
+    app.increase_block_time(100);
+    init_basic_swap!(app, dex, token_x, token_y);
+    app.increase_block_time(100);
+
+    // the swap executed 105 seconds ago, the price sat at tick 0 before it
+    let result = observe!(app, dex, pool_key, vec![0, 100, 150]).unwrap();
+    assert_eq!(result.tick_cumulatives[0], -20 * 105);
+    assert_eq!(result.tick_cumulatives[1], -20 * 5);
+    assert_eq!(result.tick_cumulatives[2], 0);
+
+    let twap_tick = (result.tick_cumulatives[0] - result.tick_cumulatives[1]) / 100;
+    assert_eq!(twap_tick, -20);
+}
+
+#[test]
+fn test_increase_observation_cardinality() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x.to_string(), token_y.to_string(), fee_tier).unwrap();
+
+    // pool does not exist yet
+    increase_observation_cardinality!(app, dex, pool_key, 10, "bob").unwrap_err();
+
+    init_basic_pool!(app, dex, token_x, token_y);
+
+    increase_observation_cardinality!(app, dex, pool_key, MAX_OBSERVATION_CARDINALITY + 1, "bob")
+        .unwrap_err();
+    increase_observation_cardinality!(app, dex, pool_key, MAX_OBSERVATION_CARDINALITY, "bob")
+        .unwrap();
+}
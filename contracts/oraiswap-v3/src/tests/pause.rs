@@ -0,0 +1,228 @@
+use decimal::*;
+
+use crate::{
+    liquidity::Liquidity,
+    percentage::Percentage,
+    sqrt_price::{calculate_sqrt_price, SqrtPrice},
+    tests::helper::{macros::*, MockApp},
+    token_amount::TokenAmount,
+    FeeTier, PauseScope, PoolKey, MAX_SQRT_PRICE, MIN_SQRT_PRICE,
+};
+
+#[test]
+fn test_pause_swaps_keeps_exits_open() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+    init_basic_pool!(app, dex, token_x, token_y);
+    init_basic_position!(app, dex, token_x, token_y);
+    init_basic_swap!(app, dex, token_x, token_y);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x.to_string(), token_y.to_string(), fee_tier).unwrap();
+
+    pause!(app, dex, PauseScope::Swaps, "alice").unwrap();
+    assert!(is_paused!(app, dex, PauseScope::Swaps));
+
+    let amount = 1000;
+    mint!(app, token_y, "bob", amount, "alice").unwrap();
+    approve!(app, token_y, dex, amount, "bob").unwrap();
+    swap!(
+        app,
+        dex,
+        pool_key,
+        false,
+        TokenAmount(amount),
+        true,
+        SqrtPrice::new(MAX_SQRT_PRICE),
+        "bob"
+    )
+    .unwrap_err();
+
+    // liquidity providers can still leave
+    claim_fee!(app, dex, 0, "alice").unwrap();
+    remove_position!(app, dex, 0, "alice").unwrap();
+
+    unpause!(app, dex, PauseScope::Swaps, "alice").unwrap();
+    assert!(!is_paused!(app, dex, PauseScope::Swaps));
+}
+
+#[test]
+fn test_pause_position_creation() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+    init_basic_pool!(app, dex, token_x, token_y);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x.to_string(), token_y.to_string(), fee_tier).unwrap();
+    let pool = get_pool!(app, dex, token_x, token_y, fee_tier).unwrap();
+
+    approve!(app, token_x, dex, 10u128.pow(10), "alice").unwrap();
+    approve!(app, token_y, dex, 10u128.pow(10), "alice").unwrap();
+
+    pause!(app, dex, PauseScope::PositionCreation, "alice").unwrap();
+
+    create_position!(
+        app,
+        dex,
+        pool_key,
+        -20,
+        10,
+        Liquidity::from_integer(1000000),
+        pool.sqrt_price,
+        pool.sqrt_price,
+        "alice"
+    )
+    .unwrap_err();
+
+    // new pools are halted with position creation
+    let other_fee_tier = FeeTier::new(Percentage::from_scale(1, 2), 1).unwrap();
+    add_fee_tier!(app, dex, other_fee_tier, "alice").unwrap();
+    create_pool!(
+        app,
+        dex,
+        token_x,
+        token_y,
+        other_fee_tier,
+        calculate_sqrt_price(0).unwrap(),
+        0,
+        "alice"
+    )
+    .unwrap_err();
+
+    unpause!(app, dex, PauseScope::PositionCreation, "alice").unwrap();
+
+    create_position!(
+        app,
+        dex,
+        pool_key,
+        -20,
+        10,
+        Liquidity::from_integer(1000000),
+        pool.sqrt_price,
+        pool.sqrt_price,
+        "alice"
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_pause_single_pool() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+    init_basic_pool!(app, dex, token_x, token_y);
+    init_basic_position!(app, dex, token_x, token_y);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x.to_string(), token_y.to_string(), fee_tier).unwrap();
+    let scope = PauseScope::Pool {
+        pool_key: pool_key.clone(),
+    };
+
+    pause!(app, dex, scope.clone(), "alice").unwrap();
+    assert!(is_paused!(app, dex, scope.clone()));
+    assert!(!is_paused!(app, dex, PauseScope::Swaps));
+
+    let amount = 1000;
+    mint!(app, token_x, "bob", amount, "alice").unwrap();
+    approve!(app, token_x, dex, amount, "bob").unwrap();
+    swap!(
+        app,
+        dex,
+        pool_key,
+        true,
+        TokenAmount(amount),
+        true,
+        SqrtPrice::new(MIN_SQRT_PRICE),
+        "bob"
+    )
+    .unwrap_err();
+
+    unpause!(app, dex, scope, "alice").unwrap();
+    swap!(
+        app,
+        dex,
+        pool_key,
+        true,
+        TokenAmount(amount),
+        true,
+        SqrtPrice::new(MIN_SQRT_PRICE),
+        "bob"
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_pause_blocks_increase_liquidity() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+    init_basic_pool!(app, dex, token_x, token_y);
+    init_basic_position!(app, dex, token_x, token_y);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x.to_string(), token_y.to_string(), fee_tier).unwrap();
+    let pool = get_pool!(app, dex, token_x, token_y, fee_tier).unwrap();
+    let liquidity_delta = Liquidity::from_integer(1000);
+
+    for scope in [
+        PauseScope::PositionCreation,
+        PauseScope::Pool {
+            pool_key: pool_key.clone(),
+        },
+    ] {
+        pause!(app, dex, scope.clone(), "alice").unwrap();
+        increase_liquidity!(
+            app,
+            dex,
+            0,
+            liquidity_delta,
+            pool.sqrt_price,
+            pool.sqrt_price,
+            "alice"
+        )
+        .unwrap_err();
+
+        // liquidity can still be withdrawn
+        decrease_liquidity!(
+            app,
+            dex,
+            0,
+            liquidity_delta,
+            TokenAmount(0),
+            TokenAmount(0),
+            "alice"
+        )
+        .unwrap();
+
+        unpause!(app, dex, scope, "alice").unwrap();
+        increase_liquidity!(
+            app,
+            dex,
+            0,
+            liquidity_delta,
+            pool.sqrt_price,
+            pool.sqrt_price,
+            "alice"
+        )
+        .unwrap();
+    }
+}
+
+#[test]
+fn test_guardian_can_pause() {
+    let mut app = MockApp::new(&[]);
+    let (dex, _, _) = init_dex_and_tokens!(app);
+
+    pause!(app, dex, PauseScope::Swaps, "bob").unwrap_err();
+    change_guardian!(app, dex, Some("bob"), "bob").unwrap_err();
+
+    change_guardian!(app, dex, Some("bob"), "alice").unwrap();
+    assert_eq!(app.get_guardian(dex.as_str()).unwrap().unwrap(), "bob");
+
+    pause!(app, dex, PauseScope::Swaps, "bob").unwrap();
+    assert!(is_paused!(app, dex, PauseScope::Swaps));
+    unpause!(app, dex, PauseScope::Swaps, "bob").unwrap();
+    assert!(!is_paused!(app, dex, PauseScope::Swaps));
+
+    change_guardian!(app, dex, None, "alice").unwrap();
+    pause!(app, dex, PauseScope::Swaps, "bob").unwrap_err();
+}
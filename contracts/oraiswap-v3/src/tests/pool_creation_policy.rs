@@ -0,0 +1,239 @@
+use cosmwasm_std::{coin, coins, Addr, Uint128};
+use decimal::*;
+
+use crate::{
+    msg::ExecuteMsg,
+    percentage::Percentage,
+    sqrt_price::calculate_sqrt_price,
+    tests::helper::{macros::*, MockApp},
+    FeeTier, PoolCreationFee, PoolCreationPolicy,
+};
+
+#[test]
+fn test_admin_only_pool_creation() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    add_fee_tier!(app, dex, fee_tier, "alice").unwrap();
+    let init_sqrt_price = calculate_sqrt_price(0).unwrap();
+
+    assert_eq!(
+        app.get_pool_creation_policy(dex.as_str()).unwrap(),
+        PoolCreationPolicy::Open
+    );
+
+    app.change_pool_creation_policy("bob", dex.as_str(), PoolCreationPolicy::AdminOnly)
+        .unwrap_err();
+    app.change_pool_creation_policy("alice", dex.as_str(), PoolCreationPolicy::AdminOnly)
+        .unwrap();
+    assert_eq!(
+        app.get_pool_creation_policy(dex.as_str()).unwrap(),
+        PoolCreationPolicy::AdminOnly
+    );
+
+    create_pool!(
+        app,
+        dex,
+        token_x,
+        token_y,
+        fee_tier,
+        init_sqrt_price,
+        0,
+        "bob"
+    )
+    .unwrap_err();
+    create_pool!(
+        app,
+        dex,
+        token_x,
+        token_y,
+        fee_tier,
+        init_sqrt_price,
+        0,
+        "alice"
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_whitelisted_pool_creation() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    add_fee_tier!(app, dex, fee_tier, "alice").unwrap();
+    let init_sqrt_price = calculate_sqrt_price(0).unwrap();
+
+    // both tokens have to be listed
+    app.change_pool_creation_policy(
+        "alice",
+        dex.as_str(),
+        PoolCreationPolicy::Whitelist {
+            tokens: vec![token_x.to_string()],
+        },
+    )
+    .unwrap();
+    create_pool!(
+        app,
+        dex,
+        token_x,
+        token_y,
+        fee_tier,
+        init_sqrt_price,
+        0,
+        "bob"
+    )
+    .unwrap_err();
+
+    app.change_pool_creation_policy(
+        "alice",
+        dex.as_str(),
+        PoolCreationPolicy::Whitelist {
+            tokens: vec![token_x.to_string(), token_y.to_string()],
+        },
+    )
+    .unwrap();
+    create_pool!(
+        app,
+        dex,
+        token_x,
+        token_y,
+        fee_tier,
+        init_sqrt_price,
+        0,
+        "bob"
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_pool_creation_fee() {
+    let mut app = MockApp::new(&[
+        ("alice", &coins(1000, "orai")),
+        ("bob", &[coin(1000, "orai"), coin(1000, "atom")]),
+    ]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let other_fee_tier = FeeTier::new(Percentage::from_scale(1, 2), 10).unwrap();
+    add_fee_tier!(app, dex, fee_tier, "alice").unwrap();
+    add_fee_tier!(app, dex, other_fee_tier, "alice").unwrap();
+    let init_sqrt_price = calculate_sqrt_price(0).unwrap();
+
+    let create_pool_msg = ExecuteMsg::CreatePool {
+        token_0: token_x.to_string(),
+        token_1: token_y.to_string(),
+        fee_tier,
+        init_sqrt_price,
+        init_tick: 0,
+    };
+
+    // funds are rejected while pool creation is free
+    app.execute(
+        Addr::unchecked("bob"),
+        dex.clone(),
+        &create_pool_msg,
+        &coins(100, "orai"),
+    )
+    .unwrap_err();
+
+    let fee = PoolCreationFee {
+        denom: "orai".to_string(),
+        amount: Uint128::new(100),
+        treasury: Addr::unchecked("treasury"),
+    };
+    app.change_pool_creation_fee(
+        "alice",
+        dex.as_str(),
+        Some(PoolCreationFee {
+            amount: Uint128::zero(),
+            ..fee.clone()
+        }),
+    )
+    .unwrap_err();
+    app.change_pool_creation_fee("alice", dex.as_str(), Some(fee.clone()))
+        .unwrap();
+    assert_eq!(
+        app.get_pool_creation_fee(dex.as_str()).unwrap(),
+        Some(fee.clone())
+    );
+
+    // unpaid
+    create_pool!(
+        app,
+        dex,
+        token_x,
+        token_y,
+        fee_tier,
+        init_sqrt_price,
+        0,
+        "bob"
+    )
+    .unwrap_err();
+    app.execute(
+        Addr::unchecked("bob"),
+        dex.clone(),
+        &create_pool_msg,
+        &coins(99, "orai"),
+    )
+    .unwrap_err();
+
+    // other denoms are rejected
+    app.execute(
+        Addr::unchecked("bob"),
+        dex.clone(),
+        &create_pool_msg,
+        &[coin(100, "atom"), coin(100, "orai")],
+    )
+    .unwrap_err();
+
+    // the treasury receives the fee and the excess is refunded
+    app.execute(
+        Addr::unchecked("bob"),
+        dex.clone(),
+        &create_pool_msg,
+        &coins(150, "orai"),
+    )
+    .unwrap();
+    assert_eq!(
+        app.query_balance(Addr::unchecked("treasury"), "orai".to_string())
+            .unwrap(),
+        Uint128::new(100)
+    );
+    assert_eq!(
+        app.query_balance(Addr::unchecked("bob"), "orai".to_string())
+            .unwrap(),
+        Uint128::new(900)
+    );
+    assert_eq!(
+        app.query_balance(dex.clone(), "orai".to_string()).unwrap(),
+        Uint128::zero()
+    );
+
+    // the admin creates pools for free and can not attach funds
+    app.execute(
+        Addr::unchecked("alice"),
+        dex.clone(),
+        &ExecuteMsg::CreatePool {
+            token_0: token_x.to_string(),
+            token_1: token_y.to_string(),
+            fee_tier: other_fee_tier,
+            init_sqrt_price,
+            init_tick: 0,
+        },
+        &coins(100, "orai"),
+    )
+    .unwrap_err();
+    create_pool!(
+        app,
+        dex,
+        token_x,
+        token_y,
+        other_fee_tier,
+        init_sqrt_price,
+        0,
+        "alice"
+    )
+    .unwrap();
+}
@@ -0,0 +1,132 @@
+use cosmwasm_std::Addr;
+use decimal::*;
+
+use crate::{
+    liquidity::Liquidity,
+    percentage::Percentage,
+    sqrt_price::calculate_sqrt_price,
+    tests::helper::{macros::*, MockApp},
+    FeeTier, PoolKey,
+};
+
+#[test]
+fn test_pool_positions() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let other_fee_tier = FeeTier::new(Percentage::from_scale(1, 2), 10).unwrap();
+    let pool_key = PoolKey::new(token_x.to_string(), token_y.to_string(), fee_tier).unwrap();
+    let other_pool_key =
+        PoolKey::new(token_x.to_string(), token_y.to_string(), other_fee_tier).unwrap();
+
+    for fee_tier in [fee_tier, other_fee_tier] {
+        add_fee_tier!(app, dex, fee_tier, "alice").unwrap();
+        create_pool!(
+            app,
+            dex,
+            token_x,
+            token_y,
+            fee_tier,
+            calculate_sqrt_price(0).unwrap(),
+            0,
+            "alice"
+        )
+        .unwrap();
+    }
+
+    let amount = 10u128.pow(10);
+    mint!(app, token_x, "bob", amount, "alice").unwrap();
+    mint!(app, token_y, "bob", amount, "alice").unwrap();
+    for owner in ["alice", "bob"] {
+        approve!(app, token_x, dex, amount, owner).unwrap();
+        approve!(app, token_y, dex, amount, owner).unwrap();
+    }
+
+    let pool = get_pool!(app, dex, token_x, token_y, fee_tier).unwrap();
+    let liquidity = Liquidity::from_integer(1000);
+    // alice: #0 in the pool, #1 in the other pool, #2 in the pool; bob: #0 in the pool
+    for (key, lower_tick, owner) in [
+        (&pool_key, -10, "alice"),
+        (&other_pool_key, -20, "alice"),
+        (&pool_key, -30, "alice"),
+        (&pool_key, -40, "bob"),
+    ] {
+        create_position!(
+            app,
+            dex,
+            key,
+            lower_tick,
+            10,
+            liquidity,
+            pool.sqrt_price,
+            pool.sqrt_price,
+            owner
+        )
+        .unwrap();
+    }
+
+    let positions = app
+        .get_pool_positions(dex.as_str(), &pool_key, None, None)
+        .unwrap();
+    assert_eq!(positions.len(), 3);
+    assert!(positions
+        .iter()
+        .all(|position| position.position.pool_key == pool_key));
+
+    // paging continues after the last token id
+    let first_page = app
+        .get_pool_positions(dex.as_str(), &pool_key, None, Some(2))
+        .unwrap();
+    assert_eq!(first_page.len(), 2);
+    let second_page = app
+        .get_pool_positions(
+            dex.as_str(),
+            &pool_key,
+            Some(first_page[1].token_id.clone()),
+            Some(2),
+        )
+        .unwrap();
+    assert_eq!(second_page.len(), 1);
+    assert_eq!(second_page[0].token_id, positions[2].token_id);
+
+    // removing #0 moves alice's last position into its index
+    let removed_token_id = app.get_token_id(dex.as_str(), "alice", 0).unwrap();
+    let moved_token_id = app.get_token_id(dex.as_str(), "alice", 2).unwrap();
+    let bob_token_id = app.get_token_id(dex.as_str(), "bob", 0).unwrap();
+    remove_position!(app, dex, 0, "alice").unwrap();
+    let positions = app
+        .get_pool_positions(dex.as_str(), &pool_key, None, None)
+        .unwrap();
+    assert_eq!(positions.len(), 2);
+    assert!(positions
+        .iter()
+        .all(|position| position.token_id != removed_token_id));
+    let moved = positions
+        .iter()
+        .find(|position| position.token_id == moved_token_id)
+        .unwrap();
+    assert_eq!(moved.owner, Addr::unchecked("alice"));
+    assert_eq!(moved.position.lower_tick_index, -30);
+    assert_eq!(
+        app.get_token_id(dex.as_str(), "alice", 0).unwrap(),
+        moved_token_id
+    );
+    let bob = positions
+        .iter()
+        .find(|position| position.token_id == bob_token_id)
+        .unwrap();
+    assert_eq!(bob.owner, Addr::unchecked("bob"));
+    assert_eq!(bob.position.lower_tick_index, -40);
+
+    // transfers keep the token id
+    let transferred_token_id = app.get_token_id(dex.as_str(), "alice", 1).unwrap();
+    transfer_position!(app, dex, 1, "bob", "alice").unwrap();
+    let other_positions = app
+        .get_pool_positions(dex.as_str(), &other_pool_key, None, None)
+        .unwrap();
+    assert_eq!(other_positions.len(), 1);
+    assert_eq!(other_positions[0].token_id, transferred_token_id);
+    assert_eq!(other_positions[0].owner, Addr::unchecked("bob"));
+    assert_eq!(other_positions[0].position.lower_tick_index, -20);
+}
@@ -2,9 +2,10 @@ use decimal::*;
 
 use crate::{
     percentage::Percentage,
+    sqrt_price::SqrtPrice,
     tests::helper::{macros::*, MockApp},
     token_amount::TokenAmount,
-    FeeTier, PoolKey,
+    FeeTier, PoolKey, MIN_SQRT_PRICE,
 };
 
 #[test]
@@ -102,3 +103,65 @@ fn test_withdraw_fee_not_deployer() {
         TokenAmount::new(0)
     );
 }
+
+#[test]
+fn test_pool_protocol_fee_override() {
+    let mut app = MockApp::new(&[("alice", &[])]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+    init_basic_pool!(app, dex, token_x, token_y);
+    init_basic_position!(app, dex, token_x, token_y);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x.to_string(), token_y.to_string(), fee_tier).unwrap();
+
+    change_pool_protocol_fee!(app, dex, pool_key, Some(Percentage::new(0)), "bob").unwrap_err();
+    change_pool_protocol_fee!(
+        app,
+        dex,
+        pool_key,
+        Some(Percentage::from_integer(2)),
+        "alice"
+    )
+    .unwrap_err();
+    change_pool_protocol_fee!(app, dex, pool_key, Some(Percentage::new(0)), "alice").unwrap();
+
+    let pool = get_pool!(app, dex, token_x, token_y, fee_tier).unwrap();
+    assert_eq!(pool.protocol_fee, Some(Percentage::new(0)));
+
+    let amount = 1000;
+    mint!(app, token_x, "bob", amount * 2, "alice").unwrap();
+    approve!(app, token_x, dex, amount * 2, "bob").unwrap();
+    swap!(
+        app,
+        dex,
+        pool_key,
+        true,
+        TokenAmount(amount),
+        true,
+        SqrtPrice::new(MIN_SQRT_PRICE),
+        "bob"
+    )
+    .unwrap();
+
+    // the whole fee goes to the liquidity providers
+    let pool = get_pool!(app, dex, token_x, token_y, fee_tier).unwrap();
+    assert_eq!(pool.fee_protocol_token_x, TokenAmount::new(0));
+
+    // clearing the override restores the global protocol fee
+    change_pool_protocol_fee!(app, dex, pool_key, None, "alice").unwrap();
+    swap!(
+        app,
+        dex,
+        pool_key,
+        true,
+        TokenAmount(amount),
+        true,
+        SqrtPrice::new(MIN_SQRT_PRICE),
+        "bob"
+    )
+    .unwrap();
+
+    let pool = get_pool!(app, dex, token_x, token_y, fee_tier).unwrap();
+    assert_eq!(pool.protocol_fee, None);
+    assert_eq!(pool.fee_protocol_token_x, TokenAmount::new(1));
+}
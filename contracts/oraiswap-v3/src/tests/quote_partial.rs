@@ -0,0 +1,92 @@
+use decimal::*;
+
+use crate::{
+    interface::PartialFillReason,
+    percentage::Percentage,
+    sqrt_price::{calculate_sqrt_price, SqrtPrice},
+    tests::helper::{macros::*, MockApp},
+    token_amount::TokenAmount,
+    FeeTier, PoolKey, MIN_SQRT_PRICE,
+};
+
+#[test]
+fn test_quote_partial_full_fill() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+    init_basic_pool!(app, dex, token_x, token_y);
+    init_basic_position!(app, dex, token_x, token_y);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x.to_string(), token_y.to_string(), fee_tier).unwrap();
+
+    let amount = TokenAmount::new(100);
+    let slippage = SqrtPrice::new(MIN_SQRT_PRICE);
+    let quote_result = quote!(app, dex, pool_key, true, amount, true, slippage).unwrap();
+    let partial_result = app
+        .quote_partial(dex.as_str(), &pool_key, true, amount, true, slippage)
+        .unwrap();
+
+    assert_eq!(partial_result.partial_fill, None);
+    assert_eq!(partial_result, quote_result);
+}
+
+#[test]
+fn test_quote_partial_price_limit() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+    init_basic_pool!(app, dex, token_x, token_y);
+    init_basic_position!(app, dex, token_x, token_y);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x.to_string(), token_y.to_string(), fee_tier).unwrap();
+
+    let amount = TokenAmount::new(10000);
+    let limit = calculate_sqrt_price(-10).unwrap();
+    quote!(app, dex, pool_key, true, amount, true, limit).unwrap_err();
+
+    let partial_result = app
+        .quote_partial(dex.as_str(), &pool_key, true, amount, true, limit)
+        .unwrap();
+    assert_eq!(
+        partial_result.partial_fill,
+        Some(PartialFillReason::PriceLimit)
+    );
+    assert_eq!(partial_result.target_sqrt_price, limit);
+    assert!(partial_result.amount_in < amount);
+    assert!(!partial_result.amount_out.is_zero());
+
+    // the reported amount is executable with the same limit
+    let amount_in = partial_result.amount_in;
+    let quote_result = quote!(app, dex, pool_key, true, amount_in, true, limit).unwrap();
+    assert!(quote_result.amount_out <= partial_result.amount_out);
+}
+
+#[test]
+fn test_quote_partial_liquidity_exhausted() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+    init_basic_pool!(app, dex, token_x, token_y);
+    init_basic_position!(app, dex, token_x, token_y);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x.to_string(), token_y.to_string(), fee_tier).unwrap();
+
+    let amount = TokenAmount::new(10000);
+    let slippage = SqrtPrice::new(MIN_SQRT_PRICE);
+    quote!(app, dex, pool_key, true, amount, true, slippage).unwrap_err();
+
+    let partial_result = app
+        .quote_partial(dex.as_str(), &pool_key, true, amount, true, slippage)
+        .unwrap();
+    assert_eq!(
+        partial_result.partial_fill,
+        Some(PartialFillReason::LiquidityExhausted)
+    );
+    assert!(partial_result.amount_in < amount);
+
+    // the position is drained of token y
+    let dex_balance = TokenAmount::new(balance_of!(app, token_y, dex));
+    assert!(partial_result.amount_out <= dex_balance);
+    assert!(dex_balance - partial_result.amount_out <= TokenAmount::new(1));
+    assert_eq!(partial_result.ticks.len(), 1);
+}
@@ -0,0 +1,273 @@
+use decimal::*;
+
+use crate::{
+    interface::SwapHop,
+    liquidity::Liquidity,
+    msg::Cw20HookMsg,
+    percentage::Percentage,
+    sqrt_price::{calculate_sqrt_price, SqrtPrice},
+    tests::helper::{macros::*, MockApp},
+    token_amount::TokenAmount,
+    FeeTier, PoolKey, MIN_SQRT_PRICE,
+};
+
+#[test]
+fn test_receive_swap() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+    init_basic_pool!(app, dex, token_x, token_y);
+    init_basic_position!(app, dex, token_x, token_y);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x.to_string(), token_y.to_string(), fee_tier).unwrap();
+
+    // no allowance is needed, the tokens are sent along with the hook
+    let amount = 1000;
+    mint!(app, token_x, "bob", amount, "alice").unwrap();
+
+    send_token!(
+        app,
+        token_x,
+        dex,
+        amount,
+        Cw20HookMsg::Swap {
+            pool_key: pool_key.clone(),
+            x_to_y: true,
+            amount: TokenAmount(amount),
+            by_amount_in: true,
+            sqrt_price_limit: SqrtPrice::new(MIN_SQRT_PRICE),
+        },
+        "bob"
+    )
+    .unwrap();
+
+    assert_eq!(balance_of!(app, token_x, "bob"), 0);
+    assert_eq!(balance_of!(app, token_y, "bob"), 993);
+    assert_eq!(balance_of!(app, token_x, dex), 1500);
+    assert_eq!(balance_of!(app, token_y, dex), 7);
+
+    let pool = get_pool!(app, dex, token_x, token_y, fee_tier).unwrap();
+    assert_eq!(pool.current_tick_index, -20);
+}
+
+#[test]
+fn test_receive_swap_refunds_unspent() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+    init_basic_pool!(app, dex, token_x, token_y);
+    init_basic_position!(app, dex, token_x, token_y);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x.to_string(), token_y.to_string(), fee_tier).unwrap();
+
+    let amount = 1000;
+    mint!(app, token_x, "bob", amount, "alice").unwrap();
+
+    let amount_out = 100;
+    send_token!(
+        app,
+        token_x,
+        dex,
+        amount,
+        Cw20HookMsg::Swap {
+            pool_key: pool_key.clone(),
+            x_to_y: true,
+            amount: TokenAmount(amount_out),
+            by_amount_in: false,
+            sqrt_price_limit: SqrtPrice::new(MIN_SQRT_PRICE),
+        },
+        "bob"
+    )
+    .unwrap();
+
+    let bob_x = balance_of!(app, token_x, "bob");
+    assert!(bob_x > 0);
+    assert_eq!(balance_of!(app, token_y, "bob"), amount_out);
+    assert_eq!(balance_of!(app, token_x, dex), 500 + amount - bob_x);
+}
+
+#[test]
+fn test_receive_swap_wrong_token() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+    init_basic_pool!(app, dex, token_x, token_y);
+    init_basic_position!(app, dex, token_x, token_y);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x.to_string(), token_y.to_string(), fee_tier).unwrap();
+
+    let amount = 1000;
+    mint!(app, token_y, "bob", amount, "alice").unwrap();
+
+    let err = send_token!(
+        app,
+        token_y,
+        dex,
+        amount,
+        Cw20HookMsg::Swap {
+            pool_key,
+            x_to_y: true,
+            amount: TokenAmount(amount),
+            by_amount_in: true,
+            sqrt_price_limit: SqrtPrice::new(MIN_SQRT_PRICE),
+        },
+        "bob"
+    )
+    .unwrap_err();
+    assert!(err.contains("error executing WasmMsg"));
+
+    assert_eq!(balance_of!(app, token_y, "bob"), amount);
+}
+
+#[test]
+fn test_receive_swap_route() {
+    let protocol_fee = Percentage::from_scale(6, 3);
+    let initial_amount = 10u128.pow(10);
+    let mut app = MockApp::new(&[]);
+    let dex = create_dex!(app, protocol_fee);
+
+    let (token_x, token_y, token_z) =
+        create_3_tokens!(app, initial_amount, initial_amount, initial_amount);
+
+    approve!(app, token_x, dex, initial_amount, "alice").unwrap();
+    approve!(app, token_y, dex, initial_amount, "alice").unwrap();
+    approve!(app, token_z, dex, initial_amount, "alice").unwrap();
+
+    let fee_tier = FeeTier::new(protocol_fee, 1).unwrap();
+    add_fee_tier!(app, dex, fee_tier, "alice").unwrap();
+
+    let init_tick = 0;
+    let init_sqrt_price = calculate_sqrt_price(init_tick).unwrap();
+    create_pool!(
+        app,
+        dex,
+        token_x,
+        token_y,
+        fee_tier,
+        init_sqrt_price,
+        init_tick,
+        "alice"
+    )
+    .unwrap();
+    create_pool!(
+        app,
+        dex,
+        token_y,
+        token_z,
+        fee_tier,
+        init_sqrt_price,
+        init_tick,
+        "alice"
+    )
+    .unwrap();
+
+    let pool_key_1 = PoolKey::new(token_x.to_string(), token_y.to_string(), fee_tier).unwrap();
+    let pool_key_2 = PoolKey::new(token_y.to_string(), token_z.to_string(), fee_tier).unwrap();
+
+    let liquidity_delta = Liquidity::new(2u128.pow(63) - 1);
+    for pool_key in [pool_key_1.clone(), pool_key_2.clone()] {
+        create_position!(
+            app,
+            dex,
+            pool_key,
+            -1,
+            1,
+            liquidity_delta,
+            init_sqrt_price,
+            init_sqrt_price,
+            "alice"
+        )
+        .unwrap();
+    }
+
+    let amount = 1000;
+    mint!(app, token_x, "bob", amount, "alice").unwrap();
+
+    let swaps = vec![
+        SwapHop {
+            pool_key: pool_key_1,
+            x_to_y: true,
+        },
+        SwapHop {
+            pool_key: pool_key_2,
+            x_to_y: true,
+        },
+    ];
+    let expected_amount_out = quote_route!(app, dex, TokenAmount(amount), swaps.clone()).unwrap();
+
+    send_token!(
+        app,
+        token_x,
+        dex,
+        amount,
+        Cw20HookMsg::SwapRoute {
+            expected_amount_out,
+            slippage: Percentage::new(0),
+            swaps,
+        },
+        "bob"
+    )
+    .unwrap();
+
+    assert_eq!(balance_of!(app, token_x, "bob"), 0);
+    assert_eq!(balance_of!(app, token_y, "bob"), 0);
+    assert_eq!(balance_of!(app, token_z, "bob"), expected_amount_out.get());
+}
+
+#[test]
+fn test_receive_create_position() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+    init_basic_pool!(app, dex, token_x, token_y);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x.to_string(), token_y.to_string(), fee_tier).unwrap();
+    let pool = get_pool!(app, dex, token_x, token_y, fee_tier).unwrap();
+
+    // token x is sent with the hook, token y is still pulled with an allowance
+    approve!(app, token_y, dex, 10u128.pow(10), "alice").unwrap();
+    let user_x_before = balance_of!(app, token_x, "alice");
+
+    send_token!(
+        app,
+        token_x,
+        dex,
+        600,
+        Cw20HookMsg::CreatePosition {
+            pool_key: pool_key.clone(),
+            lower_tick: -20,
+            upper_tick: 10,
+            liquidity_delta: Liquidity::from_integer(1000000),
+            slippage_limit_lower: pool.sqrt_price,
+            slippage_limit_upper: pool.sqrt_price,
+        },
+        "alice"
+    )
+    .unwrap();
+
+    let positions = get_all_positions!(app, dex, "alice");
+    assert_eq!(positions.len(), 1);
+    assert_eq!(positions[0].liquidity, Liquidity::from_integer(1000000));
+
+    assert_eq!(balance_of!(app, token_x, dex), 500);
+    assert_eq!(balance_of!(app, token_y, dex), 1000);
+    assert_eq!(user_x_before - balance_of!(app, token_x, "alice"), 500);
+
+    // the sent amount has to cover the position
+    send_token!(
+        app,
+        token_x,
+        dex,
+        100,
+        Cw20HookMsg::CreatePosition {
+            pool_key,
+            lower_tick: -20,
+            upper_tick: 10,
+            liquidity_delta: Liquidity::from_integer(1000000),
+            slippage_limit_lower: pool.sqrt_price,
+            slippage_limit_upper: pool.sqrt_price,
+        },
+        "alice"
+    )
+    .unwrap_err();
+}
@@ -0,0 +1,70 @@
+use decimal::*;
+
+use crate::{
+    liquidity::Liquidity,
+    percentage::Percentage,
+    seconds_per_liquidity::SecondsPerLiquidity,
+    tests::helper::{macros::*, MockApp},
+    FeeTier, PoolKey,
+};
+
+#[test]
+fn test_position_seconds_inside_in_range() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+    init_basic_pool!(app, dex, token_x, token_y);
+    init_basic_position!(app, dex, token_x, token_y);
+
+    let before = position_seconds_inside!(app, dex, 0, "alice").unwrap();
+
+    app.increase_block_time(100);
+
+    let after = position_seconds_inside!(app, dex, 0, "alice").unwrap();
+    let position = get_position!(app, dex, 0, "alice").unwrap();
+    assert_eq!(position.liquidity, Liquidity::from_integer(1000000));
+
+    // 100 seconds in range over 1000000 units of liquidity
+    assert_eq!(
+        after.unchecked_sub(before),
+        SecondsPerLiquidity::from_scale(1, 4)
+    );
+}
+
+#[test]
+fn test_position_seconds_inside_out_of_range() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+    init_basic_pool!(app, dex, token_x, token_y);
+    init_basic_position!(app, dex, token_x, token_y);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x.to_string(), token_y.to_string(), fee_tier).unwrap();
+    let pool = get_pool!(app, dex, token_x, token_y, fee_tier).unwrap();
+
+    // the range sits above the current tick
+    create_position!(
+        app,
+        dex,
+        pool_key,
+        10,
+        20,
+        Liquidity::from_integer(1000000),
+        pool.sqrt_price,
+        pool.sqrt_price,
+        "alice"
+    )
+    .unwrap();
+
+    app.increase_block_time(100);
+
+    let pool = get_pool!(app, dex, token_x, token_y, fee_tier).unwrap();
+    assert!(pool.seconds_per_liquidity_global > SecondsPerLiquidity::new(0));
+
+    let in_range = position_seconds_inside!(app, dex, 0, "alice").unwrap();
+    let out_of_range = position_seconds_inside!(app, dex, 1, "alice").unwrap();
+    assert!(in_range > SecondsPerLiquidity::new(0));
+    assert_eq!(out_of_range, SecondsPerLiquidity::new(0));
+
+    // the position has to exist
+    position_seconds_inside!(app, dex, 2, "alice").unwrap_err();
+}
@@ -0,0 +1,110 @@
+use cosmwasm_std::Event;
+use decimal::*;
+
+use crate::{
+    percentage::Percentage,
+    sqrt_price::calculate_sqrt_price,
+    tests::helper::{macros::*, MockApp},
+    token_amount::TokenAmount,
+    FeeTier, PoolKey,
+};
+
+fn swap_attribute<'a>(events: &'a [Event], key: &str) -> Option<&'a str> {
+    events
+        .iter()
+        .find(|event| event.ty == "wasm-swap")
+        .and_then(|event| event.attributes.iter().find(|attr| attr.key == key))
+        .map(|attr| attr.value.as_str())
+}
+
+#[test]
+fn test_swap_partial_stops_at_price_limit() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+    init_basic_pool!(app, dex, token_x, token_y);
+    init_basic_position!(app, dex, token_x, token_y);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x.to_string(), token_y.to_string(), fee_tier).unwrap();
+
+    let amount = 10000;
+    mint!(app, token_x, "bob", amount, "alice").unwrap();
+    approve!(app, token_x, dex, amount, "bob").unwrap();
+
+    let swap_amount = TokenAmount::new(amount);
+    let limit = calculate_sqrt_price(-10).unwrap();
+
+    // without partial fills the swap fails at the limit
+    swap!(app, dex, pool_key, true, swap_amount, true, limit, "bob").unwrap_err();
+
+    let quote_result = app
+        .quote_partial(dex.as_str(), &pool_key, true, swap_amount, true, limit)
+        .unwrap();
+    let dex_x_before = balance_of!(app, token_x, dex);
+    let dex_y_before = balance_of!(app, token_y, dex);
+
+    let result = app
+        .swap_partial(
+            "bob",
+            dex.as_str(),
+            &pool_key,
+            true,
+            swap_amount,
+            true,
+            limit,
+        )
+        .unwrap();
+
+    let pool = get_pool!(app, dex, token_x, token_y, fee_tier).unwrap();
+    assert_eq!(pool.sqrt_price, limit);
+
+    // only the consumed input is pulled from bob
+    let amount_in = quote_result.amount_in.get();
+    let amount_out = quote_result.amount_out.get();
+    assert!(amount_in < amount);
+    assert_eq!(balance_of!(app, token_x, "bob"), amount - amount_in);
+    assert_eq!(balance_of!(app, token_y, "bob"), amount_out);
+    assert_eq!(balance_of!(app, token_x, dex), dex_x_before + amount_in);
+    assert_eq!(balance_of!(app, token_y, dex), dex_y_before - amount_out);
+
+    assert_eq!(
+        swap_attribute(&result.events, "amount_in"),
+        Some(amount_in.to_string().as_str())
+    );
+    assert_eq!(
+        swap_attribute(&result.events, "partial_fill"),
+        Some("price_limit")
+    );
+}
+
+#[test]
+fn test_swap_partial_full_fill() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+    init_basic_pool!(app, dex, token_x, token_y);
+    init_basic_position!(app, dex, token_x, token_y);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x.to_string(), token_y.to_string(), fee_tier).unwrap();
+
+    let amount = 100;
+    mint!(app, token_x, "bob", amount, "alice").unwrap();
+    approve!(app, token_x, dex, amount, "bob").unwrap();
+
+    let limit = calculate_sqrt_price(-10).unwrap();
+    let result = app
+        .swap_partial(
+            "bob",
+            dex.as_str(),
+            &pool_key,
+            true,
+            TokenAmount::new(amount),
+            true,
+            limit,
+        )
+        .unwrap();
+
+    assert_eq!(balance_of!(app, token_x, "bob"), 0);
+    assert_eq!(swap_attribute(&result.events, "amount_in"), Some("100"));
+    assert_eq!(swap_attribute(&result.events, "partial_fill"), None);
+}
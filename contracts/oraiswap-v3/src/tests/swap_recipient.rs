@@ -0,0 +1,195 @@
+use decimal::*;
+
+use crate::{
+    interface::SwapHop,
+    liquidity::Liquidity,
+    percentage::Percentage,
+    sqrt_price::{calculate_sqrt_price, SqrtPrice},
+    tests::helper::{macros::*, MockApp},
+    token_amount::TokenAmount,
+    FeeTier, PoolKey, MIN_SQRT_PRICE,
+};
+
+#[test]
+fn test_swap_to_recipient() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+    init_basic_pool!(app, dex, token_x, token_y);
+    init_basic_position!(app, dex, token_x, token_y);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x.to_string(), token_y.to_string(), fee_tier).unwrap();
+
+    let amount = 1000;
+    mint!(app, token_x, "bob", amount, "alice").unwrap();
+    approve!(app, token_x, dex, amount, "bob").unwrap();
+
+    let deadline = app.block_time().plus_seconds(60);
+    swap_to!(
+        app,
+        dex,
+        pool_key,
+        true,
+        TokenAmount(amount),
+        true,
+        SqrtPrice::new(MIN_SQRT_PRICE),
+        Some("carol"),
+        Some(deadline),
+        "bob"
+    )
+    .unwrap();
+
+    assert_eq!(balance_of!(app, token_x, "bob"), 0);
+    assert_eq!(balance_of!(app, token_y, "bob"), 0);
+    assert_eq!(balance_of!(app, token_y, "carol"), 993);
+}
+
+#[test]
+fn test_swap_deadline_exceeded() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+    init_basic_pool!(app, dex, token_x, token_y);
+    init_basic_position!(app, dex, token_x, token_y);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x.to_string(), token_y.to_string(), fee_tier).unwrap();
+
+    let amount = 1000;
+    mint!(app, token_x, "bob", amount, "alice").unwrap();
+    approve!(app, token_x, dex, amount, "bob").unwrap();
+
+    let deadline = app.block_time().minus_seconds(1);
+    swap_to!(
+        app,
+        dex,
+        pool_key,
+        true,
+        TokenAmount(amount),
+        true,
+        SqrtPrice::new(MIN_SQRT_PRICE),
+        None,
+        Some(deadline),
+        "bob"
+    )
+    .unwrap_err();
+
+    assert_eq!(balance_of!(app, token_x, "bob"), amount);
+    let pool = get_pool!(app, dex, token_x, token_y, fee_tier).unwrap();
+    assert_eq!(pool.current_tick_index, 0);
+}
+
+#[test]
+fn test_swap_route_to_recipient_and_deadline() {
+    let protocol_fee = Percentage::from_scale(6, 3);
+    let initial_amount = 10u128.pow(10);
+    let mut app = MockApp::new(&[]);
+    let dex = create_dex!(app, protocol_fee);
+
+    let (token_x, token_y, token_z) =
+        create_3_tokens!(app, initial_amount, initial_amount, initial_amount);
+
+    approve!(app, token_x, dex, initial_amount, "alice").unwrap();
+    approve!(app, token_y, dex, initial_amount, "alice").unwrap();
+    approve!(app, token_z, dex, initial_amount, "alice").unwrap();
+
+    let amount = 1000;
+    mint!(app, token_x, "bob", amount, "alice").unwrap();
+    approve!(app, token_x, dex, amount, "bob").unwrap();
+    approve!(app, token_y, dex, initial_amount, "bob").unwrap();
+
+    let fee_tier = FeeTier::new(protocol_fee, 1).unwrap();
+    add_fee_tier!(app, dex, fee_tier, "alice").unwrap();
+
+    let init_tick = 0;
+    let init_sqrt_price = calculate_sqrt_price(init_tick).unwrap();
+    create_pool!(
+        app,
+        dex,
+        token_x,
+        token_y,
+        fee_tier,
+        init_sqrt_price,
+        init_tick,
+        "alice"
+    )
+    .unwrap();
+    create_pool!(
+        app,
+        dex,
+        token_y,
+        token_z,
+        fee_tier,
+        init_sqrt_price,
+        init_tick,
+        "alice"
+    )
+    .unwrap();
+
+    let pool_key_1 = PoolKey::new(token_x.to_string(), token_y.to_string(), fee_tier).unwrap();
+    let pool_key_2 = PoolKey::new(token_y.to_string(), token_z.to_string(), fee_tier).unwrap();
+
+    let liquidity_delta = Liquidity::new(2u128.pow(63) - 1);
+    for pool_key in [pool_key_1.clone(), pool_key_2.clone()] {
+        create_position!(
+            app,
+            dex,
+            pool_key,
+            -1,
+            1,
+            liquidity_delta,
+            init_sqrt_price,
+            init_sqrt_price,
+            "alice"
+        )
+        .unwrap();
+    }
+
+    let amount_in = TokenAmount(amount);
+    let slippage = Percentage::new(0);
+    let swaps = vec![
+        SwapHop {
+            pool_key: pool_key_1,
+            x_to_y: true,
+        },
+        SwapHop {
+            pool_key: pool_key_2,
+            x_to_y: true,
+        },
+    ];
+    let expected_amount_out = quote_route!(app, dex, amount_in, swaps.clone()).unwrap();
+
+    let stale_deadline = app.block_time().minus_seconds(1);
+    swap_route_to!(
+        app,
+        dex,
+        amount_in,
+        expected_amount_out,
+        slippage,
+        swaps.clone(),
+        Some("carol"),
+        Some(stale_deadline),
+        "bob"
+    )
+    .unwrap_err();
+
+    swap_route_to!(
+        app,
+        dex,
+        amount_in,
+        expected_amount_out,
+        slippage,
+        swaps,
+        Some("carol"),
+        None,
+        "bob"
+    )
+    .unwrap();
+
+    assert_eq!(balance_of!(app, token_x, "bob"), 0);
+    assert_eq!(balance_of!(app, token_y, "bob"), 0);
+    assert_eq!(balance_of!(app, token_z, "bob"), 0);
+    assert_eq!(
+        balance_of!(app, token_z, "carol"),
+        expected_amount_out.get()
+    );
+}
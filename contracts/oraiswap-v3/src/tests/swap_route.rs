@@ -153,3 +153,198 @@ fn swap_route() {
     assert_eq!(alice_amount_y_after - alice_amount_y_before, 4);
     assert_eq!(alice_amount_z_after - alice_amount_z_before, 0);
 }
+
+#[test]
+fn swap_route_settles_only_first_and_last_token() {
+    let protocol_fee = Percentage::from_scale(6, 3);
+    let initial_amount = 10u128.pow(10);
+    let mut app = MockApp::new(&[]);
+    let dex = create_dex!(app, protocol_fee);
+
+    let (token_x, token_y, token_z) =
+        create_3_tokens!(app, initial_amount, initial_amount, initial_amount);
+
+    approve!(app, token_x, dex, initial_amount, "alice").unwrap();
+    approve!(app, token_y, dex, initial_amount, "alice").unwrap();
+    approve!(app, token_z, dex, initial_amount, "alice").unwrap();
+
+    // no allowance on the intermediate token
+    let amount = 1000;
+    mint!(app, token_x, "bob", amount, "alice").unwrap();
+    approve!(app, token_x, dex, amount, "bob").unwrap();
+
+    let fee_tier = FeeTier::new(protocol_fee, 1).unwrap();
+    add_fee_tier!(app, dex, fee_tier, "alice").unwrap();
+
+    let init_tick = 0;
+    let init_sqrt_price = calculate_sqrt_price(init_tick).unwrap();
+    create_pool!(
+        app,
+        dex,
+        token_x,
+        token_y,
+        fee_tier,
+        init_sqrt_price,
+        init_tick,
+        "alice"
+    )
+    .unwrap();
+    create_pool!(
+        app,
+        dex,
+        token_y,
+        token_z,
+        fee_tier,
+        init_sqrt_price,
+        init_tick,
+        "alice"
+    )
+    .unwrap();
+
+    let pool_key_1 = PoolKey::new(token_x.to_string(), token_y.to_string(), fee_tier).unwrap();
+    let pool_key_2 = PoolKey::new(token_y.to_string(), token_z.to_string(), fee_tier).unwrap();
+
+    let liquidity_delta = Liquidity::new(2u128.pow(63) - 1);
+    for pool_key in [pool_key_1.clone(), pool_key_2.clone()] {
+        create_position!(
+            app,
+            dex,
+            pool_key,
+            -1,
+            1,
+            liquidity_delta,
+            init_sqrt_price,
+            init_sqrt_price,
+            "alice"
+        )
+        .unwrap();
+    }
+
+    let dex_y_before = balance_of!(app, token_y, dex);
+
+    let swaps = vec![
+        SwapHop {
+            pool_key: pool_key_1,
+            x_to_y: true,
+        },
+        SwapHop {
+            pool_key: pool_key_2,
+            x_to_y: true,
+        },
+    ];
+    let res = swap_route!(
+        app,
+        dex,
+        TokenAmount(amount),
+        TokenAmount(986),
+        Percentage::new(0),
+        swaps,
+        "bob"
+    )
+    .unwrap();
+
+    let count_actions = |action: &str| {
+        res.events
+            .iter()
+            .filter(|event| {
+                event
+                    .attributes
+                    .iter()
+                    .any(|attr| attr.key == "action" && attr.value == action)
+            })
+            .count()
+    };
+    assert_eq!(count_actions("transfer_from"), 1);
+    assert_eq!(count_actions("transfer"), 1);
+
+    assert_eq!(balance_of!(app, token_x, "bob"), 0);
+    assert_eq!(balance_of!(app, token_y, "bob"), 0);
+    assert_eq!(balance_of!(app, token_z, "bob"), 986);
+    assert_eq!(balance_of!(app, token_y, dex), dex_y_before);
+}
+
+#[test]
+fn swap_route_rejects_broken_hop_chain() {
+    let protocol_fee = Percentage::from_scale(6, 3);
+    let initial_amount = 10u128.pow(10);
+    let mut app = MockApp::new(&[]);
+    let dex = create_dex!(app, protocol_fee);
+
+    let (token_x, token_y, token_z) =
+        create_3_tokens!(app, initial_amount, initial_amount, initial_amount);
+
+    approve!(app, token_x, dex, initial_amount, "alice").unwrap();
+    approve!(app, token_y, dex, initial_amount, "alice").unwrap();
+    approve!(app, token_z, dex, initial_amount, "alice").unwrap();
+
+    let amount = 1000;
+    mint!(app, token_x, "bob", amount, "alice").unwrap();
+    approve!(app, token_x, dex, amount, "bob").unwrap();
+
+    let fee_tier = FeeTier::new(protocol_fee, 1).unwrap();
+    add_fee_tier!(app, dex, fee_tier, "alice").unwrap();
+
+    let init_sqrt_price = calculate_sqrt_price(0).unwrap();
+    let liquidity_delta = Liquidity::new(2u128.pow(63) - 1);
+    for (token_0, token_1) in [(&token_x, &token_y), (&token_y, &token_z)] {
+        create_pool!(
+            app,
+            dex,
+            token_0,
+            token_1,
+            fee_tier,
+            init_sqrt_price,
+            0,
+            "alice"
+        )
+        .unwrap();
+        let pool_key = PoolKey::new(token_0.to_string(), token_1.to_string(), fee_tier).unwrap();
+        create_position!(
+            app,
+            dex,
+            pool_key,
+            -1,
+            1,
+            liquidity_delta,
+            init_sqrt_price,
+            init_sqrt_price,
+            "alice"
+        )
+        .unwrap();
+    }
+
+    let pool_key_1 = PoolKey::new(token_x.to_string(), token_y.to_string(), fee_tier).unwrap();
+    let pool_key_2 = PoolKey::new(token_y.to_string(), token_z.to_string(), fee_tier).unwrap();
+
+    // the second hop pays in token z that the first hop never paid out
+    let swaps = vec![
+        SwapHop {
+            pool_key: pool_key_1.clone(),
+            x_to_y: pool_key_1.token_x == token_x.as_str(),
+        },
+        SwapHop {
+            pool_key: pool_key_2.clone(),
+            x_to_y: pool_key_2.token_x == token_z.as_str(),
+        },
+    ];
+    assert_eq!(swaps[1].token_in(), token_z.as_str());
+
+    let dex_y_before = balance_of!(app, token_y, dex);
+    let dex_z_before = balance_of!(app, token_z, dex);
+
+    quote_route!(app, dex, TokenAmount(amount), swaps.clone()).unwrap_err();
+    swap_route!(
+        app,
+        dex,
+        TokenAmount(amount),
+        TokenAmount(0),
+        Percentage::new(0),
+        swaps,
+        "bob"
+    )
+    .unwrap_err();
+
+    assert_eq!(balance_of!(app, token_x, "bob"), amount);
+    assert_eq!(balance_of!(app, token_y, dex), dex_y_before);
+    assert_eq!(balance_of!(app, token_z, dex), dex_z_before);
+}
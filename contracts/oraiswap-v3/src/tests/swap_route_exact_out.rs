@@ -0,0 +1,159 @@
+use cosmwasm_std::Addr;
+use decimal::*;
+
+use crate::{
+    interface::SwapHop,
+    liquidity::Liquidity,
+    percentage::Percentage,
+    sqrt_price::calculate_sqrt_price,
+    tests::helper::{macros::*, MockApp},
+    token_amount::TokenAmount,
+    FeeTier, PoolKey,
+};
+
+fn init_route(app: &mut MockApp) -> (Addr, Addr, Addr, Addr, Vec<SwapHop>) {
+    let protocol_fee = Percentage::from_scale(6, 3);
+    let initial_amount = 10u128.pow(10);
+    let dex = create_dex!(app, protocol_fee);
+
+    let (token_x, token_y, token_z) =
+        create_3_tokens!(app, initial_amount, initial_amount, initial_amount);
+
+    approve!(app, token_x, dex, initial_amount, "alice").unwrap();
+    approve!(app, token_y, dex, initial_amount, "alice").unwrap();
+    approve!(app, token_z, dex, initial_amount, "alice").unwrap();
+
+    let fee_tier = FeeTier::new(protocol_fee, 1).unwrap();
+    add_fee_tier!(app, dex, fee_tier, "alice").unwrap();
+
+    let init_tick = 0;
+    let init_sqrt_price = calculate_sqrt_price(init_tick).unwrap();
+    create_pool!(
+        app,
+        dex,
+        token_x,
+        token_y,
+        fee_tier,
+        init_sqrt_price,
+        init_tick,
+        "alice"
+    )
+    .unwrap();
+    create_pool!(
+        app,
+        dex,
+        token_y,
+        token_z,
+        fee_tier,
+        init_sqrt_price,
+        init_tick,
+        "alice"
+    )
+    .unwrap();
+
+    let pool_key_1 = PoolKey::new(token_x.to_string(), token_y.to_string(), fee_tier).unwrap();
+    let pool_key_2 = PoolKey::new(token_y.to_string(), token_z.to_string(), fee_tier).unwrap();
+
+    let liquidity_delta = Liquidity::new(2u128.pow(63) - 1);
+    for pool_key in [pool_key_1.clone(), pool_key_2.clone()] {
+        create_position!(
+            app,
+            dex,
+            pool_key,
+            -1,
+            1,
+            liquidity_delta,
+            init_sqrt_price,
+            init_sqrt_price,
+            "alice"
+        )
+        .unwrap();
+    }
+
+    let swaps = vec![
+        SwapHop {
+            pool_key: pool_key_1,
+            x_to_y: true,
+        },
+        SwapHop {
+            pool_key: pool_key_2,
+            x_to_y: true,
+        },
+    ];
+
+    (dex, token_x, token_y, token_z, swaps)
+}
+
+#[test]
+fn swap_route_exact_out() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, token_y, token_z, swaps) = init_route(&mut app);
+
+    let amount_out = TokenAmount(1000);
+    let amount_in = quote_route_exact_out!(app, dex, amount_out, swaps.clone()).unwrap();
+    assert!(amount_in > amount_out);
+
+    // the exact-in quote for the required input delivers at least the requested output
+    let quoted_out = quote_route!(app, dex, amount_in, swaps.clone()).unwrap();
+    assert!(quoted_out >= amount_out);
+
+    mint!(app, token_x, "bob", amount_in.get(), "alice").unwrap();
+    approve!(app, token_x, dex, amount_in.get(), "bob").unwrap();
+
+    swap_route_exact_out!(app, dex, amount_out, amount_in, swaps, "bob").unwrap();
+
+    assert_eq!(balance_of!(app, token_x, "bob"), 0);
+    assert_eq!(balance_of!(app, token_y, "bob"), 0);
+    assert_eq!(balance_of!(app, token_z, "bob"), amount_out.get());
+}
+
+#[test]
+fn swap_route_exact_out_over_max_amount_in() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, _, token_z, swaps) = init_route(&mut app);
+
+    let amount_out = TokenAmount(1000);
+    let amount_in = quote_route_exact_out!(app, dex, amount_out, swaps.clone()).unwrap();
+
+    mint!(app, token_x, "bob", amount_in.get(), "alice").unwrap();
+    approve!(app, token_x, dex, amount_in.get(), "bob").unwrap();
+
+    swap_route_exact_out!(
+        app,
+        dex,
+        amount_out,
+        amount_in - TokenAmount(1),
+        swaps.clone(),
+        "bob"
+    )
+    .unwrap_err();
+
+    swap_route_exact_out!(app, dex, amount_out, amount_in, vec![], "bob").unwrap_err();
+
+    assert_eq!(balance_of!(app, token_x, "bob"), amount_in.get());
+    assert_eq!(balance_of!(app, token_z, "bob"), 0);
+}
+
+#[test]
+fn swap_route_exact_out_rejects_invalid_routes() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, token_y, token_z, mut swaps) = init_route(&mut app);
+
+    let amount_out = TokenAmount(1000);
+    quote_route_exact_out!(app, dex, amount_out, vec![]).unwrap_err();
+
+    // the second hop pays in token z that the first hop never paid out
+    swaps[1].x_to_y = !swaps[1].x_to_y;
+    assert_eq!(swaps[1].token_in(), token_z.as_str());
+    quote_route_exact_out!(app, dex, amount_out, swaps.clone()).unwrap_err();
+
+    let max_amount_in = TokenAmount(10000);
+    mint!(app, token_x, "bob", max_amount_in.get(), "alice").unwrap();
+    approve!(app, token_x, dex, max_amount_in.get(), "bob").unwrap();
+
+    let dex_y_before = balance_of!(app, token_y, dex);
+    swap_route_exact_out!(app, dex, amount_out, max_amount_in, swaps, "bob").unwrap_err();
+
+    assert_eq!(balance_of!(app, token_x, "bob"), max_amount_in.get());
+    assert_eq!(balance_of!(app, token_y, dex), dex_y_before);
+}
@@ -0,0 +1,272 @@
+use decimal::*;
+
+use crate::{
+    interface::SwapHop,
+    liquidity::Liquidity,
+    percentage::Percentage,
+    sqrt_price::calculate_sqrt_price,
+    state::MAX_SPLIT_ROUTES,
+    tests::helper::{macros::*, MockApp},
+    token_amount::TokenAmount,
+    FeeTier, PoolKey,
+};
+
+#[test]
+fn test_swap_split() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let other_fee_tier = FeeTier::new(Percentage::from_scale(1, 2), 10).unwrap();
+    let mint_amount = 10u128.pow(10);
+    approve!(app, token_x, dex, mint_amount, "alice").unwrap();
+    approve!(app, token_y, dex, mint_amount, "alice").unwrap();
+
+    let init_sqrt_price = calculate_sqrt_price(0).unwrap();
+    let mut routes = vec![];
+    for fee_tier in [fee_tier, other_fee_tier] {
+        add_fee_tier!(app, dex, fee_tier, "alice").unwrap();
+        create_pool!(
+            app,
+            dex,
+            token_x,
+            token_y,
+            fee_tier,
+            init_sqrt_price,
+            0,
+            "alice"
+        )
+        .unwrap();
+        let pool_key = PoolKey::new(token_x.to_string(), token_y.to_string(), fee_tier).unwrap();
+        create_position!(
+            app,
+            dex,
+            pool_key,
+            -1000,
+            1000,
+            Liquidity::from_integer(10000),
+            init_sqrt_price,
+            init_sqrt_price,
+            "alice"
+        )
+        .unwrap();
+        routes.push(vec![SwapHop {
+            pool_key,
+            x_to_y: true,
+        }]);
+    }
+
+    let amount = 200;
+    mint!(app, token_x, "bob", amount, "alice").unwrap();
+    approve!(app, token_x, dex, amount, "bob").unwrap();
+
+    // the price impact makes both pools worth using despite the higher fee of the second one
+    let amount_in = TokenAmount::new(amount);
+    let split = app
+        .quote_swap_split(dex.as_str(), amount_in, routes.clone(), 10)
+        .unwrap();
+    assert_eq!(split.legs.len(), 2);
+    assert_eq!(split.legs[0].1 + split.legs[1].1, amount_in);
+    assert!(split.legs[0].1 > split.legs[1].1);
+    for swaps in &routes {
+        let single = quote_route!(app, dex, amount_in, swaps.clone()).unwrap();
+        assert!(split.amount_out > single);
+    }
+
+    // the output is short of an unreachable minimum
+    app.swap_split(
+        "bob",
+        dex.as_str(),
+        split.legs.clone(),
+        split.amount_out + TokenAmount::new(1),
+    )
+    .unwrap_err();
+
+    app.swap_split("bob", dex.as_str(), split.legs.clone(), split.amount_out)
+        .unwrap();
+    assert_eq!(balance_of!(app, token_x, "bob"), 0);
+    assert_eq!(balance_of!(app, token_y, "bob"), split.amount_out.get());
+}
+
+#[test]
+fn test_swap_split_rejects_mismatched_legs() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+    init_basic_pool!(app, dex, token_x, token_y);
+    init_basic_position!(app, dex, token_x, token_y);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x.to_string(), token_y.to_string(), fee_tier).unwrap();
+
+    let amount = 100;
+    mint!(app, token_x, "bob", amount, "alice").unwrap();
+    approve!(app, token_x, dex, amount, "bob").unwrap();
+
+    let x_to_y = vec![SwapHop {
+        pool_key: pool_key.clone(),
+        x_to_y: true,
+    }];
+    let y_to_x = vec![SwapHop {
+        pool_key,
+        x_to_y: false,
+    }];
+    let leg_amount = TokenAmount::new(amount / 2);
+
+    app.swap_split(
+        "bob",
+        dex.as_str(),
+        vec![(x_to_y.clone(), leg_amount), (y_to_x.clone(), leg_amount)],
+        TokenAmount::new(0),
+    )
+    .unwrap_err();
+    app.swap_split("bob", dex.as_str(), vec![], TokenAmount::new(0))
+        .unwrap_err();
+    app.quote_swap_split(
+        dex.as_str(),
+        TokenAmount::new(amount),
+        vec![x_to_y.clone(), y_to_x],
+        10,
+    )
+    .unwrap_err();
+
+    // legs may go through the same pool
+    app.swap_split(
+        "bob",
+        dex.as_str(),
+        vec![(x_to_y.clone(), leg_amount), (x_to_y, leg_amount)],
+        TokenAmount::new(0),
+    )
+    .unwrap();
+    assert_eq!(balance_of!(app, token_x, "bob"), 0);
+}
+
+#[test]
+fn test_swap_split_route_limit() {
+    let mut app = MockApp::new(&[]);
+    let (dex, token_x, token_y) = init_dex_and_tokens!(app);
+    init_basic_pool!(app, dex, token_x, token_y);
+    init_basic_position!(app, dex, token_x, token_y);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x.to_string(), token_y.to_string(), fee_tier).unwrap();
+
+    let leg_amount = TokenAmount::new(10);
+    let amount = leg_amount.get() * (MAX_SPLIT_ROUTES as u128 + 1);
+    mint!(app, token_x, "bob", amount, "alice").unwrap();
+    approve!(app, token_x, dex, amount, "bob").unwrap();
+
+    let x_to_y = vec![SwapHop {
+        pool_key,
+        x_to_y: true,
+    }];
+
+    let too_many = vec![x_to_y.clone(); MAX_SPLIT_ROUTES + 1];
+    app.quote_swap_split(dex.as_str(), TokenAmount::new(amount), too_many.clone(), 10)
+        .unwrap_err();
+    app.swap_split(
+        "bob",
+        dex.as_str(),
+        too_many
+            .into_iter()
+            .map(|swaps| (swaps, leg_amount))
+            .collect(),
+        TokenAmount::new(0),
+    )
+    .unwrap_err();
+    assert_eq!(balance_of!(app, token_x, "bob"), amount);
+
+    app.swap_split(
+        "bob",
+        dex.as_str(),
+        vec![(x_to_y, leg_amount); MAX_SPLIT_ROUTES],
+        TokenAmount::new(0),
+    )
+    .unwrap();
+    assert_eq!(balance_of!(app, token_x, "bob"), leg_amount.get());
+}
+
+#[test]
+fn test_swap_split_rejects_broken_leg() {
+    let protocol_fee = Percentage::from_scale(6, 3);
+    let initial_amount = 10u128.pow(10);
+    let mut app = MockApp::new(&[]);
+    let dex = create_dex!(app, protocol_fee);
+
+    let (token_x, token_y, token_z) =
+        create_3_tokens!(app, initial_amount, initial_amount, initial_amount);
+
+    approve!(app, token_x, dex, initial_amount, "alice").unwrap();
+    approve!(app, token_y, dex, initial_amount, "alice").unwrap();
+    approve!(app, token_z, dex, initial_amount, "alice").unwrap();
+
+    let fee_tier = FeeTier::new(protocol_fee, 1).unwrap();
+    add_fee_tier!(app, dex, fee_tier, "alice").unwrap();
+
+    let init_sqrt_price = calculate_sqrt_price(0).unwrap();
+    let liquidity_delta = Liquidity::new(2u128.pow(63) - 1);
+    for (token_0, token_1) in [(&token_x, &token_y), (&token_y, &token_z)] {
+        create_pool!(
+            app,
+            dex,
+            token_0,
+            token_1,
+            fee_tier,
+            init_sqrt_price,
+            0,
+            "alice"
+        )
+        .unwrap();
+        let pool_key = PoolKey::new(token_0.to_string(), token_1.to_string(), fee_tier).unwrap();
+        create_position!(
+            app,
+            dex,
+            pool_key,
+            -1,
+            1,
+            liquidity_delta,
+            init_sqrt_price,
+            init_sqrt_price,
+            "alice"
+        )
+        .unwrap();
+    }
+
+    let amount = 1000;
+    mint!(app, token_x, "bob", amount, "alice").unwrap();
+    approve!(app, token_x, dex, amount, "bob").unwrap();
+
+    let pool_key_xy = PoolKey::new(token_x.to_string(), token_y.to_string(), fee_tier).unwrap();
+    let pool_key_yz = PoolKey::new(token_y.to_string(), token_z.to_string(), fee_tier).unwrap();
+    let x_to_y = SwapHop {
+        pool_key: pool_key_xy.clone(),
+        x_to_y: pool_key_xy.token_x == token_x.as_str(),
+    };
+    // starts and ends like the valid leg, but its second hop pays in token z
+    let z_to_y = SwapHop {
+        pool_key: pool_key_yz.clone(),
+        x_to_y: pool_key_yz.token_x == token_z.as_str(),
+    };
+    let valid_leg = vec![x_to_y.clone()];
+    let broken_leg = vec![x_to_y, z_to_y];
+    let leg_amount = TokenAmount::new(amount / 2);
+
+    app.quote_swap_split(
+        dex.as_str(),
+        TokenAmount::new(amount),
+        vec![valid_leg.clone(), broken_leg.clone()],
+        10,
+    )
+    .unwrap_err();
+
+    let dex_z_before = balance_of!(app, token_z, dex);
+    app.swap_split(
+        "bob",
+        dex.as_str(),
+        vec![(valid_leg, leg_amount), (broken_leg, leg_amount)],
+        TokenAmount::new(0),
+    )
+    .unwrap_err();
+
+    assert_eq!(balance_of!(app, token_x, "bob"), amount);
+    assert_eq!(balance_of!(app, token_z, dex), dex_z_before);
+}